@@ -30,6 +30,7 @@ export default {
     'tests/embedding/cloud-embedding-engine.test.ts',
     'tests/services/background-update-queue.test.ts',
     'tests/performance/benchmark.test.ts',
+    'tests/performance/bm25-search-benchmark.test.ts',
     'tests/services/incremental-update.test.ts',
     'tests/config/config-manager.test.ts',
     'tests/config/setup-wizard.test.ts',
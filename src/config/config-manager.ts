@@ -6,10 +6,25 @@ import {
   Mode,
   VectorStoreBackend,
   EmbeddingProvider,
+  FusionStrategy,
+  SearchConfig,
 } from './types.js';
 import { ConfigValidationError } from '../utils/errors.js';
 import { logger } from '../utils/logger.js';
 
+/**
+ * ハイブリッド検索で使用するBM25の重み（α）を求める
+ *
+ * ベクトル検索の重みは1-αとなるため、bm25WeightとvectorWeightの合計が1.0でない場合は
+ * 両者の比で正規化する。
+ * @param search 検索設定（未指定の場合はデフォルト設定）
+ * @returns BM25の重み（0-1）
+ */
+export function resolveBm25Weight(search: SearchConfig | undefined): number {
+  const { bm25Weight, vectorWeight } = search ?? DEFAULT_CONFIG.search!;
+  return bm25Weight / (bm25Weight + vectorWeight);
+}
+
 /**
  * 設定ファイル管理クラス
  * .lsp-mcp.jsonの読み込み、バリデーション、デフォルト値の提供を行う
@@ -105,12 +120,19 @@ export class ConfigManager {
     }
 
     // vectorStore.backend のバリデーション
-    const validBackends: VectorStoreBackend[] = ['milvus', 'zilliz', 'qdrant'];
+    const validBackends: VectorStoreBackend[] = [
+      'milvus',
+      'zilliz',
+      'qdrant',
+      'chroma',
+      'embedded',
+      'memory',
+    ];
     if (!validBackends.includes(config.vectorStore.backend)) {
       throw new ConfigValidationError(
         `無効なベクターDBバックエンドです: ${config.vectorStore.backend}。有効な値: ${validBackends.join(', ')}`,
         undefined,
-        'ローカル実行の場合は"milvus"（Docker必要）または"chroma"（軽量）、クラウド連携の場合は"zilliz"または"qdrant"を選択してください。'
+        'ローカル実行の場合は"milvus"（Docker必要）、"chroma"（既存のChromaサーバー）、"embedded"（外部サービス不要）または"memory"（永続化なしの一時利用）、クラウド連携の場合は"zilliz"または"qdrant"を選択してください。'
       );
     }
 
@@ -124,12 +146,50 @@ export class ConfigManager {
       );
     }
 
-    // search weights のバリデーション（警告のみ）
+    // search.fusion のバリデーション
+    const validFusionStrategies: FusionStrategy[] = ['linear', 'rrf', 'zscore'];
+    if (
+      config.search?.fusion !== undefined &&
+      !validFusionStrategies.includes(config.search.fusion)
+    ) {
+      throw new ConfigValidationError(
+        `無効なスコア統合方式です: ${config.search.fusion}。有効な値: ${validFusionStrategies.join(', ')}`,
+        undefined,
+        '"search.fusion"に"linear"（重み付き線形結合）、"rrf"（順位ベース）、"zscore"（Zスコア標準化）のいずれかを設定してください。'
+      );
+    }
+    if (config.search?.rrfK !== undefined && !(config.search.rrfK > 0)) {
+      throw new ConfigValidationError(
+        `search.rrfK は正の数である必要があります（現在: ${config.search.rrfK}）`,
+        undefined,
+        '"search.rrfK"には正の数を設定してください（一般的な値: 60）。'
+      );
+    }
+
+    // search weights のバリデーション（範囲外はエラー、合計が1.0でない場合は正規化して警告）
     if (config.search) {
+      for (const key of ['bm25Weight', 'vectorWeight'] as const) {
+        const weight = config.search[key];
+        if (typeof weight !== 'number' || !(weight >= 0 && weight <= 1)) {
+          throw new ConfigValidationError(
+            `search.${key} は0から1の数値である必要があります（現在: ${weight}）`,
+            undefined,
+            `"search.${key}"には0から1の数値を設定してください（デフォルト: bm25Weight=0.3, vectorWeight=0.7）。`
+          );
+        }
+      }
+
       const totalWeight = config.search.bm25Weight + config.search.vectorWeight;
+      if (totalWeight === 0) {
+        throw new ConfigValidationError(
+          'search.bm25Weight と search.vectorWeight の両方が0です',
+          undefined,
+          '"search.bm25Weight"と"search.vectorWeight"の少なくとも一方に正の値を設定してください。'
+        );
+      }
       if (Math.abs(totalWeight - 1.0) > 0.001) {
         logger.warn(
-          `検索スコアの重み合計が1.0ではありません（現在: ${totalWeight}）。bm25Weight=${config.search.bm25Weight}, vectorWeight=${config.search.vectorWeight}`
+          `検索スコアの重み合計が1.0ではありません（現在: ${totalWeight}）。bm25Weight=${config.search.bm25Weight}, vectorWeight=${config.search.vectorWeight}。合計が1.0になるよう正規化して使用します（BM25の重み: ${resolveBm25Weight(config.search)}）`
         );
       }
     }
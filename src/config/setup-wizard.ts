@@ -34,7 +34,7 @@ export interface SetupOptions {
 /**
  * プリセット名
  */
-export type PresetName = 'quickstart' | 'performance' | 'cloud';
+export type PresetName = 'quickstart' | 'performance' | 'chroma' | 'cloud';
 
 /**
  * 保存オプション
@@ -134,6 +134,29 @@ export class SetupWizard {
           },
         };
 
+      case 'chroma':
+        return {
+          backend: 'chroma' as const,
+          config: {
+            address: vectorAddress || 'localhost:8000',
+            ...(vectorToken ? { token: vectorToken } : {}),
+          },
+        };
+
+      case 'embedded':
+        return {
+          backend: 'embedded' as const,
+          config: {
+            path: './tmp/vectors.db',
+          },
+        };
+
+      case 'memory':
+        return {
+          backend: 'memory' as const,
+          config: {},
+        };
+
       default:
         throw new Error(`未対応のベクターDBバックエンドです: ${vectorBackend as string}`);
     }
@@ -198,7 +221,14 @@ export class SetupWizard {
       throw new Error(`無効なモードです: ${options.mode}`);
     }
 
-    const validBackends: VectorStoreBackend[] = ['milvus', 'zilliz', 'qdrant'];
+    const validBackends: VectorStoreBackend[] = [
+      'milvus',
+      'zilliz',
+      'qdrant',
+      'chroma',
+      'embedded',
+      'memory',
+    ];
     if (!validBackends.includes(options.vectorBackend)) {
       throw new Error(`無効なベクターDBバックエンドです: ${options.vectorBackend}`);
     }
@@ -264,7 +294,7 @@ export class SetupWizard {
    * プリセットを使用して設定を生成
    *
    * @param preset プリセット名
-   * @param cloudOptions クラウドプリセット用のオプション
+   * @param cloudOptions クラウド・Chromaプリセット用のオプション（接続先・認証情報）
    * @returns 生成された設定
    */
   usePreset(preset: PresetName, cloudOptions?: Partial<SetupOptions>): LspMcpConfig {
@@ -285,6 +315,16 @@ export class SetupWizard {
           embeddingProvider: 'transformers',
         });
 
+      case 'chroma':
+        // 既存のChromaサーバーを使用（アドレス省略時はlocalhost:8000）
+        return this.generateConfig({
+          mode: 'local',
+          vectorBackend: 'chroma',
+          embeddingProvider: 'transformers',
+          vectorAddress: cloudOptions?.vectorAddress,
+          vectorToken: cloudOptions?.vectorToken,
+        });
+
       case 'cloud':
         // クラウドモード
         if (
@@ -11,7 +11,7 @@ export type Mode = 'local' | 'cloud';
 /**
  * ベクターDBバックエンド
  */
-export type VectorStoreBackend = 'milvus' | 'zilliz' | 'qdrant';
+export type VectorStoreBackend = 'milvus' | 'zilliz' | 'qdrant' | 'chroma' | 'embedded' | 'memory';
 
 /**
  * 埋め込みプロバイダー
@@ -50,12 +50,27 @@ export interface PrivacyConfig {
   blockExternalCalls: boolean;
 }
 
+/**
+ * スコア統合方式
+ *
+ * - linear: Min-Max正規化したスコアをαで線形結合（従来方式）
+ * - rrf: 各リストの順位から Reciprocal Rank Fusion（1/(k+rank)）で統合
+ * - zscore: 各リストのスコアをZスコアで標準化してαで線形結合
+ */
+export type FusionStrategy = 'linear' | 'rrf' | 'zscore';
+
 /**
  * 検索設定
  */
 export interface SearchConfig {
+  /** BM25スコアの重み（0-1） */
   bm25Weight: number;
+  /** ベクトル検索スコアの重み（0-1、合計が1でない場合はbm25Weightとの比で正規化） */
   vectorWeight: number;
+  /** BM25とベクトル検索のスコア統合方式（linear / rrf / zscore） */
+  fusion?: FusionStrategy;
+  /** RRFの定数k（fusionがrrfの場合に使用） */
+  rrfK?: number;
 }
 
 /**
@@ -122,6 +137,8 @@ export const DEFAULT_CONFIG: LspMcpConfig = {
   search: {
     bm25Weight: 0.3,
     vectorWeight: 0.7,
+    fusion: 'linear',
+    rrfK: 60,
   },
   indexing: {
     languages: ['typescript', 'javascript', 'python', 'go', 'rust', 'java', 'c', 'cpp'],
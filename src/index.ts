@@ -3,12 +3,18 @@
  * with Tree-sitter AST parsing and vector database
  */
 
+import * as path from 'path';
 import { MCPServer } from './server/mcp-server.js';
 import { logger, LogLevel } from './utils/logger.js';
-import { ConfigManager } from './config/config-manager.js';
+import { ConfigManager, resolveBm25Weight } from './config/config-manager.js';
 import { LocalEmbeddingEngine } from './embedding/local-embedding-engine.js';
 import { CloudEmbeddingEngine } from './embedding/cloud-embedding-engine.js';
 import { MilvusPlugin } from './storage/milvus-plugin.js';
+import { EmbeddedPlugin } from './storage/embedded-plugin.js';
+import { MemoryPlugin } from './storage/memory-plugin.js';
+import { QdrantPlugin } from './storage/qdrant-plugin.js';
+import { ChromaPlugin } from './storage/chroma-plugin.js';
+import { VectorStorePluginRegistry } from './storage/types.js';
 import { BM25Engine } from './storage/bm25-engine.js';
 import { FileScanner } from './scanner/file-scanner.js';
 import { SymbolExtractor } from './parser/symbol-extractor.js';
@@ -71,17 +77,30 @@ export async function main(): Promise<void> {
 
     // 3. ベクターストアを初期化
     logger.info(`Initializing vector store: ${config.vectorStore.backend}`);
-    if (config.vectorStore.backend === 'milvus' || config.vectorStore.backend === 'zilliz') {
-      vectorStore = new MilvusPlugin();
-      await vectorStore.connect(config.vectorStore);
-      logger.info('Vector store connected');
-    } else {
+    const bm25DbPath = './tmp/bm25.db';
+    const registry = new VectorStorePluginRegistry();
+    registry.register(new MilvusPlugin());
+    registry.register(new EmbeddedPlugin());
+    registry.register(new QdrantPlugin());
+    registry.register(new ChromaPlugin());
+    registry.register(new MemoryPlugin());
+
+    // Zilliz CloudはMilvusプラグインで接続する
+    const pluginName =
+      config.vectorStore.backend === 'zilliz' ? 'milvus' : config.vectorStore.backend;
+    if (!registry.has(pluginName)) {
       throw new Error(`Unknown vector store backend: ${config.vectorStore.backend}`);
     }
+    vectorStore = registry.get(pluginName);
+    if (config.vectorStore.backend === 'embedded' && !config.vectorStore.config.path) {
+      // 組み込みストアはBM25のDBと同じディレクトリに保存する
+      config.vectorStore.config.path = path.join(path.dirname(bm25DbPath), 'vectors.db');
+    }
+    await vectorStore.connect(config.vectorStore);
+    logger.info('Vector store connected');
 
     // 4. BM25エンジンを初期化
     logger.info('Initializing BM25 engine...');
-    const bm25DbPath = './tmp/bm25.db';
     bm25Engine = new BM25Engine(bm25DbPath);
     await bm25Engine.initialize();
     logger.info('BM25 engine initialized');
@@ -110,10 +129,22 @@ export async function main(): Promise<void> {
     );
     logger.info('Indexing service created');
 
+    // 前回のセッションでインデックス化したプロジェクトの統計を復元
+    await indexingService.loadIndexMetadata();
+    const restoredProjects = await indexingService.getAllIndexStats();
+    logger.info(`Restored ${restoredProjects.length} indexed project(s) from registry`);
+
     // 7. Hybrid Search Engineを作成
     logger.info('Creating hybrid search engine...');
-    hybridSearchEngine = new HybridSearchEngine(bm25Engine, vectorStore, 0.3);
-    logger.info('Hybrid search engine created');
+    hybridSearchEngine = new HybridSearchEngine(
+      bm25Engine,
+      vectorStore,
+      resolveBm25Weight(config.search),
+      { strategy: config.search?.fusion, rrfK: config.search?.rrfK }
+    );
+    logger.info(
+      `Hybrid search engine created (fusion: ${hybridSearchEngine.getFusionOptions().strategy})`
+    );
 
     // 8. MCPサーバーを起動
     logger.info('Starting MCP server...');
@@ -132,6 +163,7 @@ export async function main(): Promise<void> {
     process.on('SIGINT', async () => {
       logger.info('Received SIGINT, shutting down...');
       await server.shutdown();
+      if (indexingService) await indexingService.disableAllWatchers();
       if (vectorStore) await vectorStore.disconnect();
       if (bm25Engine) await bm25Engine.close();
       process.exit(0);
@@ -140,6 +172,7 @@ export async function main(): Promise<void> {
     process.on('SIGTERM', async () => {
       logger.info('Received SIGTERM, shutting down...');
       await server.shutdown();
+      if (indexingService) await indexingService.disableAllWatchers();
       if (vectorStore) await vectorStore.disconnect();
       if (bm25Engine) await bm25Engine.close();
       process.exit(0);
@@ -0,0 +1,119 @@
+/**
+ * Code Chunker: シンボルを抽出できないソースコードを行範囲のチャンクに分割する
+ */
+
+/**
+ * チャンク分割オプション
+ */
+export interface CodeChunkerOptions {
+  /** 1チャンクあたりの行数（デフォルト: 40） */
+  chunkLines?: number;
+  /** 隣接チャンク間で重複させる行数（デフォルト: 10） */
+  overlapLines?: number;
+}
+
+/**
+ * コードチャンクの情報
+ */
+export interface CodeChunk {
+  text: string; // チャンク内容
+  startLine: number; // 開始行番号（0始まり）
+  endLine: number; // 終了行番号（0始まり、終端を含む）
+}
+
+/**
+ * 行範囲
+ */
+export interface LineRange {
+  startLine: number; // 開始行番号（0始まり）
+  endLine: number; // 終了行番号（0始まり、終端を含む）
+}
+
+/**
+ * Code Chunker
+ *
+ * スライディングウィンドウで重複する行範囲のチャンクを生成します。
+ * 空白行のみのチャンクは生成しません。
+ */
+export class CodeChunker {
+  private chunkLines: number;
+  private overlapLines: number;
+
+  constructor(options: CodeChunkerOptions = {}) {
+    this.chunkLines = Math.max(1, options.chunkLines ?? 40);
+    // 重複行数はチャンク行数未満に制限（ウィンドウが必ず前進するように）
+    this.overlapLines = Math.min(Math.max(0, options.overlapLines ?? 10), this.chunkLines - 1);
+  }
+
+  /**
+   * ソースコードをチャンクに分割
+   * @param content ソースコード
+   * @returns チャンクの配列
+   */
+  chunk(content: string): CodeChunk[] {
+    const chunks: CodeChunk[] = [];
+    const lines = content.split('\n');
+    const step = this.chunkLines - this.overlapLines;
+
+    for (let start = 0; start < lines.length; start += step) {
+      const end = Math.min(start + this.chunkLines, lines.length) - 1;
+      const text = lines.slice(start, end + 1).join('\n');
+
+      if (text.trim().length > 0) {
+        chunks.push({ text, startLine: start, endLine: end });
+      }
+
+      // 最終行に到達したら終了（末尾だけの小さな重複チャンクを作らない）
+      if (end >= lines.length - 1) {
+        break;
+      }
+    }
+
+    return chunks;
+  }
+
+  /**
+   * 指定した行範囲に含まれない行をチャンクに分割
+   *
+   * シンボルの定義範囲外にあるトップレベルのコード等を検索対象にするために使用します。
+   * 範囲外の連続する行ごとに`chunk`と同じ規則で分割します。
+   *
+   * @param content ソースコード
+   * @param covered 除外する行範囲（0始まり、終端を含む）
+   * @returns チャンクの配列（行番号はファイル全体での行番号）
+   */
+  chunkUncovered(content: string, covered: LineRange[]): CodeChunk[] {
+    const lines = content.split('\n');
+    const isCovered = new Array<boolean>(lines.length).fill(false);
+    for (const range of covered) {
+      for (let line = Math.max(0, range.startLine); line <= range.endLine; line++) {
+        if (line >= lines.length) {
+          break;
+        }
+        isCovered[line] = true;
+      }
+    }
+
+    const chunks: CodeChunk[] = [];
+    for (let start = 0; start < lines.length; start++) {
+      if (isCovered[start]) {
+        continue;
+      }
+      let end = start;
+      while (end + 1 < lines.length && !isCovered[end + 1]) {
+        end++;
+      }
+
+      for (const chunk of this.chunk(lines.slice(start, end + 1).join('\n'))) {
+        chunks.push({
+          text: chunk.text,
+          startLine: chunk.startLine + start,
+          endLine: chunk.endLine + start,
+        });
+      }
+      start = end;
+    }
+
+    return chunks;
+  }
+}
@@ -6,6 +6,7 @@ export { SymbolExtractor } from './symbol-extractor.js';
 export { CommentExtractor } from './comment-extractor.js';
 export { MarkdownParser } from './markdown-parser.js';
 export { DocCodeLinker } from './doc-code-linker.js';
+export { CodeChunker } from './code-chunker.js';
 export { Language, SymbolType, SymbolScope, CommentType, CommentMarker } from './types.js';
 export type {
   ParseResult,
@@ -37,3 +38,4 @@ export type {
   RelatedScoreResult,
   CodeFileInfo,
 } from './doc-code-linker.js';
+export type { CodeChunkerOptions, CodeChunk } from './code-chunker.js';
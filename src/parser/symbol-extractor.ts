@@ -5,6 +5,7 @@
 import Parser from 'tree-sitter';
 import { LanguageParser } from './language-parser.js';
 import { ASTEngine } from './ast-engine.js';
+import { CommentExtractor } from './comment-extractor.js';
 import {
   Language,
   SymbolInfo,
@@ -13,6 +14,8 @@ import {
   SymbolExtractionResult,
   ParameterInfo,
   ParserError,
+  CommentInfo,
+  CommentType,
 } from './types.js';
 
 /**
@@ -20,9 +23,11 @@ import {
  */
 export class SymbolExtractor {
   private astEngine: ASTEngine;
+  private commentExtractor: CommentExtractor;
 
   constructor(languageParser: LanguageParser) {
     this.astEngine = new ASTEngine(languageParser);
+    this.commentExtractor = new CommentExtractor(languageParser);
   }
 
   /**
@@ -69,10 +74,12 @@ export class SymbolExtractor {
 
         case Language.Rust:
           this.extractRustSymbols(astResult.rootNode, code, symbols);
+          this.attachDocstrings(symbols, code, language);
           break;
 
         case Language.Java:
           this.extractJavaSymbols(astResult.rootNode, code, symbols);
+          this.attachDocstrings(symbols, code, language);
           break;
 
         default:
@@ -1088,26 +1095,912 @@ export class SymbolExtractor {
   }
 
   /**
-   * Rustのシンボル抽出（基本実装）
+   * Rustのシンボル抽出
    */
   private extractRustSymbols(
-    _rootNode: Parser.SyntaxNode,
+    rootNode: Parser.SyntaxNode,
+    _code: string,
+    symbols: SymbolInfo[]
+  ): void {
+    const implNodes: Parser.SyntaxNode[] = [];
+
+    this.astEngine.traverseAST(rootNode, (node) => {
+      try {
+        // impl/traitブロック内のアイテムはメンバーとして別途抽出する
+        if (this.isRustAssociatedItem(node)) {
+          return true;
+        }
+
+        // implブロック（型定義がimplより後に現れる場合があるため後で処理）
+        if (node.type === 'impl_item') {
+          implNodes.push(node);
+        }
+
+        // 関数
+        if (node.type === 'function_item') {
+          this.extractRustFunction(node, _code, symbols, this.getRustScope(node));
+        }
+
+        // 構造体
+        if (node.type === 'struct_item') {
+          this.extractRustStruct(node, _code, symbols);
+        }
+
+        // Enum
+        if (node.type === 'enum_item') {
+          this.extractRustEnum(node, _code, symbols);
+        }
+
+        // トレイト
+        if (node.type === 'trait_item') {
+          this.extractRustTrait(node, _code, symbols);
+        }
+
+        // 定数・static変数
+        if (node.type === 'const_item' || node.type === 'static_item') {
+          this.extractRustConstant(node, _code, symbols);
+        }
+
+        // 型エイリアス
+        if (node.type === 'type_item') {
+          this.extractRustTypeAlias(node, _code, symbols);
+        }
+
+        // マクロ定義（macro_rules!）
+        if (node.type === 'macro_definition') {
+          this.extractRustMacro(node, _code, symbols);
+        }
+
+        // モジュール
+        if (node.type === 'mod_item') {
+          this.extractRustModule(node, _code, symbols);
+        }
+      } catch (error) {
+        // エラーがあっても処理を継続
+      }
+
+      return true;
+    });
+
+    for (const implNode of implNodes) {
+      try {
+        this.extractRustImpl(implNode, _code, symbols);
+      } catch (error) {
+        // エラーがあっても処理を継続
+      }
+    }
+  }
+
+  /**
+   * Rust関数抽出
+   */
+  private extractRustFunction(
+    node: Parser.SyntaxNode,
+    _code: string,
+    symbols: SymbolInfo[],
+    scope: SymbolScope
+  ): void {
+    const nameNode = node.childForFieldName('name');
+    if (!nameNode) return;
+
+    const name = nameNode.text;
+    const parameters = this.extractRustParameters(node);
+    const returnTypeNode = node.childForFieldName('return_type');
+
+    // async fnの確認
+    const modifiers = node.children.find((c) => c.type === 'function_modifiers');
+    const isAsync = modifiers?.children.some((c) => c.text === 'async') ?? false;
+
+    // selfを受け取らない関連関数はstaticとして扱う
+    const hasSelf =
+      node.childForFieldName('parameters')?.children.some((c) => c.type === 'self_parameter') ??
+      false;
+
+    symbols.push({
+      name,
+      type: scope === SymbolScope.Class ? SymbolType.Method : SymbolType.Function,
+      scope,
+      position: this.astEngine.getNodePosition(node),
+      parameters,
+      returnType: returnTypeNode?.text,
+      isAsync,
+      isStatic: scope === SymbolScope.Class ? !hasSelf : undefined,
+      isAbstract: node.type === 'function_signature_item',
+      isExported: this.isRustPublic(node),
+    });
+  }
+
+  /**
+   * Rustパラメータ抽出
+   */
+  private extractRustParameters(node: Parser.SyntaxNode): ParameterInfo[] {
+    const params: ParameterInfo[] = [];
+    const paramsNode = node.childForFieldName('parameters');
+
+    if (!paramsNode) return params;
+
+    for (const child of paramsNode.children) {
+      // self/&self/&mut selfはself_parameterノードのため対象外
+      if (child.type === 'parameter') {
+        const patternNode = child.childForFieldName('pattern');
+        const typeNode = child.childForFieldName('type');
+
+        if (patternNode) {
+          params.push({
+            name: patternNode.text,
+            type: typeNode?.text,
+          });
+        }
+      }
+    }
+
+    return params;
+  }
+
+  /**
+   * Rust implブロック抽出
+   *
+   * メソッドを対象の型のメンバーとして追加し、トレイト実装の場合はトレイト名をimplementsに記録する
+   *
+   * 対象の型がファイル内で定義されていない場合（別ファイルの型、`Vec<T>`、ジェネリクスの`T`等）は
+   * 型を作らず、implブロック自体を`SymbolType.Impl`のシンボルとして登録する
+   */
+  private extractRustImpl(node: Parser.SyntaxNode, _code: string, symbols: SymbolInfo[]): void {
+    const typeNode = node.childForFieldName('type');
+    if (!typeNode) return;
+
+    const typeName = this.getRustTypeName(typeNode);
+    const traitNode = node.childForFieldName('trait');
+    const traitName = traitNode ? this.getRustTypeName(traitNode) : undefined;
+
+    const methods: SymbolInfo[] = [];
+    const bodyNode = node.childForFieldName('body');
+    if (bodyNode) {
+      for (const member of bodyNode.children) {
+        if (member.type === 'function_item') {
+          this.extractRustFunction(member, _code, methods, SymbolScope.Class);
+        }
+      }
+    }
+
+    if (traitName) {
+      for (const method of methods) {
+        method.implements = [traitName];
+      }
+    }
+
+    // 同じ型に対する複数のimplブロックは1つのシンボルにまとめる
+    const ownerTypes = [SymbolType.Struct, SymbolType.Enum, SymbolType.Impl];
+    let owner = symbols.find((s) => s.name === typeName && ownerTypes.includes(s.type));
+
+    // 型がファイル内にない場合は存在しない構造体を作らず、implブロックとして登録
+    if (!owner) {
+      owner = {
+        name: typeName,
+        type: SymbolType.Impl,
+        scope: this.getRustScope(node),
+        position: this.astEngine.getNodePosition(node),
+        valueType: typeNode.text,
+      };
+      symbols.push(owner);
+    }
+
+    if (methods.length > 0) {
+      owner.members = [...(owner.members ?? []), ...methods];
+    }
+
+    if (traitName && !owner.implements?.includes(traitName)) {
+      owner.implements = [...(owner.implements ?? []), traitName];
+    }
+  }
+
+  /**
+   * Rust型名を取得（ジェネリクス・パス・参照を除いた名前）
+   */
+  private getRustTypeName(node: Parser.SyntaxNode): string {
+    if (node.type === 'generic_type' || node.type === 'reference_type') {
+      const inner = node.childForFieldName('type');
+      return inner ? this.getRustTypeName(inner) : node.text;
+    }
+    if (node.type === 'scoped_type_identifier') {
+      return node.childForFieldName('name')?.text ?? node.text;
+    }
+    return node.text;
+  }
+
+  /**
+   * Rust構造体抽出
+   */
+  private extractRustStruct(node: Parser.SyntaxNode, _code: string, symbols: SymbolInfo[]): void {
+    const nameNode = node.childForFieldName('name');
+    if (!nameNode) return;
+
+    const name = nameNode.text;
+
+    // フィールドの抽出（タプル構造体はフィールド名がないため対象外）
+    const members: SymbolInfo[] = [];
+    const bodyNode = node.childForFieldName('body');
+
+    if (bodyNode && bodyNode.type === 'field_declaration_list') {
+      for (const field of bodyNode.children) {
+        if (field.type === 'field_declaration') {
+          this.extractRustField(field, _code, members);
+        }
+      }
+    }
+
+    symbols.push({
+      name,
+      type: SymbolType.Struct,
+      scope: this.getRustScope(node),
+      position: this.astEngine.getNodePosition(node),
+      members: members.length > 0 ? members : undefined,
+      isExported: this.isRustPublic(node),
+    });
+  }
+
+  /**
+   * Rust構造体フィールド抽出
+   */
+  private extractRustField(node: Parser.SyntaxNode, _code: string, members: SymbolInfo[]): void {
+    const nameNode = node.childForFieldName('name');
+    if (!nameNode) return;
+
+    const typeNode = node.childForFieldName('type');
+
+    members.push({
+      name: nameNode.text,
+      type: SymbolType.Variable,
+      scope: SymbolScope.Class,
+      position: this.astEngine.getNodePosition(node),
+      valueType: typeNode?.text,
+      isPrivate: !this.isRustPublic(node),
+    });
+  }
+
+  /**
+   * Rust Enum抽出
+   */
+  private extractRustEnum(node: Parser.SyntaxNode, _code: string, symbols: SymbolInfo[]): void {
+    const nameNode = node.childForFieldName('name');
+    if (!nameNode) return;
+
+    symbols.push({
+      name: nameNode.text,
+      type: SymbolType.Enum,
+      scope: this.getRustScope(node),
+      position: this.astEngine.getNodePosition(node),
+      isExported: this.isRustPublic(node),
+    });
+  }
+
+  /**
+   * Rustトレイト抽出
+   */
+  private extractRustTrait(node: Parser.SyntaxNode, _code: string, symbols: SymbolInfo[]): void {
+    const nameNode = node.childForFieldName('name');
+    if (!nameNode) return;
+
+    const name = nameNode.text;
+
+    // メソッドシグネチャとデフォルト実装の抽出
+    const members: SymbolInfo[] = [];
+    const bodyNode = node.childForFieldName('body');
+
+    if (bodyNode) {
+      for (const member of bodyNode.children) {
+        if (member.type === 'function_item' || member.type === 'function_signature_item') {
+          this.extractRustFunction(member, _code, members, SymbolScope.Class);
+        }
+      }
+    }
+
+    symbols.push({
+      name,
+      type: SymbolType.Trait,
+      scope: this.getRustScope(node),
+      position: this.astEngine.getNodePosition(node),
+      members: members.length > 0 ? members : undefined,
+      isExported: this.isRustPublic(node),
+    });
+  }
+
+  /**
+   * Rust定数・static変数抽出
+   */
+  private extractRustConstant(
+    node: Parser.SyntaxNode,
+    _code: string,
+    symbols: SymbolInfo[]
+  ): void {
+    const nameNode = node.childForFieldName('name');
+    if (!nameNode) return;
+
+    const typeNode = node.childForFieldName('type');
+    const valueNode = node.childForFieldName('value');
+
+    // static mutは変数として扱う
+    const isMutable = node.children.some((c) => c.type === 'mutable_specifier');
+
+    symbols.push({
+      name: nameNode.text,
+      type: isMutable ? SymbolType.Variable : SymbolType.Constant,
+      scope: this.getRustScope(node),
+      position: this.astEngine.getNodePosition(node),
+      valueType: typeNode?.text,
+      initialValue: valueNode?.text,
+      isStatic: node.type === 'static_item',
+      isExported: this.isRustPublic(node),
+    });
+  }
+
+  /**
+   * Rust型エイリアス抽出
+   */
+  private extractRustTypeAlias(
+    node: Parser.SyntaxNode,
+    _code: string,
+    symbols: SymbolInfo[]
+  ): void {
+    const nameNode = node.childForFieldName('name');
+    if (!nameNode) return;
+
+    const typeNode = node.childForFieldName('type');
+
+    symbols.push({
+      name: nameNode.text,
+      type: SymbolType.TypeAlias,
+      scope: this.getRustScope(node),
+      position: this.astEngine.getNodePosition(node),
+      valueType: typeNode?.text,
+      isExported: this.isRustPublic(node),
+    });
+  }
+
+  /**
+   * Rustマクロ抽出（macro_rules!）
+   */
+  private extractRustMacro(node: Parser.SyntaxNode, _code: string, symbols: SymbolInfo[]): void {
+    const nameNode = node.childForFieldName('name');
+    if (!nameNode) return;
+
+    // macro_rules!にはpubがないため、#[macro_export]属性で公開を判定
+    let isExported = false;
+    let previous = node.previousNamedSibling;
+    while (previous && previous.type === 'attribute_item') {
+      if (previous.text.includes('macro_export')) {
+        isExported = true;
+        break;
+      }
+      previous = previous.previousNamedSibling;
+    }
+
+    symbols.push({
+      name: nameNode.text,
+      type: SymbolType.Macro,
+      scope: this.getRustScope(node),
+      position: this.astEngine.getNodePosition(node),
+      isExported,
+    });
+  }
+
+  /**
+   * Rustモジュール抽出
+   */
+  private extractRustModule(node: Parser.SyntaxNode, _code: string, symbols: SymbolInfo[]): void {
+    const nameNode = node.childForFieldName('name');
+    if (!nameNode) return;
+
+    symbols.push({
+      name: nameNode.text,
+      type: SymbolType.Module,
+      scope: this.getRustScope(node),
+      position: this.astEngine.getNodePosition(node),
+      isExported: this.isRustPublic(node),
+    });
+  }
+
+  /**
+   * Rustアイテムのスコープを判定
+   */
+  private getRustScope(node: Parser.SyntaxNode): SymbolScope {
+    let current = node.parent;
+    while (current) {
+      if (current.type === 'function_item') {
+        return SymbolScope.Function;
+      }
+      if (current.type === 'mod_item') {
+        return SymbolScope.Module;
+      }
+      current = current.parent;
+    }
+    return SymbolScope.Global;
+  }
+
+  /**
+   * pub可視性（pub, pub(crate)等）を持つか確認
+   *
+   * `pub(self)`（`pub(in self)`）は非公開と同じ意味のため対象外
+   */
+  private isRustPublic(node: Parser.SyntaxNode): boolean {
+    return node.children.some(
+      (c) =>
+        c.type === 'visibility_modifier' && !/^pub\s*\(\s*(in\s+)?self\s*\)$/.test(c.text)
+    );
+  }
+
+  /**
+   * impl/traitブロック直下のアイテムか確認
+   */
+  private isRustAssociatedItem(node: Parser.SyntaxNode): boolean {
+    const parent = node.parent;
+    if (!parent || parent.type !== 'declaration_list') {
+      return false;
+    }
+    const owner = parent.parent?.type;
+    return owner === 'impl_item' || owner === 'trait_item';
+  }
+
+  /**
+   * Javaのシンボル抽出
+   */
+  private extractJavaSymbols(
+    rootNode: Parser.SyntaxNode,
     _code: string,
-    _symbols: SymbolInfo[]
+    symbols: SymbolInfo[]
   ): void {
-    // Rustの実装は将来のタスクとして予約
-    // 現時点では空の実装
+    this.astEngine.traverseAST(rootNode, (node) => {
+      try {
+        // クラス・レコード
+        if (node.type === 'class_declaration' || node.type === 'record_declaration') {
+          this.extractJavaClass(node, _code, symbols);
+        }
+
+        // インターフェース
+        if (node.type === 'interface_declaration') {
+          this.extractJavaInterface(node, _code, symbols);
+        }
+
+        // Enum
+        if (node.type === 'enum_declaration') {
+          this.extractJavaEnum(node, _code, symbols);
+        }
+
+        // アノテーション型（@interface）
+        if (node.type === 'annotation_type_declaration') {
+          this.extractJavaAnnotationType(node, _code, symbols);
+        }
+
+        // 型定義の外にあるメソッド（コード断片など）
+        if (node.type === 'method_declaration' && !this.isJavaMemberNode(node)) {
+          this.extractJavaMethod(node, _code, symbols, SymbolScope.Global);
+        }
+      } catch (error) {
+        // エラーがあっても処理を継続
+      }
+
+      return true;
+    });
   }
 
   /**
-   * Javaのシンボル抽出（基本実装）
+   * Javaクラス・レコード抽出
    */
-  private extractJavaSymbols(
-    _rootNode: Parser.SyntaxNode,
+  private extractJavaClass(node: Parser.SyntaxNode, _code: string, symbols: SymbolInfo[]): void {
+    const nameNode = node.childForFieldName('name');
+    if (!nameNode) return;
+
+    const { keywords, annotations } = this.getJavaModifiers(node);
+
+    // 継承とインターフェース実装の抽出
+    const superclassNode = node.childForFieldName('superclass');
+    const extendsClasses = superclassNode
+      ? superclassNode.namedChildren.map((c) => this.getJavaTypeName(c))
+      : [];
+    const implementsInterfaces = this.getJavaTypeList(node.childForFieldName('interfaces'));
+
+    // メンバーの抽出（レコードのコンポーネントはフィールドとして扱う）
+    const members: SymbolInfo[] = [];
+    if (node.type === 'record_declaration') {
+      const paramsNode = node.childForFieldName('parameters');
+      for (const component of paramsNode?.children ?? []) {
+        if (component.type !== 'formal_parameter') continue;
+
+        const componentName = component.childForFieldName('name');
+        if (!componentName) continue;
+
+        members.push({
+          name: componentName.text,
+          type: SymbolType.Variable,
+          scope: SymbolScope.Class,
+          position: this.astEngine.getNodePosition(component),
+          valueType: component.childForFieldName('type')?.text,
+          isPrivate: true,
+        });
+      }
+    }
+    members.push(...this.extractJavaMembers(node.childForFieldName('body'), _code));
+
+    symbols.push({
+      name: nameNode.text,
+      type: SymbolType.Class,
+      scope: this.getJavaScope(node),
+      position: this.astEngine.getNodePosition(node),
+      extends: extendsClasses.length > 0 ? extendsClasses : undefined,
+      implements: implementsInterfaces.length > 0 ? implementsInterfaces : undefined,
+      members: members.length > 0 ? members : undefined,
+      isAbstract: keywords.includes('abstract'),
+      isStatic: keywords.includes('static'),
+      isPrivate: keywords.includes('private'),
+      isExported: keywords.includes('public'),
+      annotations: annotations.length > 0 ? annotations : undefined,
+    });
+  }
+
+  /**
+   * Javaインターフェース抽出
+   */
+  private extractJavaInterface(
+    node: Parser.SyntaxNode,
+    _code: string,
+    symbols: SymbolInfo[]
+  ): void {
+    const nameNode = node.childForFieldName('name');
+    if (!nameNode) return;
+
+    const { keywords, annotations } = this.getJavaModifiers(node);
+    const extendsInterfaces = this.getJavaTypeList(
+      node.children.find((c) => c.type === 'extends_interfaces') ?? null
+    );
+    const members = this.extractJavaMembers(node.childForFieldName('body'), _code);
+
+    symbols.push({
+      name: nameNode.text,
+      type: SymbolType.Interface,
+      scope: this.getJavaScope(node),
+      position: this.astEngine.getNodePosition(node),
+      extends: extendsInterfaces.length > 0 ? extendsInterfaces : undefined,
+      members: members.length > 0 ? members : undefined,
+      isPrivate: keywords.includes('private'),
+      isExported: keywords.includes('public'),
+      annotations: annotations.length > 0 ? annotations : undefined,
+    });
+  }
+
+  /**
+   * Java Enum抽出
+   */
+  private extractJavaEnum(node: Parser.SyntaxNode, _code: string, symbols: SymbolInfo[]): void {
+    const nameNode = node.childForFieldName('name');
+    if (!nameNode) return;
+
+    const { keywords, annotations } = this.getJavaModifiers(node);
+    const implementsInterfaces = this.getJavaTypeList(node.childForFieldName('interfaces'));
+
+    // 列挙定数は定数メンバーとして扱う
+    const members: SymbolInfo[] = [];
+    const bodyNode = node.childForFieldName('body');
+    for (const constant of bodyNode?.children ?? []) {
+      if (constant.type !== 'enum_constant') continue;
+
+      const constantName = constant.childForFieldName('name');
+      if (!constantName) continue;
+
+      members.push({
+        name: constantName.text,
+        type: SymbolType.Constant,
+        scope: SymbolScope.Class,
+        position: this.astEngine.getNodePosition(constant),
+        isStatic: true,
+      });
+    }
+    members.push(...this.extractJavaMembers(bodyNode, _code));
+
+    symbols.push({
+      name: nameNode.text,
+      type: SymbolType.Enum,
+      scope: this.getJavaScope(node),
+      position: this.astEngine.getNodePosition(node),
+      implements: implementsInterfaces.length > 0 ? implementsInterfaces : undefined,
+      members: members.length > 0 ? members : undefined,
+      isPrivate: keywords.includes('private'),
+      isExported: keywords.includes('public'),
+      annotations: annotations.length > 0 ? annotations : undefined,
+    });
+  }
+
+  /**
+   * Javaアノテーション型抽出（@interface）
+   */
+  private extractJavaAnnotationType(
+    node: Parser.SyntaxNode,
+    _code: string,
+    symbols: SymbolInfo[]
+  ): void {
+    const nameNode = node.childForFieldName('name');
+    if (!nameNode) return;
+
+    const { keywords, annotations } = this.getJavaModifiers(node);
+    const members = this.extractJavaMembers(node.childForFieldName('body'), _code);
+
+    symbols.push({
+      name: nameNode.text,
+      type: SymbolType.Annotation,
+      scope: this.getJavaScope(node),
+      position: this.astEngine.getNodePosition(node),
+      members: members.length > 0 ? members : undefined,
+      isExported: keywords.includes('public'),
+      annotations: annotations.length > 0 ? annotations : undefined,
+    });
+  }
+
+  /**
+   * Java型定義本体からメンバー（メソッド、コンストラクタ、フィールド）を抽出
+   */
+  private extractJavaMembers(bodyNode: Parser.SyntaxNode | null, _code: string): SymbolInfo[] {
+    const members: SymbolInfo[] = [];
+    if (!bodyNode) return members;
+
+    for (const member of bodyNode.children) {
+      if (
+        member.type === 'method_declaration' ||
+        member.type === 'annotation_type_element_declaration'
+      ) {
+        this.extractJavaMethod(member, _code, members, SymbolScope.Class);
+      } else if (
+        member.type === 'constructor_declaration' ||
+        member.type === 'compact_constructor_declaration'
+      ) {
+        this.extractJavaConstructor(member, _code, members);
+      } else if (member.type === 'field_declaration' || member.type === 'constant_declaration') {
+        this.extractJavaField(member, _code, members);
+      } else if (member.type === 'enum_body_declarations') {
+        members.push(...this.extractJavaMembers(member, _code));
+      }
+    }
+
+    return members;
+  }
+
+  /**
+   * Javaメソッド抽出
+   */
+  private extractJavaMethod(
+    node: Parser.SyntaxNode,
+    _code: string,
+    symbols: SymbolInfo[],
+    scope: SymbolScope
+  ): void {
+    const nameNode = node.childForFieldName('name');
+    if (!nameNode) return;
+
+    const { keywords, annotations } = this.getJavaModifiers(node);
+    const returnTypeNode = node.childForFieldName('type');
+
+    // 本体を持たないメソッド（インターフェース、abstract）は抽象メソッドとして扱う
+    const isAbstract = !node.childForFieldName('body') && !keywords.includes('native');
+
+    symbols.push({
+      name: nameNode.text,
+      type: scope === SymbolScope.Class ? SymbolType.Method : SymbolType.Function,
+      scope,
+      position: this.astEngine.getNodePosition(node),
+      parameters: this.extractJavaParameters(node),
+      returnType: returnTypeNode?.text,
+      isStatic: keywords.includes('static'),
+      isPrivate: keywords.includes('private'),
+      isAbstract,
+      isExported: keywords.includes('public'),
+      annotations: annotations.length > 0 ? annotations : undefined,
+    });
+  }
+
+  /**
+   * Javaコンストラクタ抽出
+   */
+  private extractJavaConstructor(
+    node: Parser.SyntaxNode,
     _code: string,
-    _symbols: SymbolInfo[]
+    members: SymbolInfo[]
   ): void {
-    // Javaの実装は将来のタスクとして予約
-    // 現時点では空の実装
+    const nameNode = node.childForFieldName('name');
+    if (!nameNode) return;
+
+    const { keywords, annotations } = this.getJavaModifiers(node);
+
+    members.push({
+      name: nameNode.text,
+      type: SymbolType.Method,
+      scope: SymbolScope.Class,
+      position: this.astEngine.getNodePosition(node),
+      parameters: this.extractJavaParameters(node),
+      isPrivate: keywords.includes('private'),
+      isExported: keywords.includes('public'),
+      annotations: annotations.length > 0 ? annotations : undefined,
+    });
+  }
+
+  /**
+   * Javaフィールド抽出（static finalは定数として扱う）
+   */
+  private extractJavaField(node: Parser.SyntaxNode, _code: string, members: SymbolInfo[]): void {
+    const { keywords, annotations } = this.getJavaModifiers(node);
+    const typeNode = node.childForFieldName('type');
+
+    const isStatic = keywords.includes('static');
+    const isConstant =
+      node.type === 'constant_declaration' || (isStatic && keywords.includes('final'));
+
+    for (const declarator of node.children) {
+      if (declarator.type !== 'variable_declarator') continue;
+
+      const nameNode = declarator.childForFieldName('name');
+      if (!nameNode) continue;
+
+      members.push({
+        name: nameNode.text,
+        type: isConstant ? SymbolType.Constant : SymbolType.Variable,
+        scope: SymbolScope.Class,
+        position: this.astEngine.getNodePosition(node),
+        valueType: typeNode?.text,
+        initialValue: declarator.childForFieldName('value')?.text,
+        isStatic,
+        isPrivate: keywords.includes('private'),
+        isExported: keywords.includes('public'),
+        annotations: annotations.length > 0 ? annotations : undefined,
+      });
+    }
+  }
+
+  /**
+   * Javaパラメータ抽出
+   */
+  private extractJavaParameters(node: Parser.SyntaxNode): ParameterInfo[] {
+    const params: ParameterInfo[] = [];
+    const paramsNode = node.childForFieldName('parameters');
+
+    if (!paramsNode) return params;
+
+    for (const child of paramsNode.children) {
+      if (child.type === 'formal_parameter') {
+        const nameNode = child.childForFieldName('name');
+        const typeNode = child.childForFieldName('type');
+
+        if (nameNode) {
+          params.push({
+            name: nameNode.text,
+            type: typeNode?.text,
+          });
+        }
+      } else if (child.type === 'spread_parameter') {
+        // 可変長引数（String... args）
+        const declarator = child.namedChildren.find((c) => c.type === 'variable_declarator');
+        const typeNode = child.namedChildren.find(
+          (c) => c.type !== 'variable_declarator' && c.type !== 'modifiers'
+        );
+        const nameNode = declarator?.childForFieldName('name');
+
+        if (nameNode) {
+          params.push({
+            name: nameNode.text,
+            type: typeNode ? `${typeNode.text}...` : undefined,
+          });
+        }
+      }
+    }
+
+    return params;
+  }
+
+  /**
+   * Java修飾子（キーワードとアノテーション）を取得
+   */
+  private getJavaModifiers(node: Parser.SyntaxNode): { keywords: string[]; annotations: string[] } {
+    const keywords: string[] = [];
+    const annotations: string[] = [];
+    const modifiers = node.children.find((c) => c.type === 'modifiers');
+
+    for (const child of modifiers?.children ?? []) {
+      if (child.type === 'marker_annotation' || child.type === 'annotation') {
+        annotations.push(child.childForFieldName('name')?.text ?? child.text);
+      } else {
+        keywords.push(child.text);
+      }
+    }
+
+    return { keywords, annotations };
+  }
+
+  /**
+   * Java型リスト（implements/extends句）から型名を取得
+   */
+  private getJavaTypeList(node: Parser.SyntaxNode | null): string[] {
+    const typeList = node?.namedChildren.find((c) => c.type === 'type_list');
+    return typeList ? typeList.namedChildren.map((c) => this.getJavaTypeName(c)) : [];
+  }
+
+  /**
+   * Java型名を取得（型引数を除いた名前）
+   */
+  private getJavaTypeName(node: Parser.SyntaxNode): string {
+    if (node.type === 'generic_type') {
+      const baseType = node.namedChildren[0];
+      return baseType ? baseType.text : node.text;
+    }
+    return node.text;
+  }
+
+  /**
+   * Javaの型定義・メンバーのスコープを判定
+   */
+  private getJavaScope(node: Parser.SyntaxNode): SymbolScope {
+    let current = node.parent;
+    while (current) {
+      if (
+        current.type === 'method_declaration' ||
+        current.type === 'constructor_declaration' ||
+        current.type === 'lambda_expression'
+      ) {
+        return SymbolScope.Function;
+      }
+      if (
+        current.type === 'class_body' ||
+        current.type === 'interface_body' ||
+        current.type === 'enum_body' ||
+        current.type === 'annotation_type_body'
+      ) {
+        return SymbolScope.Class;
+      }
+      current = current.parent;
+    }
+    return SymbolScope.Global;
+  }
+
+  /**
+   * Java型定義本体直下のメンバーか確認
+   */
+  private isJavaMemberNode(node: Parser.SyntaxNode): boolean {
+    const parentType = node.parent?.type;
+    return (
+      parentType === 'class_body' ||
+      parentType === 'interface_body' ||
+      parentType === 'enum_body_declarations' ||
+      parentType === 'annotation_type_body'
+    );
+  }
+
+  /**
+   * CommentExtractorで抽出したdoc commentをシンボルのdocstringとして関連付け
+   *
+   * doc commentの直後（属性・アノテーション行を挟んでもよい）から始まるシンボルに紐付ける
+   */
+  private attachDocstrings(symbols: SymbolInfo[], code: string, language: Language): void {
+    const docComments = new Map<number, CommentInfo>();
+    for (const comment of this.commentExtractor.extractComments(code, language).comments) {
+      if (comment.type === CommentType.DocComment) {
+        docComments.set(comment.position.endLine, comment);
+      }
+    }
+
+    if (docComments.size === 0) return;
+
+    const lines = code.split('\n');
+
+    const attach = (symbol: SymbolInfo): void => {
+      // #[derive(...)]や@Overrideなどの行を遡ってスキップ
+      let line = symbol.position.startLine - 1;
+      while (line >= 0 && /^\s*(#\[|@)/.test(lines[line] ?? '')) {
+        line--;
+      }
+
+      const comment = docComments.get(line);
+      if (comment && !symbol.docstring) {
+        symbol.docstring = comment.content;
+      }
+
+      symbol.members?.forEach(attach);
+    };
+
+    symbols.forEach(attach);
   }
 }
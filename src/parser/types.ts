@@ -87,6 +87,11 @@ export enum SymbolType {
   Parameter = 'parameter',
   Trait = 'trait',
   Enum = 'enum',
+  TypeAlias = 'type_alias',
+  Macro = 'macro',
+  Module = 'module',
+  Annotation = 'annotation',
+  Impl = 'impl', // Rust impl block whose target type is not defined in the file
 }
 
 /**
@@ -140,6 +145,9 @@ export interface SymbolInfo {
   isPrivate?: boolean;
   isAbstract?: boolean;
 
+  // Java specific
+  annotations?: string[]; // e.g., Override, Deprecated
+
   // Arduino specific
   isArduinoSpecialFunction?: boolean; // setup or loop
 }
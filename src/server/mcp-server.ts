@@ -59,6 +59,13 @@ import {
   handleClearIndex,
   type ClearIndexInput,
 } from '../tools/clear-index-tool.js';
+import {
+  TOOL_NAME as LIST_TODOS_TOOL_NAME,
+  TOOL_DESCRIPTION as LIST_TODOS_TOOL_DESCRIPTION,
+  getInputSchemaJSON as getListTodosInputSchema,
+  handleListTodos,
+  type ListTodosInput,
+} from '../tools/list-todos-tool.js';
 import { DocCodeLinker } from '../parser/doc-code-linker.js';
 import { SymbolExtractor } from '../parser/symbol-extractor.js';
 import { MarkdownParser } from '../parser/markdown-parser.js';
@@ -223,6 +230,15 @@ export class MCPServer {
         });
       }
 
+      // list_todosツールを登録
+      if (this.vectorStore) {
+        tools.push({
+          name: LIST_TODOS_TOOL_NAME,
+          description: LIST_TODOS_TOOL_DESCRIPTION,
+          inputSchema: getListTodosInputSchema(),
+        });
+      }
+
       // health_checkツールを登録（常に利用可能）
       if (this.healthChecker) {
         tools.push({
@@ -395,6 +411,30 @@ export class MCPServer {
           };
         }
 
+        // list_todosツール
+        if (toolName === LIST_TODOS_TOOL_NAME) {
+          if (!this.vectorStore) {
+            throw new Error('Vector store is not available');
+          }
+
+          // ツールハンドラーを実行
+          const result = await handleListTodos(
+            args as ListTodosInput,
+            this.vectorStore,
+            this.embeddingEngine?.getDimension()
+          );
+
+          // MCPレスポンス形式で返す
+          return {
+            content: [
+              {
+                type: 'text',
+                text: JSON.stringify(result, null, 2),
+              },
+            ],
+          };
+        }
+
         // health_checkツール
         if (toolName === HEALTH_CHECK_TOOL_NAME) {
           if (!this.healthChecker) {
@@ -5,9 +5,16 @@
  * Promise + setTimeoutによる非同期処理でCPU使用率を制限しながら更新を実行
  */
 
-import { IndexingService } from './indexing-service.js';
+import type { IndexingService } from './indexing-service.js';
 import { logger } from '../utils/logger.js';
 
+/**
+ * キューアイテムの操作種別
+ * - update: 再インデックス化（追加・変更されたファイル）
+ * - delete: インデックスから削除（削除されたファイル）
+ */
+export type QueueOperation = 'update' | 'delete';
+
 /**
  * キューアイテム
  */
@@ -16,6 +23,8 @@ interface QueueItem {
   filePath: string;
   /** プロジェクトID */
   projectId: string;
+  /** 操作種別 */
+  operation: QueueOperation;
   /** 優先度（高いほど優先）*/
   priority: number;
   /** タイムスタンプ（エンキュー時刻） */
@@ -71,9 +80,14 @@ export class BackgroundUpdateQueue {
   /**
    * ファイル更新をキューに追加
    *
-   * 同じファイルパスが既にキューに存在する場合、優先度とタイムスタンプを更新
+   * 同じファイルパスが既にキューに存在する場合、優先度とタイムスタンプ、操作種別を更新
    */
-  enqueue(filePath: string, projectId: string, priority?: number): void {
+  enqueue(
+    filePath: string,
+    projectId: string,
+    priority?: number,
+    operation: QueueOperation = 'update'
+  ): void {
     const timestamp = Date.now();
 
     // 優先度が指定されていない場合、タイムスタンプを優先度として使用
@@ -84,6 +98,7 @@ export class BackgroundUpdateQueue {
     this.queue.set(filePath, {
       filePath,
       projectId,
+      operation,
       priority: effectivePriority,
       timestamp,
     });
@@ -91,6 +106,7 @@ export class BackgroundUpdateQueue {
     logger.debug('File enqueued for background update', {
       filePath,
       projectId,
+      operation,
       priority: effectivePriority,
       queueSize: this.queue.size,
     });
@@ -170,10 +186,15 @@ export class BackgroundUpdateQueue {
         logger.debug('Processing file update', {
           filePath: nextItem.filePath,
           projectId: nextItem.projectId,
+          operation: nextItem.operation,
           priority: nextItem.priority,
         });
 
-        await this.indexingService.updateFile(nextItem.filePath, nextItem.projectId);
+        if (nextItem.operation === 'delete') {
+          await this.indexingService.deleteFile(nextItem.filePath, nextItem.projectId);
+        } else {
+          await this.indexingService.updateFile(nextItem.filePath, nextItem.projectId);
+        }
 
         this.processedCount++;
 
@@ -5,8 +5,11 @@
  * Claude Context（Zilliz）のアプローチを参考に実装
  */
 
-import type { BM25Engine, SearchResult } from '../storage/bm25-engine';
-import type { VectorStorePlugin, QueryResult } from '../storage/types';
+import type { BM25Engine, BM25SearchFilter, SearchResult } from '../storage/bm25-engine';
+import { normalizeFileType } from '../storage/bm25-engine';
+import type { VectorStorePlugin, QueryResult, MetadataFilter } from '../storage/types';
+import type { FusionStrategy } from '../config/types';
+import { parseBM25Query, hasConstraints } from '../storage/bm25-query';
 import { Logger } from '../utils/logger';
 
 /**
@@ -21,16 +24,35 @@ export interface HybridSearchResult {
   metadata?: Record<string, unknown>;
 }
 
+/**
+ * スコア統合オプション
+ */
+export interface FusionOptions {
+  /** 統合方式（デフォルト: linear） */
+  strategy?: FusionStrategy;
+  /** RRFの定数k（デフォルト: 60） */
+  rrfK?: number;
+}
+
+/**
+ * RRFの定数kのデフォルト値
+ */
+export const DEFAULT_RRF_K = 60;
+
 /**
  * 検索フィルタ
  */
 export interface SearchFilter {
+  /** プロジェクトID（BM25・ベクトル検索の両方に検索時点で適用） */
+  projectId?: string;
   /** ファイルタイプフィルタ（拡張子：ts, py等） */
   fileTypes?: string[];
-  /** 言語フィルタ */
+  /** 言語フィルタ（大文字・小文字を区別しない） */
   languages?: string[];
-  /** パスパターンフィルタ */
+  /** パスパターンフィルタ（ファイルパスの部分一致） */
   pathPattern?: string;
+  /** シンボル種別フィルタ（function, class, comment等） */
+  types?: string[];
 }
 
 /**
@@ -68,6 +90,7 @@ interface MergedDocument {
 export class HybridSearchEngine {
   private logger: Logger;
   private alpha: number; // BM25の重み（0-1）
+  private fusion: Required<FusionOptions>;
 
   /**
    * ハイブリッド検索エンジンを初期化
@@ -75,17 +98,20 @@ export class HybridSearchEngine {
    * @param bm25Engine BM25全文検索エンジン
    * @param vectorStore ベクターストアプラグイン
    * @param alpha BM25の重み（デフォルト: 0.3、範囲: 0-1）
-   * @throws alpha が 0-1 の範囲外の場合
+   * @param fusion スコア統合オプション（デフォルト: linear）
+   * @throws alpha が 0-1 の範囲外の場合、またはRRFの定数kが正の数でない場合
    */
   constructor(
     private bm25Engine: BM25Engine,
     private vectorStore: VectorStorePlugin,
-    alpha: number = 0.3
+    alpha: number = 0.3,
+    fusion: FusionOptions = {}
   ) {
     if (alpha < 0 || alpha > 1) {
       throw new Error('Alpha parameter must be between 0 and 1');
     }
     this.alpha = alpha;
+    this.fusion = this.resolveFusionOptions(fusion, { strategy: 'linear', rrfK: DEFAULT_RRF_K });
     this.logger = new Logger();
   }
 
@@ -97,6 +123,7 @@ export class HybridSearchEngine {
    * @param queryVector クエリベクトル（ベクトル検索用）
    * @param topK 取得する上位結果数
    * @param filter フィルタ条件（オプション）
+   * @param fusion スコア統合オプション（オプション、未指定の項目は初期化時の設定を使用）
    * @returns ハイブリッドスコアでランキングされた検索結果
    */
   async search(
@@ -104,27 +131,169 @@ export class HybridSearchEngine {
     query: string,
     queryVector: number[],
     topK: number,
-    filter?: SearchFilter
+    filter?: SearchFilter,
+    fusion?: FusionOptions
   ): Promise<HybridSearchResult[]> {
-    this.logger.debug(`Hybrid search: query="${query}", topK=${topK}`);
+    const fusionOptions = this.resolveFusionOptions(fusion ?? {}, this.fusion);
+    this.logger.debug(
+      `Hybrid search: query="${query}", topK=${topK}, fusion=${fusionOptions.strategy}`
+    );
 
+    // フィルタは取得後ではなく各検索に渡して絞り込む（条件外の結果で枠を埋めない）
     // 1. BM25検索を実行（クエリが空でない場合のみ）
     const bm25Results: SearchResult[] =
       query.trim().length > 0
-        ? await this.bm25Engine.search(query, topK * 2) // 多めに取得してマージ
+        ? await this.bm25Engine.search(
+            query,
+            topK * 2, // 多めに取得してマージ
+            filter ? this.buildBM25Filter(filter) : undefined
+          )
         : [];
 
-    // 2. ベクトル検索を実行
-    const vectorResults: QueryResult[] = await this.vectorStore.query(
-      collectionName,
-      queryVector,
-      topK * 2 // 多めに取得してマージ
+    // 2. ベクトル検索を実行（フィルタはベクターストアの検索式に変換して適用）
+    const vectorResults: QueryResult[] = await this.restrictToConstraints(
+      query,
+      await this.vectorStore.query(
+        collectionName,
+        queryVector,
+        topK * 2, // 多めに取得してマージ
+        filter ? this.buildVectorFilter(filter) : undefined
+      )
+    );
+
+    // 3. 結果を統合してハイブリッドスコアを計算
+    const hybridResults = this.fuseResults(bm25Results, vectorResults, fusionOptions);
+
+    // 4. ランキングしてtopK件を返す（フィルタは各検索で適用済み）
+    const ranked = this.rankResults(hybridResults, topK);
+
+    // 5. BM25のみの結果はベクターストアのメタデータがないため、BM25の属性で補う
+    return this.fillBM25Metadata(ranked);
+  }
+
+  /**
+   * 検索語を使わずにフィルタのみで検索
+   *
+   * `lang:rust type:function`のようにフィールド指定のみのクエリに使用します。
+   * BM25インデックスの属性で絞り込むため、スコアはすべて1とし、ドキュメントIDの順に返します。
+   * limit件に満たない場合は、条件に一致するドキュメントをすべて返します。
+   *
+   * @param filter 検索フィルタ
+   * @param limit 取得する最大件数
+   * @param query 除外（`-term`）等の条件を含むクエリ（オプション）
+   * @returns 検索結果（メタデータはBM25の属性）
+   */
+  async searchByFilter(
+    filter: SearchFilter,
+    limit: number,
+    query: string = ''
+  ): Promise<HybridSearchResult[]> {
+    const bm25Filter = this.buildBM25Filter(filter) ?? {};
+
+    // クエリの条件で除外される分を見込んで、limit件に達するか候補がなくなるまで取得件数を増やす
+    let candidateCount = limit;
+    for (;;) {
+      const candidates = await this.bm25Engine.findDocuments(bm25Filter, candidateCount);
+      const ids =
+        query.trim().length > 0
+          ? await this.bm25Engine.filterDocuments(candidates, query)
+          : candidates;
+      if (ids.length >= limit || candidates.length < candidateCount) {
+        return this.fillBM25Metadata(ids.slice(0, limit).map((id) => ({ id, score: 1 })));
+      }
+      candidateCount *= 2;
+    }
+  }
+
+  /**
+   * メタデータを持たない結果にBM25インデックスのドキュメント属性を設定
+   *
+   * 取得後の条件（`-lang:`等）や結果の表示がBM25のみの結果にも適用されるようにします。
+   *
+   * @param results ランキング済みの検索結果
+   * @returns メタデータを補った検索結果
+   */
+  private async fillBM25Metadata(results: HybridSearchResult[]): Promise<HybridSearchResult[]> {
+    const missing = results.filter(
+      (result) => !result.metadata || Object.keys(result.metadata).length === 0
+    );
+    if (missing.length === 0) {
+      return results;
+    }
+
+    const attributes = await this.bm25Engine.getDocumentAttributes(
+      missing.map((result) => result.id)
+    );
+    for (const result of missing) {
+      const attribute = attributes.get(result.id);
+      if (!attribute) {
+        continue;
+      }
+      const metadata: Record<string, unknown> = {
+        project_id: attribute.projectId,
+        file_path: attribute.filePath,
+        language: attribute.language,
+        file_type: attribute.fileType,
+        type: attribute.symbolType,
+      };
+      result.metadata = Object.fromEntries(
+        Object.entries(metadata).filter(([, value]) => value !== undefined)
+      );
+    }
+    return results;
+  }
+
+  /**
+   * ベクトル検索結果にクエリのフレーズ・OR・除外の条件を適用
+   *
+   * 条件を満たさないドキュメントが意味的な類似度だけで結果に混ざらないよう、
+   * BM25インデックスのターム出現位置で判定します。
+   */
+  private async restrictToConstraints(
+    query: string,
+    vectorResults: QueryResult[]
+  ): Promise<QueryResult[]> {
+    if (vectorResults.length === 0 || !hasConstraints(parseBM25Query(query))) {
+      return vectorResults;
+    }
+
+    const allowed = new Set(
+      await this.bm25Engine.filterDocuments(vectorResults.map((result) => result.id), query)
     );
+    return vectorResults.filter((result) => allowed.has(result.id));
+  }
 
-    // 3. 結果をマージ
+  /**
+   * BM25とベクトル検索の結果を指定の方式で統合
+   *
+   * @param bm25Results BM25検索結果
+   * @param vectorResults ベクトル検索結果
+   * @param fusion スコア統合オプション（オプション、未指定の項目は初期化時の設定を使用）
+   * @returns ハイブリッドスコア（0-1）付きの結果（未ソート）
+   */
+  fuseResults(
+    bm25Results: SearchResult[],
+    vectorResults: QueryResult[],
+    fusion: FusionOptions = {}
+  ): HybridSearchResult[] {
+    const { strategy, rrfK } = this.resolveFusionOptions(fusion, this.fusion);
     const mergedMap = this.mergeResults(bm25Results, vectorResults);
 
-    // 4. スコアを正規化
+    switch (strategy) {
+      case 'rrf':
+        return this.fuseByReciprocalRank(mergedMap, bm25Results, vectorResults, rrfK);
+      case 'zscore':
+        return this.fuseByZScore(mergedMap, bm25Results, vectorResults);
+      default:
+        return this.fuseLinear(mergedMap);
+    }
+  }
+
+  /**
+   * Min-Max正規化したスコアを線形結合
+   */
+  private fuseLinear(mergedMap: Map<string, MergedDocument>): HybridSearchResult[] {
+    // スコアを正規化
     const normalizedBM25 = this.normalizeScores(
       Array.from(mergedMap.values()).map((doc) => ({ id: doc.id, score: doc.bm25Score }))
     );
@@ -146,18 +315,122 @@ export class HybridSearchEngine {
       }
     });
 
-    // 5. ハイブリッドスコアを計算
-    const hybridResults: HybridSearchResult[] = Array.from(mergedMap.values()).map((doc) => ({
+    // ハイブリッドスコアを計算
+    return Array.from(mergedMap.values()).map((doc) => ({
       id: doc.id,
       score: this.calculateHybridScore(doc.bm25Score, doc.vectorScore),
       metadata: doc.metadata,
     }));
+  }
+
+  /**
+   * Reciprocal Rank Fusionで統合
+   *
+   * score = (α/(k+rank_bm25) + (1-α)/(k+rank_vector)) * (k+1)
+   *
+   * スコアの分布に依存せず順位のみを使うため、片方のリストのヒットが1件でも過大評価されません。
+   * 両方のリストで1位の場合に1.0となるよう(k+1)倍します。
+   */
+  private fuseByReciprocalRank(
+    mergedMap: Map<string, MergedDocument>,
+    bm25Results: SearchResult[],
+    vectorResults: QueryResult[],
+    k: number
+  ): HybridSearchResult[] {
+    const bm25Ranks = this.rankPositions(
+      bm25Results.map((r) => ({ id: r.documentId, score: r.score }))
+    );
+    const vectorRanks = this.rankPositions(vectorResults);
+
+    return Array.from(mergedMap.values()).map((doc) => {
+      const bm25Rank = bm25Ranks.get(doc.id);
+      const vectorRank = vectorRanks.get(doc.id);
+      const bm25Part = bm25Rank !== undefined ? this.alpha / (k + bm25Rank) : 0;
+      const vectorPart = vectorRank !== undefined ? (1 - this.alpha) / (k + vectorRank) : 0;
+
+      return {
+        id: doc.id,
+        score: (bm25Part + vectorPart) * (k + 1),
+        metadata: doc.metadata,
+      };
+    });
+  }
+
+  /**
+   * Zスコアで標準化したスコアを線形結合
+   *
+   * 各リストのスコアを (score - 平均) / 標準偏差 で標準化し、ロジスティック関数で0-1に写像します。
+   * 単一ヒットや同点のみのリストは0.5（平均相当）となり、リストに含まれない場合は0とします。
+   */
+  private fuseByZScore(
+    mergedMap: Map<string, MergedDocument>,
+    bm25Results: SearchResult[],
+    vectorResults: QueryResult[]
+  ): HybridSearchResult[] {
+    const bm25Scores = this.standardizeScores(
+      bm25Results.map((r) => ({ id: r.documentId, score: r.score }))
+    );
+    const vectorScores = this.standardizeScores(vectorResults);
+
+    return Array.from(mergedMap.values()).map((doc) => ({
+      id: doc.id,
+      score: this.calculateHybridScore(bm25Scores.get(doc.id) ?? 0, vectorScores.get(doc.id) ?? 0),
+      metadata: doc.metadata,
+    }));
+  }
 
-    // 6. フィルタリング
-    const filtered = filter ? this.filterResults(hybridResults, filter) : hybridResults;
+  /**
+   * スコアの降順で1始まりの順位を付与
+   */
+  private rankPositions(scores: Array<{ id: string; score: number }>): Map<string, number> {
+    const ranks = new Map<string, number>();
+    [...scores]
+      .sort((a, b) => b.score - a.score)
+      .forEach((item, index) => {
+        if (!ranks.has(item.id)) {
+          ranks.set(item.id, index + 1);
+        }
+      });
+    return ranks;
+  }
 
-    // 7. ランキングしてtopK件を返す
-    return this.rankResults(filtered, topK);
+  /**
+   * スコアをZスコアで標準化し、ロジスティック関数で0-1に写像
+   */
+  private standardizeScores(scores: Array<{ id: string; score: number }>): Map<string, number> {
+    const standardized = new Map<string, number>();
+    if (scores.length === 0) {
+      return standardized;
+    }
+
+    const mean = scores.reduce((sum, s) => sum + s.score, 0) / scores.length;
+    const variance = scores.reduce((sum, s) => sum + (s.score - mean) ** 2, 0) / scores.length;
+    const stdDev = Math.sqrt(variance);
+
+    for (const s of scores) {
+      const z = stdDev > 0 ? (s.score - mean) / stdDev : 0;
+      standardized.set(s.id, 1 / (1 + Math.exp(-z)));
+    }
+    return standardized;
+  }
+
+  /**
+   * スコア統合オプションを検証し、未指定の項目を補完
+   *
+   * @throws RRFの定数kが正の数でない場合
+   */
+  private resolveFusionOptions(
+    fusion: FusionOptions,
+    defaults: Required<FusionOptions>
+  ): Required<FusionOptions> {
+    const resolved = {
+      strategy: fusion.strategy ?? defaults.strategy,
+      rrfK: fusion.rrfK ?? defaults.rrfK,
+    };
+    if (!(resolved.rrfK > 0)) {
+      throw new Error('RRF constant k must be a positive number');
+    }
+    return resolved;
   }
 
   /**
@@ -251,7 +524,63 @@ export class HybridSearchEngine {
   }
 
   /**
-   * 検索結果をフィルタリング
+   * 検索フィルタをBM25の検索フィルタに変換
+   *
+   * @param filter 検索フィルタ
+   * @returns BM25検索フィルタ（条件がない場合はundefined）
+   */
+  buildBM25Filter(filter: SearchFilter): BM25SearchFilter | undefined {
+    const bm25Filter: BM25SearchFilter = {};
+
+    if (filter.projectId !== undefined) {
+      bm25Filter.projectId = filter.projectId;
+    }
+    if (filter.languages && filter.languages.length > 0) {
+      bm25Filter.languages = filter.languages;
+    }
+    if (filter.fileTypes && filter.fileTypes.length > 0) {
+      bm25Filter.fileTypes = filter.fileTypes.map(normalizeFileType);
+    }
+    if (filter.pathPattern) {
+      bm25Filter.pathPattern = filter.pathPattern;
+    }
+    if (filter.types && filter.types.length > 0) {
+      bm25Filter.types = filter.types;
+    }
+
+    return Object.keys(bm25Filter).length > 0 ? bm25Filter : undefined;
+  }
+
+  /**
+   * 検索フィルタをベクターストアのメタデータフィルタに変換
+   *
+   * @param filter 検索フィルタ
+   * @returns メタデータフィルタ（条件がない場合はundefined）
+   */
+  buildVectorFilter(filter: SearchFilter): MetadataFilter | undefined {
+    const metadataFilter: MetadataFilter = {};
+
+    if (filter.projectId !== undefined) {
+      metadataFilter.project_id = filter.projectId;
+    }
+    if (filter.languages && filter.languages.length > 0) {
+      metadataFilter.language = filter.languages.map((language) => language.toLowerCase());
+    }
+    if (filter.fileTypes && filter.fileTypes.length > 0) {
+      metadataFilter.file_type = filter.fileTypes.map(normalizeFileType);
+    }
+    if (filter.pathPattern) {
+      metadataFilter.file_path = { $like: `%${filter.pathPattern}%` };
+    }
+    if (filter.types && filter.types.length > 0) {
+      metadataFilter.type = filter.types;
+    }
+
+    return Object.keys(metadataFilter).length > 0 ? metadataFilter : undefined;
+  }
+
+  /**
+   * 検索結果をフィルタリング（メタデータを持つ結果に対する後段の絞り込み用）
    *
    * @param results 検索結果
    * @param filter フィルタ条件
@@ -262,24 +591,37 @@ export class HybridSearchEngine {
 
     // ファイルタイプフィルタ
     if (filter.fileTypes && filter.fileTypes.length > 0) {
+      const fileTypes = filter.fileTypes.map(normalizeFileType);
       filtered = filtered.filter((result) => {
-        const fileType = result.metadata?.fileType as string | undefined;
-        return fileType && filter.fileTypes!.includes(fileType);
+        const fileType = (result.metadata?.file_type ?? result.metadata?.fileType) as
+          | string
+          | undefined;
+        return fileType !== undefined && fileTypes.includes(normalizeFileType(fileType));
       });
     }
 
-    // 言語フィルタ
+    // 言語フィルタ（大文字・小文字を区別しない）
     if (filter.languages && filter.languages.length > 0) {
+      const languages = filter.languages.map((language) => language.toLowerCase());
       filtered = filtered.filter((result) => {
         const language = result.metadata?.language as string | undefined;
-        return language && filter.languages!.includes(language);
+        return language !== undefined && languages.includes(language.toLowerCase());
       });
     }
 
-    // パスパターンフィルタ
+    // シンボル種別フィルタ
+    if (filter.types && filter.types.length > 0) {
+      filtered = filtered.filter((result) => {
+        const type = result.metadata?.type as string | undefined;
+        return type !== undefined && filter.types!.includes(type);
+      });
+    }
+
+    // パスパターンフィルタ（IDはプロジェクトIDを含むため、ファイルパスのメタデータを優先）
     if (filter.pathPattern) {
       filtered = filtered.filter((result) => {
-        return result.id.includes(filter.pathPattern!);
+        const filePath = (result.metadata?.file_path as string | undefined) ?? result.id;
+        return filePath.includes(filter.pathPattern!);
       });
     }
 
@@ -301,6 +643,13 @@ export class HybridSearchEngine {
     return topK !== undefined ? sorted.slice(0, topK) : sorted;
   }
 
+  /**
+   * スコア統合オプションを取得
+   */
+  getFusionOptions(): Required<FusionOptions> {
+    return { ...this.fusion };
+  }
+
   /**
    * α（BM25の重み）を取得
    */
@@ -7,3 +7,5 @@
 export * from './hybrid-search-engine';
 export * from './indexing-service';
 export * from './background-update-queue';
+export * from './vector-id';
+export * from './search-query-parser';
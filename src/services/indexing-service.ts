@@ -6,6 +6,7 @@
  */
 
 import { EventEmitter } from 'events';
+import { createHash } from 'crypto';
 import * as fs from 'fs/promises';
 import * as path from 'path';
 import { FileScanner } from '../scanner/file-scanner.js';
@@ -13,10 +14,39 @@ import { SymbolExtractor } from '../parser/symbol-extractor.js';
 import { CommentExtractor } from '../parser/comment-extractor.js';
 import { MarkdownParser } from '../parser/markdown-parser.js';
 import { DocCodeLinker } from '../parser/doc-code-linker.js';
-import { Language } from '../parser/types.js';
+import { CodeChunker } from '../parser/code-chunker.js';
+import { VectorIdAllocator } from './vector-id.js';
+import { BackgroundUpdateQueue } from './background-update-queue.js';
+import { FileWatcher, DEFAULT_IGNORE_PATTERNS } from '../watcher/file-watcher.js';
+import { createIgnoreMatcher } from '../watcher/ignore-matcher.js';
+import type { IgnoreMatcher } from '../watcher/types.js';
+import { FileWatcherEvent } from '../watcher/types.js';
+import {
+  CommentType,
+  Language,
+  SymbolType,
+  type CommentInfo,
+  type SymbolInfo,
+} from '../parser/types.js';
 import type { EmbeddingEngine } from '../embedding/types.js';
 import type { VectorStorePlugin, Vector } from '../storage/types.js';
-import type { BM25Engine } from '../storage/bm25-engine.js';
+import type {
+  BM25Engine,
+  BM25DocumentAttributes,
+  FileManifestEntry,
+} from '../storage/bm25-engine.js';
+import { normalizeFileType } from '../storage/bm25-engine.js';
+
+/**
+ * インデックス形式のバージョン
+ *
+ * BM25のトークナイザーなど、既存のインデックスを作り直す必要がある変更を加えたときに上げる。
+ * マニフェストに記録したバージョンと異なるファイルは、内容が同じでも再インデックス化する。
+ * - 0: バージョン未記録（自然言語向けトークナイザーでインデックス化）
+ * - 1: コード向けトークナイザー
+ * - 2: ベクトル/BM25ドキュメントのIDにプロジェクトIDを含める
+ */
+export const INDEX_FORMAT_VERSION = 2;
 
 /**
  * インデックス化オプション
@@ -50,6 +80,10 @@ export interface FileIndexResult {
   error?: string;
   /** 処理時間（ミリ秒） */
   processingTime?: number;
+  /** 内容に変更がないためスキップしたか */
+  skipped?: boolean;
+  /** 生成されたベクトル/ドキュメントID */
+  vectorIds?: string[];
 }
 
 /**
@@ -66,6 +100,12 @@ export interface ProjectIndexResult {
   indexedFiles: number;
   /** 失敗したファイル数 */
   failedFiles: number;
+  /** 内容に変更がないためスキップしたファイル数 */
+  skippedFiles: number;
+  /** 変更を検出して再埋め込みしたファイル数 */
+  reembeddedFiles: number;
+  /** 削除されたためインデックスから除去したファイル数 */
+  purgedFiles: number;
   /** 総シンボル数 */
   totalSymbols: number;
   /** 総ベクトル数 */
@@ -94,6 +134,17 @@ export interface IndexStats {
   totalSymbols: number;
   /** 総ベクトル数 */
   totalVectors: number;
+  /** インデックス化に使用したオプション */
+  options?: IndexingOptions;
+}
+
+/**
+ * ファイルの変更検出に使用するフィンガープリント
+ */
+interface FileFingerprint {
+  size: number;
+  mtime: number;
+  hash: string;
 }
 
 /**
@@ -130,7 +181,9 @@ export interface ClearResult {
 export class IndexingService extends EventEmitter {
   private indexMetadata: Map<string, IndexStats> = new Map();
   private collectionName = 'code_vectors';
-  private fileWatchers: Map<string, any> = new Map();
+  private fileWatchers: Map<string, FileWatcher> = new Map();
+  private updateQueue = new BackgroundUpdateQueue(this);
+  private codeChunker = new CodeChunker();
 
   constructor(
     private fileScanner: FileScanner,
@@ -156,19 +209,42 @@ export class IndexingService extends EventEmitter {
     const startTime = Date.now();
 
     // ステータスを更新
-    this.updateIndexMetadata(projectId, rootPath, 'indexing');
+    await this.updateIndexMetadata(projectId, rootPath, 'indexing', undefined, options);
 
     try {
       // ファイルをスキャン
       const files = await this.fileScanner.scan();
 
+      // 前回のマニフェストを読み込む（変更のないファイルは再埋め込みしない）
+      const manifest = await this.loadManifest(projectId);
+
+      // 前回から削除されたファイルをインデックスから除去
+      const scannedFiles = new Set(files);
+      let purgedFiles = 0;
+      for (const filePath of manifest.keys()) {
+        if (!scannedFiles.has(filePath)) {
+          await this.deleteFileFromIndex(filePath, projectId);
+          await this.bm25Engine.deleteFileManifest(projectId, filePath);
+          purgedFiles++;
+        }
+      }
+
       if (files.length === 0) {
+        await this.updateIndexMetadata(projectId, rootPath, 'indexed', {
+          totalFiles: 0,
+          totalSymbols: 0,
+          totalVectors: 0,
+        });
+
         return {
           success: true,
           projectId,
           totalFiles: 0,
           indexedFiles: 0,
           failedFiles: 0,
+          skippedFiles: 0,
+          reembeddedFiles: 0,
+          purgedFiles,
           totalSymbols: 0,
           totalVectors: 0,
           processingTime: Date.now() - startTime,
@@ -195,14 +271,18 @@ export class IndexingService extends EventEmitter {
       const chunks = this.chunkArray(files, maxWorkers);
       for (const chunk of chunks) {
         const chunkResults = await Promise.all(
-          chunk.map((file) => this.indexFileInternal(file, projectId))
+          chunk.map((file) => this.indexFileIncremental(file, projectId, manifest.get(file)))
         );
         results.push(...chunkResults);
       }
 
-      // 結果を集計
-      const indexedFiles = results.filter((r) => r.success).length;
+      // 結果を集計（スキップしたファイルはマニフェストの値をシンボル数・ベクトル数に含める）
+      const indexedFiles = results.filter((r) => r.success && !r.skipped).length;
       const failedFiles = results.filter((r) => !r.success).length;
+      const skippedFiles = results.filter((r) => r.skipped).length;
+      const reembeddedFiles = results.filter(
+        (r) => r.success && !r.skipped && manifest.has(r.filePath)
+      ).length;
       const totalSymbols = results.reduce((sum, r) => sum + r.symbolsCount, 0);
       const totalVectors = results.reduce((sum, r) => sum + r.vectorsCount, 0);
 
@@ -214,8 +294,8 @@ export class IndexingService extends EventEmitter {
       }
 
       // メタデータを更新
-      this.updateIndexMetadata(projectId, rootPath, 'indexed', {
-        totalFiles: indexedFiles,
+      await this.updateIndexMetadata(projectId, rootPath, 'indexed', {
+        totalFiles: indexedFiles + skippedFiles,
         totalSymbols,
         totalVectors,
       });
@@ -226,13 +306,16 @@ export class IndexingService extends EventEmitter {
         totalFiles: files.length,
         indexedFiles,
         failedFiles,
+        skippedFiles,
+        reembeddedFiles,
+        purgedFiles,
         totalSymbols,
         totalVectors,
         processingTime: Date.now() - startTime,
         errors,
       };
     } catch (error) {
-      this.updateIndexMetadata(projectId, rootPath, 'error');
+      await this.updateIndexMetadata(projectId, rootPath, 'error');
       throw error;
     }
   }
@@ -244,6 +327,54 @@ export class IndexingService extends EventEmitter {
     return this.indexFileInternal(filePath, projectId);
   }
 
+  /**
+   * 前回のマニフェストと比較し、変更のあるファイル（またはインデックス形式が古いファイル）のみ
+   * インデックス化
+   */
+  private async indexFileIncremental(
+    filePath: string,
+    projectId: string,
+    previous?: FileManifestEntry
+  ): Promise<FileIndexResult> {
+    let fingerprint: FileFingerprint;
+    try {
+      fingerprint = await this.computeFingerprint(filePath, previous);
+    } catch (error) {
+      // 読み込めないファイルのエラー報告はindexFileInternalに任せる
+      return this.indexFileInternal(filePath, projectId);
+    }
+
+    if (
+      previous &&
+      previous.hash === fingerprint.hash &&
+      previous.indexVersion === INDEX_FORMAT_VERSION
+    ) {
+      // 内容が同じならmtimeのみ更新（次回はハッシュ計算も省略できる）
+      if (previous.size !== fingerprint.size || previous.mtime !== fingerprint.mtime) {
+        await this.bm25Engine.upsertFileManifest({ ...previous, ...fingerprint });
+      }
+
+      return {
+        success: true,
+        filePath,
+        symbolsCount: previous.symbolsCount,
+        vectorsCount: previous.ids.length,
+        skipped: true,
+      };
+    }
+
+    // 変更されたファイルは古いエントリを削除してから再インデックス化
+    // （再インデックス化に失敗しても次回スキップされないよう、マニフェストから先に削除する）
+    if (previous) {
+      await this.bm25Engine.deleteFileManifest(projectId, filePath);
+      await this.deleteFileFromIndex(filePath, projectId);
+    }
+
+    const result = await this.indexFileInternal(filePath, projectId);
+    await this.recordManifest(filePath, projectId, result, fingerprint);
+    return result;
+  }
+
   /**
    * ファイルをインデックス化（内部実装）
    */
@@ -276,16 +407,18 @@ export class IndexingService extends EventEmitter {
       // ファイルタイプを判定
       if (ext === '.md') {
         return await this.indexMarkdownFile(filePath, content, projectId, startTime);
-      } else if (this.isCodeFile(ext)) {
-        return await this.indexCodeFile(filePath, content, projectId, startTime);
-      } else {
+      } else if (content.includes('\u0000')) {
+        // バイナリファイルはテキストとして検索できないためインデックス化しない
         return {
           success: false,
           filePath,
           symbolsCount: 0,
           vectorsCount: 0,
-          error: `Unsupported file type: ${ext}`,
+          error: `Unsupported file type: ${ext} (binary content)`,
         };
+      } else {
+        // 構文解析に対応していない拡張子（スキャン対象に追加した拡張子等）はチャンクのみ登録
+        return await this.indexCodeFile(filePath, content, projectId, startTime);
       }
     } catch (error: any) {
       this.emit('fileError', { filePath, projectId, error: error.message });
@@ -310,6 +443,7 @@ export class IndexingService extends EventEmitter {
     startTime: number
   ): Promise<FileIndexResult> {
     const language = this.getLanguageFromExtension(path.extname(filePath));
+    const fileType = this.getFileType(filePath);
 
     try {
       // シンボル抽出
@@ -317,26 +451,90 @@ export class IndexingService extends EventEmitter {
       const symbols = symbolResult.symbols;
 
       // コメント抽出
-      const _comments = this.commentExtractor.extractComments(content, language);
+      const commentResult = this.commentExtractor.extractComments(content, language);
 
       // 埋め込みを生成
       const vectors: Vector[] = [];
       const texts: string[] = [];
       const metadatas: Array<Record<string, any>> = [];
-
-      // シンボルごとに埋め込みを生成
-      for (const symbol of symbols) {
-        const text = this.buildSymbolText(symbol, content);
-        texts.push(text);
-        metadatas.push({
+      const ids: string[] = [];
+      const idAllocator = new VectorIdAllocator(projectId, filePath);
+
+      // シンボルごとに埋め込みを生成（メソッドは所有する型とは別に登録）
+      const addSymbol = (symbol: SymbolInfo, parent?: string): void => {
+        const qualifiedName = parent ? `${parent}.${symbol.name}` : symbol.name;
+        ids.push(idAllocator.allocate(symbol.type, qualifiedName));
+        texts.push(this.buildSymbolText(symbol, content));
+        const metadata: Record<string, any> = {
           project_id: projectId,
           file_path: filePath,
+          file_type: fileType,
           language: language.toString(),
           type: symbol.type,
           name: symbol.name,
           line_start: symbol.position.startLine,
           line_end: symbol.position.endLine,
           scope: symbol.scope,
+        };
+        if (parent) {
+          metadata.parent = parent;
+        }
+        if (symbol.implements && symbol.implements.length > 0) {
+          metadata.implements = symbol.implements;
+        }
+        metadatas.push(metadata);
+      };
+
+      for (const symbol of symbols) {
+        addSymbol(symbol);
+        for (const member of symbol.members ?? []) {
+          if (member.type === SymbolType.Method) {
+            addSymbol(member, symbol.name);
+          }
+        }
+      }
+
+      // シンボルの定義範囲外の行は行範囲のチャンクとして登録
+      // （シンボルを抽出できない言語ではファイル全体、スクリプトではトップレベルのコード）
+      const uncoveredChunks = this.codeChunker.chunkUncovered(
+        content,
+        symbols.map((symbol) => symbol.position)
+      );
+      for (const chunk of uncoveredChunks) {
+        ids.push(idAllocator.allocate('chunk', String(chunk.startLine)));
+        texts.push(chunk.text);
+        metadatas.push({
+          project_id: projectId,
+          file_path: filePath,
+          file_type: fileType,
+          language: language.toString(),
+          type: 'chunk',
+          name: `chunk_${chunk.startLine}`,
+          line_start: chunk.startLine,
+          line_end: chunk.endLine,
+        });
+      }
+
+      // コメントブロックごとに埋め込みを生成（TODO/FIXME等のマーカーと関連シンボルを付与）
+      for (const comment of this.mergeCommentBlocks(commentResult.comments)) {
+        const associatedSymbol =
+          comment.associatedSymbol ?? this.findEnclosingSymbol(symbols, comment.position.startLine);
+
+        ids.push(idAllocator.allocate('comment', associatedSymbol));
+        texts.push(comment.content);
+        metadatas.push({
+          project_id: projectId,
+          file_path: filePath,
+          file_type: fileType,
+          language: language.toString(),
+          type: 'comment',
+          name: `comment_${comment.position.startLine}`,
+          line_start: comment.position.startLine,
+          line_end: comment.position.endLine,
+          comment_type: comment.type,
+          content: comment.content.substring(0, 500),
+          ...(comment.marker ? { marker: comment.marker } : {}),
+          ...(associatedSymbol ? { associated_symbol: associatedSymbol } : {}),
         });
       }
 
@@ -346,7 +544,7 @@ export class IndexingService extends EventEmitter {
 
         for (let i = 0; i < embeddings.length; i++) {
           vectors.push({
-            id: `${filePath}:${metadatas[i].line_start}`,
+            id: ids[i],
             vector: embeddings[i],
             metadata: metadatas[i],
           });
@@ -355,10 +553,14 @@ export class IndexingService extends EventEmitter {
         // ベクターストアに保存
         await this.vectorStore.upsert(this.collectionName, vectors);
 
-        // BM25インデックスに追加
-        for (let i = 0; i < texts.length; i++) {
-          await this.bm25Engine.indexDocument(vectors[i].id, texts[i]);
-        }
+        // BM25インデックスに追加（ファイル単位で1トランザクション）
+        await this.bm25Engine.indexDocuments(
+          vectors.map((vector, i) => ({
+            documentId: vector.id,
+            content: texts[i],
+            attributes: this.toDocumentAttributes(metadatas[i]),
+          }))
+        );
       }
 
       this.emit('fileCompleted', {
@@ -375,6 +577,7 @@ export class IndexingService extends EventEmitter {
         vectorsCount: vectors.length,
         hasErrors: symbolResult.hasError,
         processingTime: Date.now() - startTime,
+        vectorIds: vectors.map((v) => v.id),
       };
     } catch (error: any) {
       this.emit('fileError', { filePath, projectId, error: error.message });
@@ -401,6 +604,8 @@ export class IndexingService extends EventEmitter {
     projectId: string,
     startTime: number
   ): Promise<FileIndexResult> {
+    const fileType = this.getFileType(filePath);
+
     try {
       // Markdown解析
       const parsed = await this.markdownParser.parse(content);
@@ -409,13 +614,17 @@ export class IndexingService extends EventEmitter {
       const vectors: Vector[] = [];
       const texts: string[] = [];
       const metadatas: Array<Record<string, any>> = [];
+      const ids: string[] = [];
+      const idAllocator = new VectorIdAllocator(projectId, filePath);
 
       // 見出しセクションごとに埋め込み
       for (const heading of parsed.headings) {
+        ids.push(idAllocator.allocate('heading', heading.text));
         texts.push(heading.text);
         metadatas.push({
           project_id: projectId,
           file_path: filePath,
+          file_type: fileType,
           language: 'markdown',
           type: 'heading',
           name: heading.text,
@@ -426,10 +635,12 @@ export class IndexingService extends EventEmitter {
 
       // コードブロックごとに埋め込み
       for (const codeBlock of parsed.codeBlocks) {
+        ids.push(idAllocator.allocate('code_block'));
         texts.push(codeBlock.code);
         metadatas.push({
           project_id: projectId,
           file_path: filePath,
+          file_type: fileType,
           language: codeBlock.language,
           type: 'code_block',
           name: `code_block_${codeBlock.startLine}`,
@@ -444,7 +655,7 @@ export class IndexingService extends EventEmitter {
 
         for (let i = 0; i < embeddings.length; i++) {
           vectors.push({
-            id: `${filePath}:${metadatas[i].line_start}`,
+            id: ids[i],
             vector: embeddings[i],
             metadata: metadatas[i],
           });
@@ -453,10 +664,14 @@ export class IndexingService extends EventEmitter {
         // ベクターストアに保存
         await this.vectorStore.upsert(this.collectionName, vectors);
 
-        // BM25インデックスに追加
-        for (let i = 0; i < texts.length; i++) {
-          await this.bm25Engine.indexDocument(vectors[i].id, texts[i]);
-        }
+        // BM25インデックスに追加（ファイル単位で1トランザクション）
+        await this.bm25Engine.indexDocuments(
+          vectors.map((vector, i) => ({
+            documentId: vector.id,
+            content: texts[i],
+            attributes: this.toDocumentAttributes(metadatas[i]),
+          }))
+        );
       }
 
       this.emit('fileCompleted', {
@@ -472,6 +687,7 @@ export class IndexingService extends EventEmitter {
         symbolsCount: 0,
         vectorsCount: vectors.length,
         processingTime: Date.now() - startTime,
+        vectorIds: vectors.map((v) => v.id),
       };
     } catch (error: any) {
       this.emit('fileError', { filePath, projectId, error: error.message });
@@ -493,11 +709,14 @@ export class IndexingService extends EventEmitter {
    */
   async updateFile(filePath: string, projectId: string): Promise<FileIndexResult> {
     try {
-      // 古いインデックスエントリを削除
-      await this.deleteFileFromIndex(filePath);
+      // 古いインデックスエントリを削除（失敗時に古いハッシュが残らないようマニフェストも先に削除）
+      await this.bm25Engine.deleteFileManifest(projectId, filePath);
+      await this.deleteFileFromIndex(filePath, projectId);
 
       // 新しいインデックスを作成
-      return await this.indexFileInternal(filePath, projectId);
+      const result = await this.indexFileInternal(filePath, projectId);
+      await this.recordManifest(filePath, projectId, result);
+      return result;
     } catch (error: any) {
       return {
         success: false,
@@ -512,9 +731,10 @@ export class IndexingService extends EventEmitter {
   /**
    * ファイルを削除（インデックスから削除）
    */
-  async deleteFile(filePath: string, _projectId: string): Promise<RemoveResult> {
+  async deleteFile(filePath: string, projectId: string): Promise<RemoveResult> {
     try {
-      await this.deleteFileFromIndex(filePath);
+      await this.deleteFileFromIndex(filePath, projectId);
+      await this.bm25Engine.deleteFileManifest(projectId, filePath);
       return { success: true, filePath };
     } catch (error: any) {
       return { success: false, filePath, error: error.message };
@@ -523,55 +743,24 @@ export class IndexingService extends EventEmitter {
 
   /**
    * ファイルのインデックスエントリを削除（内部実装）
+   *
+   * 同じファイルを別のプロジェクトとしてインデックス化したエントリは削除しません。
    */
-  private async deleteFileFromIndex(filePath: string): Promise<void> {
+  private async deleteFileFromIndex(filePath: string, projectId: string): Promise<void> {
+    // ベクターストアからfile_path・project_idメタデータが一致するベクトルを削除
+    // （ファイルが既に削除・リネームされていても行数に依存せず削除できる）
     try {
-      // ファイル内の全シンボル/セクションのIDを収集
-      // IDの形式: ${filePath}:${lineNumber}
-      // 簡易実装: ファイルを読み込んで行数を推定し、可能性のあるIDを生成して削除を試みる
-      // または、ベクターストアにメタデータでクエリして該当IDを取得
-
-      // より効率的な実装: ファイル内容を再解析してIDを特定
-      // ただし、ファイルが既に削除されている場合は解析できないため、
-      // ベクターストアのメタデータフィルタを使用するのが最善
-
-      // 現状の簡易実装: 想定される最大行数（例: 10000行）までのIDを試行削除
-      // 注: 本番実装では、ベクターストアのメタデータフィルタクエリを使用すべき
-
-      const idsToDelete: string[] = [];
-
-      // ファイルが存在する場合は、行数を取得してIDを生成
-      try {
-        const content = await fs.readFile(filePath, 'utf-8');
-        const lines = content.split('\n');
-        for (let i = 1; i <= lines.length; i++) {
-          idsToDelete.push(`${filePath}:${i}`);
-        }
-      } catch (error) {
-        // ファイルが存在しない場合は、最大行数で推定
-        // 簡易実装として、最大1000行を想定
-        for (let i = 1; i <= 1000; i++) {
-          idsToDelete.push(`${filePath}:${i}`);
-        }
-      }
-
-      // ベクターストアから削除（存在しないIDは無視される）
-      if (idsToDelete.length > 0) {
-        try {
-          await this.vectorStore.delete(this.collectionName, idsToDelete);
-        } catch (error) {
-          // エラーがあってもスキップ
-        }
-      }
+      await this.vectorStore.deleteByFilter(this.collectionName, {
+        file_path: filePath,
+        project_id: projectId,
+      });
+    } catch (error) {
+      // エラーがあってもスキップ（コレクションが存在しない場合など）
+    }
 
-      // BM25インデックスから削除
-      for (const id of idsToDelete) {
-        try {
-          await this.bm25Engine.deleteDocument(id);
-        } catch (error) {
-          // エラーがあってもスキップ（ドキュメントが存在しない場合など）
-        }
-      }
+    // BM25インデックスからファイルのドキュメントを削除
+    try {
+      await this.bm25Engine.deleteByFile(projectId, filePath);
     } catch (error) {
       // エラーがあってもスキップ
     }
@@ -587,18 +776,94 @@ export class IndexingService extends EventEmitter {
 
   /**
    * File Watcherを有効化
+   *
+   * インデックス化済みプロジェクトのルートパスを監視し、ファイルの追加・変更・削除を
+   * BackgroundUpdateQueue経由でインデックスに反映する。除外パターンはインデックス化時の
+   * オプションを使用する。
    */
   async enableWatcher(projectId: string): Promise<void> {
-    // 注: 実際の実装では、FileWatcherインスタンスを作成して管理する
-    // この簡易実装では、外部からFileWatcherを設定することを想定
-    this.fileWatchers.set(projectId, true);
+    if (this.fileWatchers.has(projectId)) {
+      return;
+    }
+
+    const metadata = this.indexMetadata.get(projectId);
+    if (!metadata || !metadata.rootPath) {
+      throw new Error(`Project is not indexed: ${projectId}`);
+    }
+
+    const options = metadata.options ?? {};
+    const ignorePatterns = [...DEFAULT_IGNORE_PATTERNS, ...(options.excludePatterns ?? [])];
+    const isIgnored = createIgnoreMatcher(metadata.rootPath, ignorePatterns);
+    const watcher = new FileWatcher({ rootPath: metadata.rootPath, ignorePatterns });
+
+    const enqueue = (filePath: string, operation: 'update' | 'delete'): void => {
+      if (this.isWatchTarget(filePath, options, isIgnored)) {
+        this.updateQueue.enqueue(filePath, projectId, undefined, operation);
+      }
+    };
+    watcher.on(FileWatcherEvent.FILE_ADDED, (filePath: string) => enqueue(filePath, 'update'));
+    watcher.on(FileWatcherEvent.FILE_CHANGED, (filePath: string) => enqueue(filePath, 'update'));
+    watcher.on(FileWatcherEvent.FILE_DELETED, (filePath: string) => enqueue(filePath, 'delete'));
+
+    await watcher.start();
+    this.fileWatchers.set(projectId, watcher);
+    this.updateQueue.start();
   }
 
   /**
    * File Watcherを無効化
    */
   async disableWatcher(projectId: string): Promise<void> {
+    const watcher = this.fileWatchers.get(projectId);
+    if (!watcher) {
+      return;
+    }
+
+    await watcher.stop();
     this.fileWatchers.delete(projectId);
+
+    // 監視中のプロジェクトがなくなったらキュー処理も停止
+    if (this.fileWatchers.size === 0) {
+      this.updateQueue.stop();
+    }
+  }
+
+  /**
+   * すべてのFile Watcherを無効化（サーバー終了時に呼び出す）
+   */
+  async disableAllWatchers(): Promise<void> {
+    for (const projectId of Array.from(this.fileWatchers.keys())) {
+      await this.disableWatcher(projectId);
+    }
+  }
+
+  /**
+   * File Watcherが有効か
+   */
+  isWatching(projectId: string): boolean {
+    return this.fileWatchers.has(projectId);
+  }
+
+  /**
+   * プロジェクトレジストリからインデックス統計を復元（サーバー起動時に呼び出す）
+   *
+   * インデックス化中に終了したプロジェクトは'error'として復元する
+   */
+  async loadIndexMetadata(): Promise<void> {
+    const records = await this.bm25Engine.getProjects();
+
+    for (const record of records) {
+      this.indexMetadata.set(record.projectId, {
+        projectId: record.projectId,
+        rootPath: record.rootPath,
+        status: record.status === 'indexed' ? 'indexed' : 'error',
+        lastIndexed: new Date(record.lastIndexed),
+        totalFiles: record.totalFiles,
+        totalSymbols: record.totalSymbols,
+        totalVectors: record.totalVectors,
+        options: record.options as IndexingOptions,
+      });
+    }
   }
 
   /**
@@ -634,11 +899,23 @@ export class IndexingService extends EventEmitter {
    */
   async clearIndex(projectId: string): Promise<ClearResult> {
     try {
-      // メタデータをクリア
-      this.indexMetadata.delete(projectId);
+      // 監視を停止（クリア後に変更イベントで再登録されないように）
+      await this.disableWatcher(projectId);
+
+      // ベクターストアからプロジェクトのベクトルを削除
+      try {
+        await this.vectorStore.deleteByFilter(this.collectionName, { project_id: projectId });
+      } catch (error) {
+        // コレクションが存在しない場合はスキップ
+      }
+
+      // プロジェクトのBM25ドキュメントを削除（マニフェストに記録のないドキュメントも含む）
+      await this.bm25Engine.deleteByProject(projectId);
 
-      // 注: 実際の実装では、ベクターストアとBM25から該当データを削除する必要がある
-      // ここでは簡易実装として、メタデータのみクリア
+      // マニフェストとメタデータをクリア（次回のindex_projectで全ファイルを再インデックス化）
+      await this.bm25Engine.clearFileManifest(projectId);
+      await this.bm25Engine.deleteProject(projectId);
+      this.indexMetadata.delete(projectId);
 
       return { success: true };
     } catch (error: any) {
@@ -651,8 +928,12 @@ export class IndexingService extends EventEmitter {
    */
   async clearAllIndexes(): Promise<ClearResult> {
     try {
+      await this.disableAllWatchers();
       this.indexMetadata.clear();
 
+      // マニフェストを含めBM25インデックスをクリア
+      await this.bm25Engine.clearIndex();
+
       // コレクションを削除して再作成
       try {
         await this.vectorStore.deleteCollection(this.collectionName);
@@ -667,17 +948,18 @@ export class IndexingService extends EventEmitter {
   }
 
   /**
-   * メタデータを更新
+   * メタデータを更新（プロジェクトレジストリにも保存）
    */
-  private updateIndexMetadata(
+  private async updateIndexMetadata(
     projectId: string,
     rootPath: string,
     status: 'indexed' | 'indexing' | 'error',
-    stats?: { totalFiles: number; totalSymbols: number; totalVectors: number }
-  ): void {
+    stats?: { totalFiles: number; totalSymbols: number; totalVectors: number },
+    options?: IndexingOptions
+  ): Promise<void> {
     const existing = this.indexMetadata.get(projectId);
 
-    this.indexMetadata.set(projectId, {
+    const metadata: IndexStats = {
       projectId,
       rootPath,
       status,
@@ -685,16 +967,142 @@ export class IndexingService extends EventEmitter {
       totalFiles: stats?.totalFiles ?? existing?.totalFiles ?? 0,
       totalSymbols: stats?.totalSymbols ?? existing?.totalSymbols ?? 0,
       totalVectors: stats?.totalVectors ?? existing?.totalVectors ?? 0,
-    });
+      options: options ?? existing?.options ?? {},
+    };
+    this.indexMetadata.set(projectId, metadata);
+
+    try {
+      await this.bm25Engine.upsertProject({
+        projectId,
+        rootPath,
+        options: { ...metadata.options },
+        status,
+        lastIndexed: metadata.lastIndexed.getTime(),
+        totalFiles: metadata.totalFiles,
+        totalSymbols: metadata.totalSymbols,
+        totalVectors: metadata.totalVectors,
+      });
+    } catch (error) {
+      // 保存に失敗してもインデックス化は継続（再起動後に統計が失われるだけ）
+    }
+  }
+
+  /**
+   * プロジェクトのマニフェストをファイルパスをキーとするMapで取得
+   */
+  private async loadManifest(projectId: string): Promise<Map<string, FileManifestEntry>> {
+    const entries = await this.bm25Engine.getFileManifest(projectId);
+    return new Map(entries.map((entry) => [entry.filePath, entry]));
+  }
+
+  /**
+   * ファイルのサイズ・mtime・内容ハッシュを取得
+   *
+   * サイズとmtimeが前回と同じ場合はファイルを読まずに前回のハッシュを使う
+   */
+  private async computeFingerprint(
+    filePath: string,
+    previous?: FileManifestEntry
+  ): Promise<FileFingerprint> {
+    const stats = await fs.stat(filePath);
+    const size = stats.size;
+    const mtime = stats.mtimeMs;
+
+    if (previous && previous.size === size && previous.mtime === mtime) {
+      return { size, mtime, hash: previous.hash };
+    }
+
+    const content = await fs.readFile(filePath);
+    const hash = createHash('sha256').update(content).digest('hex');
+    return { size, mtime, hash };
+  }
+
+  /**
+   * インデックス化に成功したファイルをマニフェストに記録
+   */
+  private async recordManifest(
+    filePath: string,
+    projectId: string,
+    result: FileIndexResult,
+    fingerprint?: FileFingerprint
+  ): Promise<void> {
+    if (!result.success) {
+      return;
+    }
+
+    try {
+      const { size, mtime, hash } = fingerprint ?? (await this.computeFingerprint(filePath));
+      await this.bm25Engine.upsertFileManifest({
+        filePath,
+        projectId,
+        size,
+        mtime,
+        hash,
+        symbolsCount: result.symbolsCount,
+        ids: result.vectorIds ?? [],
+        indexVersion: INDEX_FORMAT_VERSION,
+      });
+    } catch (error) {
+      // マニフェストの記録に失敗しても次回再インデックス化されるだけなので無視
+    }
+  }
+
+  /**
+   * 連続する単一行コメントを1つのコメントブロックにまとめる
+   *
+   * マーカー付きのコメントはTODO単位で一覧できるよう個別に扱う
+   */
+  private mergeCommentBlocks(comments: CommentInfo[]): CommentInfo[] {
+    const blocks: CommentInfo[] = [];
+
+    for (const comment of comments) {
+      const last = blocks[blocks.length - 1];
+      if (
+        last &&
+        last.type === CommentType.SingleLine &&
+        comment.type === CommentType.SingleLine &&
+        !last.marker &&
+        !comment.marker &&
+        comment.position.startLine === last.position.endLine + 1
+      ) {
+        blocks[blocks.length - 1] = {
+          ...last,
+          content: `${last.content}\n${comment.content}`,
+          position: { ...last.position, endLine: comment.position.endLine },
+          associatedSymbol: comment.associatedSymbol ?? last.associatedSymbol,
+        };
+        continue;
+      }
+      blocks.push(comment);
+    }
+
+    return blocks;
+  }
+
+  /**
+   * 指定行を含む最も内側のシンボル名を取得（メソッドは「型名.メソッド名」）
+   */
+  private findEnclosingSymbol(symbols: SymbolInfo[], line: number): string | undefined {
+    const contains = (symbol: SymbolInfo): boolean =>
+      symbol.position.startLine <= line && line <= symbol.position.endLine;
+
+    for (const symbol of symbols) {
+      if (!contains(symbol)) continue;
+
+      const member = symbol.members?.find((m) => m.type === SymbolType.Method && contains(m));
+      return member ? `${symbol.name}.${member.name}` : symbol.name;
+    }
+
+    return undefined;
   }
 
   /**
    * シンボルからテキストを構築
    */
-  private buildSymbolText(symbol: any, content: string): string {
+  private buildSymbolText(symbol: SymbolInfo, content: string): string {
     const lines = content.split('\n');
     const snippet = lines
-      .slice(symbol.startLine - 1, symbol.endLine)
+      .slice(symbol.position.startLine, symbol.position.endLine + 1)
       .join('\n')
       .substring(0, 500);
 
@@ -726,6 +1134,56 @@ export class IndexingService extends EventEmitter {
     return map[ext] || Language.Unknown;
   }
 
+  /**
+   * ベクトルのメタデータからBM25のドキュメント属性を生成
+   *
+   * Markdownの見出しは自然言語向け、それ以外（シンボル、コメント、コードブロック）は
+   * 識別子を分割するコード向けのトークナイザーを使用します。
+   */
+  private toDocumentAttributes(metadata: Record<string, any>): BM25DocumentAttributes {
+    return {
+      projectId: metadata.project_id,
+      language: metadata.language,
+      filePath: metadata.file_path,
+      fileType: metadata.file_type,
+      symbolType: metadata.type,
+      tokenizer: metadata.language === 'markdown' ? 'text' : 'code',
+    };
+  }
+
+  /**
+   * ファイルタイプ（先頭のドットを除いた拡張子）を取得
+   */
+  private getFileType(filePath: string): string {
+    return normalizeFileType(path.extname(filePath));
+  }
+
+  /**
+   * File Watcherのイベントをインデックスに反映する対象か判定
+   * @param isIgnored 除外パターン（デフォルトの除外パターンとexcludePatterns）の判定関数
+   */
+  private isWatchTarget(
+    filePath: string,
+    options: IndexingOptions,
+    isIgnored: IgnoreMatcher
+  ): boolean {
+    if (isIgnored(filePath)) {
+      return false;
+    }
+
+    const ext = path.extname(filePath);
+    if (ext === '.md') {
+      return options.includeDocuments !== false;
+    }
+    if (!this.isCodeFile(ext)) {
+      return false;
+    }
+    if (options.languages && options.languages.length > 0) {
+      return options.languages.includes(this.getLanguageFromExtension(ext));
+    }
+    return true;
+  }
+
   /**
    * コードファイルかどうか判定
    */
@@ -0,0 +1,352 @@
+/**
+ * Search Query Parser: search_codeのクエリ構文を解析する
+ *
+ * クエリ中のフィールド指定を検索フィルタと取得後の条件に変換し、残りを
+ * BM25用のキーワードクエリと埋め込み用の自然文に分けます。
+ *
+ * クエリ構文:
+ * - `lang:ts` / `language:python`: 言語で絞り込み（`ts`・`py`等の略称に対応）
+ * - `path:src/storage/**`: ファイルパスのglob（`**`は任意の階層、`*`・`?`は区切り内）
+ * - `type:function`: シンボル種別で絞り込み
+ * - `name:parse*`: シンボル名のglob（大文字・小文字を区別しない）
+ * - `-path:tests/**`等: フィールド指定の除外
+ * - `"phrase"`・`-term`・`a OR b`: BM25のフレーズ・除外・OR（`bm25-query.ts`を参照）
+ *
+ * フィールドの値は`path:"my dir/**"`のようにダブルクォートで囲めます。
+ * 未知のフィールド（`std::vec`等）は通常の単語として扱います。
+ */
+
+import type { SearchFilter } from './hybrid-search-engine';
+import { SymbolType } from '../parser/types';
+
+/**
+ * フィールド名
+ */
+export type SearchQueryField = 'lang' | 'path' | 'type' | 'name';
+
+/**
+ * 取得後に検索結果へ適用する条件
+ */
+export interface SearchResultCondition {
+  /** 対象フィールド */
+  field: SearchQueryField;
+  /** 値のパターン */
+  pattern: RegExp;
+  /** trueの場合は一致する結果を除外 */
+  negated: boolean;
+}
+
+/**
+ * 解析済みの検索クエリ
+ */
+export interface ParsedSearchQuery {
+  /** BM25に渡すクエリ（フレーズ・除外・ORを保持し、フィールド指定を除いたもの） */
+  keywordQuery: string;
+  /** 埋め込みに使う自然文（演算子と除外する単語を除いたもの） */
+  semanticQuery: string;
+  /** 検索時に各検索へ渡すフィルタ */
+  filter: SearchFilter;
+  /** 取得後に適用する条件（パス・シンボル名のglobと除外指定） */
+  conditions: SearchResultCondition[];
+}
+
+/**
+ * 条件の判定対象となる検索結果
+ */
+export interface SearchQueryTarget {
+  /** ドキュメントID（`${filePath}:${kind}:${qualifiedName}`形式） */
+  id: string;
+  /** メタデータ */
+  metadata?: Record<string, unknown>;
+}
+
+/**
+ * 検索結果のIDの構成要素
+ */
+interface ParsedResultId {
+  filePath?: string;
+  kind?: string;
+  qualifiedName?: string;
+}
+
+/**
+ * IDに現れるエントリの種別（シンボル種別と、チャンク・コメント・見出し・コードブロック）
+ */
+const RESULT_ID_KINDS: ReadonlySet<string> = new Set<string>([
+  ...Object.values(SymbolType),
+  'chunk',
+  'comment',
+  'heading',
+  'code_block',
+]);
+
+/**
+ * フィールド名の別名
+ */
+const FIELD_ALIASES: Record<string, SearchQueryField> = {
+  lang: 'lang',
+  language: 'lang',
+  path: 'path',
+  type: 'type',
+  kind: 'type',
+  name: 'name',
+  symbol: 'name',
+};
+
+/**
+ * 言語名の略称
+ */
+const LANGUAGE_ALIASES: Record<string, string> = {
+  ts: 'typescript',
+  tsx: 'typescript',
+  js: 'javascript',
+  jsx: 'javascript',
+  py: 'python',
+  rs: 'rust',
+  golang: 'go',
+  'c++': 'cpp',
+  cc: 'cpp',
+  md: 'markdown',
+};
+
+/**
+ * クエリの構成要素（フィールド指定・フレーズ・単語、先頭の`-`は除外）
+ */
+const QUERY_ITEM_PATTERN = /(-?)(?:([A-Za-z]+):"([^"]*)"|"([^"]*)"|(\S+))/g;
+
+/**
+ * 単語形式のフィールド指定（`field:value`）
+ */
+const FIELD_PATTERN = /^([A-Za-z]+):(.+)$/;
+
+/**
+ * 言語名を正規化（小文字化して略称を展開）
+ */
+export function normalizeLanguage(language: string): string {
+  const lower = language.toLowerCase();
+  return LANGUAGE_ALIASES[lower] ?? lower;
+}
+
+/**
+ * globを正規表現に変換
+ *
+ * @param glob globパターン（`**`・`*`・`?`）
+ * @param options.pathSegments trueの場合はパスとして扱い、パス区切りの直後から末尾までの一致を要求
+ * @returns 正規表現（パス以外は大文字・小文字を区別しない）
+ */
+export function globToRegExp(glob: string, options: { pathSegments?: boolean } = {}): RegExp {
+  let source = '';
+  for (let i = 0; i < glob.length; i++) {
+    const char = glob[i];
+    if (char === '*' && glob[i + 1] === '*') {
+      // `**/`は0個以上のディレクトリに一致させる
+      const slash = glob[i + 2] === '/';
+      source += slash ? '(?:.*/)?' : '.*';
+      i += slash ? 2 : 1;
+    } else if (char === '*') {
+      source += options.pathSegments ? '[^/]*' : '.*';
+    } else if (char === '?') {
+      source += options.pathSegments ? '[^/]' : '.';
+    } else {
+      source += char.replace(/[.+^${}()|[\]\\]/g, '\\$&');
+    }
+  }
+
+  return options.pathSegments ? new RegExp(`(?:^|/)${source}$`) : new RegExp(`^${source}$`, 'i');
+}
+
+/**
+ * globのうちワイルドカードを含まない最長の部分
+ */
+function longestLiteral(glob: string): string {
+  return glob
+    .split(/[*?]+/)
+    .reduce((longest, part) => (part.length > longest.length ? part : longest), '');
+}
+
+/**
+ * search_codeのクエリを解析
+ *
+ * @example
+ * ```typescript
+ * parseSearchQuery('lang:ts path:src/storage/** "upsert vectors" -test');
+ * // keywordQuery: '"upsert vectors" -test', semanticQuery: 'upsert vectors'
+ * // filter: { languages: ['typescript'], pathPattern: 'src/storage/' }
+ * ```
+ *
+ * @param query 検索クエリ
+ * @returns 解析済みクエリ
+ */
+export function parseSearchQuery(query: string): ParsedSearchQuery {
+  const keywordItems: string[] = [];
+  const semanticItems: string[] = [];
+  const languages: string[] = [];
+  const types: string[] = [];
+  const paths: string[] = [];
+  const conditions: SearchResultCondition[] = [];
+
+  for (const match of query.matchAll(QUERY_ITEM_PATTERN)) {
+    const [item, negated, quotedField, quotedFieldValue, , word] = match;
+    const fieldMatch = quotedField !== undefined ? null : word?.match(FIELD_PATTERN);
+    const field = FIELD_ALIASES[(quotedField ?? fieldMatch?.[1] ?? '').toLowerCase()];
+    const value = quotedFieldValue ?? fieldMatch?.[2];
+
+    if (!field || !value) {
+      // 通常の単語・フレーズ（未知のフィールドを含む）
+      keywordItems.push(item);
+      if (!negated && item !== 'OR') {
+        semanticItems.push(item.replace(/"/g, ''));
+      }
+      continue;
+    }
+
+    // フィールド指定の直前のORは連結先がなくなるため除く
+    if (keywordItems[keywordItems.length - 1] === 'OR') {
+      keywordItems.pop();
+    }
+
+    if (negated) {
+      const pattern =
+        field === 'path'
+          ? globToRegExp(value, { pathSegments: true })
+          : globToRegExp(field === 'lang' ? normalizeLanguage(value) : value);
+      conditions.push({ field, pattern, negated: true });
+      continue;
+    }
+
+    switch (field) {
+      case 'lang':
+        languages.push(normalizeLanguage(value));
+        break;
+      case 'type':
+        types.push(value.toLowerCase());
+        break;
+      case 'path':
+        paths.push(value);
+        conditions.push({ field, pattern: globToRegExp(value, { pathSegments: true }), negated });
+        break;
+      case 'name': {
+        conditions.push({ field, pattern: globToRegExp(value), negated });
+        // 名前の固定部分は検索語としても使う
+        const literal = longestLiteral(value);
+        if (literal) {
+          keywordItems.push(literal);
+          semanticItems.push(literal);
+        }
+        break;
+      }
+    }
+  }
+
+  const filter: SearchFilter = {};
+  if (languages.length > 0) {
+    filter.languages = languages;
+  }
+  if (types.length > 0) {
+    filter.types = types;
+  }
+  // パスは1つだけ指定された場合に固定部分で事前に絞り込む（globの判定は取得後）
+  if (paths.length === 1 && longestLiteral(paths[0])) {
+    filter.pathPattern = longestLiteral(paths[0]);
+  }
+
+  return {
+    keywordQuery: keywordItems.join(' '),
+    semanticQuery: semanticItems.join(' ').trim(),
+    filter,
+    conditions,
+  };
+}
+
+/**
+ * 検索結果のIDを構成要素に分解
+ *
+ * IDは`${projectId}:${filePath}:${kind}:${qualifiedName}`形式ですが、ファイルパス（Windowsの
+ * ドライブ等）や修飾名にも`:`が含まれ得るため、末尾から既知の種別を挟む区切りを探します。
+ *
+ * @param id 検索結果のID
+ * @param projectId 結果のプロジェクトID（指定時はファイルパスから前置部分を除く）
+ */
+function parseResultId(id: string, projectId?: string): ParsedResultId {
+  let position = id.length;
+  while (position > 0) {
+    const separator = id.lastIndexOf(':', position - 1);
+    if (separator < 0) {
+      break;
+    }
+    const next = id.indexOf(':', separator + 1);
+    const kind = id.slice(separator + 1, next < 0 ? id.length : next);
+    if (RESULT_ID_KINDS.has(kind)) {
+      const location = id.slice(0, separator);
+      return {
+        filePath:
+          projectId && location.startsWith(`${projectId}:`)
+            ? location.slice(projectId.length + 1)
+            : location,
+        kind,
+        qualifiedName: next < 0 ? undefined : id.slice(next + 1),
+      };
+    }
+    position = separator;
+  }
+  return {};
+}
+
+/**
+ * 検索結果のフィールド値を取得
+ */
+function fieldValue(field: SearchQueryField, target: SearchQueryTarget): string | undefined {
+  const metadata = target.metadata ?? {};
+  const { filePath, kind, qualifiedName } = parseResultId(
+    target.id,
+    metadata['project_id'] as string | undefined
+  );
+
+  switch (field) {
+    case 'path':
+      return (metadata['file_path'] as string | undefined) ?? filePath;
+    case 'type':
+      return (metadata['type'] as string | undefined) ?? kind;
+    case 'lang':
+      return (metadata['language'] as string | undefined)?.toLowerCase();
+    case 'name':
+      // BM25のみの結果はシンボル名のメタデータを持たないため、IDの修飾名の末尾を使う
+      return (
+        (metadata['name'] as string | undefined) ??
+        qualifiedName?.replace(/#\d+$/, '').split('.').pop()
+      );
+  }
+}
+
+/**
+ * 検索結果が取得後の条件を満たすか判定
+ *
+ * 同じフィールドの条件はいずれかに一致すればよく、
+ * 異なるフィールドの条件はすべてを満たす必要があります。
+ * 除外条件は値を判定できない結果には適用しません。
+ *
+ * @param conditions 取得後の条件
+ * @param target 検索結果
+ * @returns 条件を満たす場合true
+ */
+export function matchesSearchConditions(
+  conditions: SearchResultCondition[],
+  target: SearchQueryTarget
+): boolean {
+  const required = new Map<SearchQueryField, boolean>();
+
+  for (const condition of conditions) {
+    const value = fieldValue(condition.field, target);
+    const matched = value !== undefined && condition.pattern.test(value);
+
+    if (condition.negated) {
+      if (matched) {
+        return false;
+      }
+    } else {
+      required.set(condition.field, (required.get(condition.field) ?? false) || matched);
+    }
+  }
+
+  return Array.from(required.values()).every(Boolean);
+}
@@ -0,0 +1,85 @@
+/**
+ * Vector ID: ベクトル/BM25ドキュメントのIDを生成する
+ *
+ * IDは `${projectId}:${filePath}:${kind}:${qualifiedName}` 形式で、行番号に依存しないため
+ * ファイル先頭に行を挿入しても変化しません。同じディレクトリを複数のプロジェクトとして
+ * インデックス化しても互いのベクトル/ドキュメントを上書きしないよう、プロジェクトIDを含めます。
+ * 同一ファイル内で同じ種別・名前が重複する場合（オーバーロード、同名の見出し等）は
+ * 出現順に `#2`, `#3`... を付与します。
+ * 長いパスや見出しでIDが上限を超える場合は、先頭部分にIDのハッシュを付けた形に短縮します。
+ */
+
+import { createHash } from 'crypto';
+
+/**
+ * IDの最大長（UTF-8のバイト数）。Milvusの主キー（VarChar、max_length 512）に合わせる
+ */
+export const MAX_VECTOR_ID_BYTES = 512;
+
+/**
+ * 短縮時に付与するハッシュの桁数
+ */
+const ID_HASH_LENGTH = 16;
+
+/**
+ * IDを上限のバイト数に収める
+ *
+ * 上限を超える場合は `${先頭部分}~${ハッシュ}` 形式にします。ハッシュは元のID全体から
+ * 計算するため、先頭部分が同じでも元のIDが異なれば別のIDになります。
+ */
+export function boundVectorId(id: string, maxBytes: number = MAX_VECTOR_ID_BYTES): string {
+  if (Buffer.byteLength(id, 'utf8') <= maxBytes) {
+    return id;
+  }
+
+  const hash = createHash('sha256').update(id).digest('hex').slice(0, ID_HASH_LENGTH);
+  const prefixBytes = maxBytes - ID_HASH_LENGTH - 1;
+
+  // マルチバイト文字の途中で切らないよう、文字単位で先頭部分を詰める
+  let prefix = '';
+  let bytes = 0;
+  for (const char of id) {
+    const charBytes = Buffer.byteLength(char, 'utf8');
+    if (bytes + charBytes > prefixBytes) {
+      break;
+    }
+    prefix += char;
+    bytes += charBytes;
+  }
+
+  return `${prefix}~${hash}`;
+}
+
+/**
+ * ファイル単位のベクトルIDアロケータ
+ *
+ * 出現順に `allocate` を呼び出すことで、同じ内容のファイルからは常に同じIDが生成されます。
+ */
+export class VectorIdAllocator {
+  private counts: Map<string, number> = new Map();
+
+  /**
+   * @param projectId プロジェクトID
+   * @param filePath ファイルパス
+   */
+  constructor(
+    private projectId: string,
+    private filePath: string
+  ) {}
+
+  /**
+   * IDを割り当て
+   * @param kind エントリの種別（シンボル種別、'comment'、'heading'等）
+   * @param qualifiedName 修飾名（例: `Type.method`）。空の場合は種別内の出現順で区別
+   * @returns ベクトルID（`MAX_VECTOR_ID_BYTES`以内）
+   */
+  allocate(kind: string, qualifiedName: string = ''): string {
+    const prefix = `${this.projectId}:${this.filePath}:${kind}`;
+    const base = qualifiedName ? `${prefix}:${qualifiedName}` : prefix;
+
+    const count = (this.counts.get(base) ?? 0) + 1;
+    this.counts.set(base, count);
+
+    return boundVectorId(count === 1 ? base : `${base}#${count}`);
+  }
+}
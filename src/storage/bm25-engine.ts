@@ -2,6 +2,17 @@ import type BetterSqlite3 from 'better-sqlite3';
 import Database from 'better-sqlite3';
 import * as fs from 'fs/promises';
 import * as path from 'path';
+import { tokenize as tokenizeWithPositions, type TokenizerType } from './tokenizer.js';
+import { encodePositions, decodePositions } from './position-codec.js';
+import {
+  parseBM25Query,
+  hasConstraints,
+  constraintTerms,
+  satisfiesConstraints,
+  proximityScore,
+  type ParsedBM25Query,
+  type TermPositions,
+} from './bm25-query.js';
 
 /**
  * BM25パラメータ
@@ -11,6 +22,8 @@ export interface BM25Params {
   k1: number;
   /** ドキュメント長の正規化を制御 (デフォルト: 0.75) */
   b: number;
+  /** クエリタームが近接して出現する場合のブーストの重み (デフォルト: 0.5、0で無効) */
+  proximityWeight: number;
 }
 
 /**
@@ -23,6 +36,102 @@ export interface SearchResult {
   score: number;
 }
 
+/**
+ * ドキュメント属性（検索フィルタの対象）
+ */
+export interface BM25DocumentAttributes {
+  /** プロジェクトID */
+  projectId?: string;
+  /** 言語（小文字で保存） */
+  language?: string;
+  /** ファイルパス */
+  filePath?: string;
+  /** ファイルタイプ（先頭のドットを除いた拡張子） */
+  fileType?: string;
+  /** シンボル種別（function, class, comment等） */
+  symbolType?: string;
+  /** トークナイザーの種類（デフォルト: text） */
+  tokenizer?: TokenizerType;
+}
+
+/**
+ * 一括インデックス化するドキュメント
+ */
+export interface BM25Document {
+  /** ドキュメントID */
+  documentId: string;
+  /** ドキュメント内容 */
+  content: string;
+  /** ドキュメント属性（検索時の絞り込みに使用） */
+  attributes?: BM25DocumentAttributes;
+}
+
+/**
+ * 検索フィルタ（SQLで適用、すべての条件をANDで結合）
+ */
+export interface BM25SearchFilter {
+  /** プロジェクトID */
+  projectId?: string;
+  /** 言語（いずれかに一致、大文字・小文字を区別しない） */
+  languages?: string[];
+  /** ファイルタイプ（いずれかに一致、先頭のドットは無視） */
+  fileTypes?: string[];
+  /** ファイルパスの部分一致 */
+  pathPattern?: string;
+  /** シンボル種別（いずれかに一致） */
+  types?: string[];
+}
+
+/**
+ * document_statsに保持する属性列（旧スキーマのマイグレーション対象）
+ */
+const DOCUMENT_ATTRIBUTE_COLUMNS = [
+  'project_id',
+  'language',
+  'file_path',
+  'file_type',
+  'symbol_type',
+];
+
+/**
+ * 転置インデックスのテーブル定義（positionsは`position-codec.ts`でエンコードしたBLOB）
+ */
+const INVERTED_INDEX_COLUMNS = `
+  term TEXT NOT NULL,
+  document_id TEXT NOT NULL,
+  frequency INTEGER NOT NULL,
+  positions BLOB NOT NULL,
+  PRIMARY KEY (term, document_id)
+`;
+
+/**
+ * ファイルマニフェストのテーブル定義（同じファイルを複数のプロジェクトが登録できるよう複合キー）
+ */
+const FILE_MANIFEST_COLUMNS = `
+  file_path TEXT NOT NULL,
+  project_id TEXT NOT NULL,
+  size INTEGER NOT NULL,
+  mtime REAL NOT NULL,
+  hash TEXT NOT NULL,
+  symbols_count INTEGER NOT NULL,
+  ids TEXT NOT NULL,
+  index_version INTEGER NOT NULL DEFAULT 0,
+  PRIMARY KEY (project_id, file_path)
+`;
+
+/**
+ * 出現位置をJSONからBLOBに移行する際に1度に変換する行数
+ */
+const POSITIONS_MIGRATION_BATCH_SIZE = 10000;
+
+/**
+ * キャッシュするプリペアドステートメントの上限数
+ *
+ * 検索のSQLはクエリのターム数やフィルタの値の数でプレースホルダーの数が変わるため、
+ * 上限を超えた場合は最も長く使われていないものから破棄します。
+ */
+export const MAX_CACHED_STATEMENTS = 64;
+
 /**
  * 転置インデックスのエントリ
  */
@@ -46,35 +155,73 @@ export interface DocumentStats {
 }
 
 /**
- * 英語の一般的なストップワード
+ * ファイルマニフェストのエントリ（インクリメンタル再インデックス用）
+ */
+export interface FileManifestEntry {
+  /** ファイルパス */
+  filePath: string;
+  /** プロジェクトID */
+  projectId: string;
+  /** ファイルサイズ（バイト） */
+  size: number;
+  /** 最終更新時刻（ミリ秒） */
+  mtime: number;
+  /** ファイル内容のハッシュ（SHA-256） */
+  hash: string;
+  /** 抽出されたシンボル数 */
+  symbolsCount: number;
+  /** ファイルから生成されたベクトル/ドキュメントID */
+  ids: string[];
+  /** インデックス化したときのインデックス形式のバージョン（未記録の場合は0） */
+  indexVersion?: number;
+}
+
+/**
+ * プロジェクトレジストリのレコード（再起動後にインデックス状態を復元するため）
+ */
+export interface ProjectRecord {
+  /** プロジェクトID */
+  projectId: string;
+  /** ルートパス */
+  rootPath: string;
+  /** インデックス化に使用したオプション */
+  options: Record<string, unknown>;
+  /** ステータス */
+  status: string;
+  /** 最終インデックス化日時（ミリ秒） */
+  lastIndexed: number;
+  /** 総ファイル数 */
+  totalFiles: number;
+  /** 総シンボル数 */
+  totalSymbols: number;
+  /** 総ベクトル数 */
+  totalVectors: number;
+}
+
+/**
+ * クエリタームの出現（転置インデックスとドキュメント長を結合した行）
+ */
+interface Posting {
+  /** ターム */
+  term: string;
+  /** ドキュメントID */
+  documentId: string;
+  /** ターム出現頻度 */
+  frequency: number;
+  /** ドキュメント長 */
+  length: number;
+  /** ターム出現位置（要求した場合のみ） */
+  positions?: number[];
+}
+
+/**
+ * ファイルタイプを正規化（先頭の"."を除去し小文字化）
+ *
+ * BM25のドキュメント属性・検索フィルタとベクターストアのメタデータフィルタで共通して使用します。
  */
-const STOP_WORDS = new Set([
-  'a',
-  'an',
-  'and',
-  'are',
-  'as',
-  'at',
-  'be',
-  'by',
-  'for',
-  'from',
-  'has',
-  'he',
-  'in',
-  'is',
-  'it',
-  'its',
-  'of',
-  'on',
-  'that',
-  'the',
-  'this',
-  'to',
-  'was',
-  'will',
-  'with',
-]);
+export function normalizeFileType(fileType: string): string {
+  return fileType.replace(/^\./, '').toLowerCase();
+}
 
 /**
  * BM25全文検索エンジン
@@ -83,11 +230,15 @@ export class BM25Engine {
   private db: BetterSqlite3.Database | null = null;
   private params: BM25Params;
   private isInitialized = false;
+  /** プリペアドステートメントのキャッシュ（SQL文字列をキーとし、使用順に並ぶLRU） */
+  private statements: Map<string, BetterSqlite3.Statement> = new Map();
+  /** コーパス統計のキャッシュ（インデックスの更新時に破棄） */
+  private corpusStats: DocumentStats | null = null;
 
   /**
    * BM25エンジンを初期化
    * @param dbPath SQLiteデータベースファイルのパス
-   * @param params BM25パラメータ（デフォルト: k1=1.5, b=0.75）
+   * @param params BM25パラメータ（デフォルト: k1=1.5, b=0.75, proximityWeight=0.5）
    */
   constructor(
     private dbPath: string,
@@ -96,6 +247,7 @@ export class BM25Engine {
     this.params = {
       k1: params?.k1 ?? 1.5,
       b: params?.b ?? 0.75,
+      proximityWeight: params?.proximityWeight ?? 0.5,
     };
   }
 
@@ -129,21 +281,61 @@ export class BM25Engine {
     }
 
     // 転置インデックステーブル
-    this.db.exec(`
-      CREATE TABLE IF NOT EXISTS inverted_index (
-        term TEXT NOT NULL,
-        document_id TEXT NOT NULL,
-        frequency INTEGER NOT NULL,
-        positions TEXT NOT NULL,
-        PRIMARY KEY (term, document_id)
-      )
-    `);
+    this.db.exec(`CREATE TABLE IF NOT EXISTS inverted_index (${INVERTED_INDEX_COLUMNS})`);
+
+    // 出現位置をJSON文字列で保存していた旧スキーマを移行
+    this.migratePositionsToBlob();
 
     // ドキュメント統計テーブル
     this.db.exec(`
       CREATE TABLE IF NOT EXISTS document_stats (
         document_id TEXT PRIMARY KEY,
-        length INTEGER NOT NULL
+        length INTEGER NOT NULL,
+        project_id TEXT,
+        language TEXT,
+        file_path TEXT,
+        file_type TEXT,
+        symbol_type TEXT
+      )
+    `);
+
+    // 旧スキーマのデータベースには不足している属性列を追加
+    const columns = this.db.prepare(`PRAGMA table_info(document_stats)`).all() as Array<{
+      name: string;
+    }>;
+    for (const column of DOCUMENT_ATTRIBUTE_COLUMNS) {
+      if (!columns.some((existing) => existing.name === column)) {
+        this.db.exec(`ALTER TABLE document_stats ADD COLUMN ${column} TEXT`);
+      }
+    }
+
+    // ファイルマニフェストテーブル（再起動後も変更のないファイルをスキップするため）
+    this.db.exec(`CREATE TABLE IF NOT EXISTS file_manifest (${FILE_MANIFEST_COLUMNS})`);
+
+    // ファイルパスのみを主キーとしていた旧スキーマを移行
+    this.migrateManifestKey();
+
+    // インデックス形式のバージョン列がない旧スキーマには列を追加（既存の行は0）
+    const manifestColumns = this.db.prepare(`PRAGMA table_info(file_manifest)`).all() as Array<{
+      name: string;
+    }>;
+    if (!manifestColumns.some((column) => column.name === 'index_version')) {
+      this.db.exec(
+        `ALTER TABLE file_manifest ADD COLUMN index_version INTEGER NOT NULL DEFAULT 0`
+      );
+    }
+
+    // プロジェクトレジストリテーブル
+    this.db.exec(`
+      CREATE TABLE IF NOT EXISTS project_registry (
+        project_id TEXT PRIMARY KEY,
+        root_path TEXT NOT NULL,
+        options TEXT NOT NULL,
+        status TEXT NOT NULL,
+        last_indexed INTEGER NOT NULL,
+        total_files INTEGER NOT NULL,
+        total_symbols INTEGER NOT NULL,
+        total_vectors INTEGER NOT NULL
       )
     `);
 
@@ -154,89 +346,266 @@ export class BM25Engine {
     this.db.exec(`
       CREATE INDEX IF NOT EXISTS idx_document ON inverted_index(document_id);
     `);
+    this.db.exec(`
+      CREATE INDEX IF NOT EXISTS idx_manifest_project ON file_manifest(project_id);
+    `);
+    this.db.exec(`
+      CREATE INDEX IF NOT EXISTS idx_document_project ON document_stats(project_id);
+    `);
+    this.db.exec(`
+      CREATE INDEX IF NOT EXISTS idx_document_language ON document_stats(language);
+    `);
   }
 
   /**
-   * テキストをトークン化
-   * @param text 入力テキスト
-   * @returns トークンの配列
+   * 出現位置をJSON文字列（TEXT列）で保存していた転置インデックスをBLOB列に移行
+   *
+   * 新しいテーブルに変換しながら移し替え、旧テーブルと置き換えてから領域を解放します。
    */
-  tokenize(text: string): string[] {
-    if (!text || text.trim().length === 0) {
-      return [];
+  private migratePositionsToBlob(): void {
+    const db = this.db;
+    if (!db) {
+      throw new Error('Database not initialized');
     }
 
-    // 小文字化して記号を分割
-    const tokens = text
-      .toLowerCase()
-      .split(/[\s\-_@.,:;!?(){}[\]<>/\\|"'`~]+/)
-      .filter((token) => token.length > 0)
-      .filter((token) => !STOP_WORDS.has(token));
+    const columns = db.prepare(`PRAGMA table_info(inverted_index)`).all() as Array<{
+      name: string;
+      type: string;
+    }>;
+    const positionsColumn = columns.find((column) => column.name === 'positions');
+    if (!positionsColumn || positionsColumn.type.toUpperCase() === 'BLOB') {
+      return;
+    }
+
+    const select = db.prepare(`
+      SELECT rowid, term, document_id, frequency, positions FROM inverted_index
+      WHERE rowid > ? ORDER BY rowid LIMIT ?
+    `);
+
+    const migrate = db.transaction(() => {
+      db.exec(`DROP TABLE IF EXISTS inverted_index_migration`);
+      db.exec(`CREATE TABLE inverted_index_migration (${INVERTED_INDEX_COLUMNS})`);
+      const insert = db.prepare(`
+        INSERT INTO inverted_index_migration (term, document_id, frequency, positions)
+        VALUES (?, ?, ?, ?)
+      `);
+
+      // 大きなインデックスでもメモリに載せきらないよう行IDの順に分割して変換
+      let lastRowId = 0;
+      for (;;) {
+        const rows = select.all(lastRowId, POSITIONS_MIGRATION_BATCH_SIZE) as Array<{
+          rowid: number;
+          term: string;
+          document_id: string;
+          frequency: number;
+          positions: string | Buffer;
+        }>;
+        if (rows.length === 0) {
+          break;
+        }
 
-    return tokens;
+        for (const row of rows) {
+          const positions =
+            typeof row.positions === 'string'
+              ? encodePositions(JSON.parse(row.positions) as number[])
+              : row.positions;
+          insert.run(row.term, row.document_id, row.frequency, positions);
+        }
+        lastRowId = rows[rows.length - 1].rowid;
+      }
+
+      // 旧テーブルのインデックスは削除されるため、createTablesで作り直す
+      db.exec(`DROP TABLE inverted_index`);
+      db.exec(`ALTER TABLE inverted_index_migration RENAME TO inverted_index`);
+    });
+
+    migrate();
+
+    // 旧テーブルが使っていた領域をファイルから解放
+    db.exec('VACUUM');
+  }
+
+  /**
+   * ファイルパスのみを主キーとしていたファイルマニフェストを(project_id, file_path)の複合キーに移行
+   */
+  private migrateManifestKey(): void {
+    const db = this.db;
+    if (!db) {
+      throw new Error('Database not initialized');
+    }
+
+    const columns = db.prepare(`PRAGMA table_info(file_manifest)`).all() as Array<{
+      name: string;
+      pk: number;
+    }>;
+    const projectColumn = columns.find((column) => column.name === 'project_id');
+    if (!projectColumn || projectColumn.pk > 0) {
+      return;
+    }
+
+    const migrate = db.transaction(() => {
+      db.exec(`DROP TABLE IF EXISTS file_manifest_migration`);
+      db.exec(`CREATE TABLE file_manifest_migration (${FILE_MANIFEST_COLUMNS})`);
+      db.exec(`
+        INSERT INTO file_manifest_migration
+          (file_path, project_id, size, mtime, hash, symbols_count, ids)
+        SELECT file_path, project_id, size, mtime, hash, symbols_count, ids FROM file_manifest
+      `);
+
+      // 旧テーブルのインデックスは削除されるため、createTablesで作り直す
+      db.exec(`DROP TABLE file_manifest`);
+      db.exec(`ALTER TABLE file_manifest_migration RENAME TO file_manifest`);
+    });
+
+    migrate();
+  }
+
+  /**
+   * プリペアドステートメントを取得（同じSQLは再利用）
+   *
+   * キャッシュは`MAX_CACHED_STATEMENTS`件までで、超えた場合は最も古いものを破棄します。
+   *
+   * @param sql SQL文
+   * @returns プリペアドステートメント
+   */
+  private prepare(sql: string): BetterSqlite3.Statement {
+    if (!this.db) {
+      throw new Error('Database not initialized');
+    }
+
+    let statement = this.statements.get(sql);
+    if (statement) {
+      // Mapの挿入順を使用順として扱うため、末尾に移動する
+      this.statements.delete(sql);
+    } else {
+      statement = this.db.prepare(sql);
+    }
+    this.statements.set(sql, statement);
+
+    if (this.statements.size > MAX_CACHED_STATEMENTS) {
+      const oldest = this.statements.keys().next().value as string;
+      this.statements.delete(oldest);
+    }
+    return statement;
+  }
+
+  /**
+   * キャッシュしているプリペアドステートメントの数を取得（テスト用）
+   */
+  getCachedStatementCount(): number {
+    return this.statements.size;
+  }
+
+  /**
+   * テキストをトークン化
+   * @param text 入力テキスト
+   * @param tokenizer トークナイザーの種類（デフォルト: text）
+   * @returns トークンの配列
+   */
+  tokenize(text: string, tokenizer: TokenizerType = 'text'): string[] {
+    return tokenizeWithPositions(text, tokenizer).map((token) => token.term);
   }
 
   /**
    * ドキュメントをインデックス化
    * @param documentId ドキュメントID
    * @param content ドキュメント内容
+   * @param attributes ドキュメント属性（検索時の絞り込みに使用）
+   */
+  async indexDocument(
+    documentId: string,
+    content: string,
+    attributes: BM25DocumentAttributes = {}
+  ): Promise<void> {
+    await this.indexDocuments([{ documentId, content, attributes }]);
+  }
+
+  /**
+   * 複数のドキュメントを1つのトランザクションでインデックス化
+   *
+   * 既存のドキュメントは置き換えます。同じIDが複数含まれる場合は後のものが有効です。
+   *
+   * @param documents インデックス化するドキュメントの配列
    */
-  async indexDocument(documentId: string, content: string): Promise<void> {
+  async indexDocuments(documents: BM25Document[]): Promise<void> {
     if (!this.db) {
       throw new Error('Database not initialized');
     }
 
-    // 既存のインデックスを削除
-    await this.deleteDocument(documentId);
-
-    const tokens = this.tokenize(content);
-    const termFrequency = new Map<string, { freq: number; positions: number[] }>();
+    if (documents.length === 0) {
+      return;
+    }
 
-    // ターム頻度と位置を計算
-    tokens.forEach((token, index) => {
-      const entry = termFrequency.get(token) ?? { freq: 0, positions: [] };
-      entry.freq++;
-      entry.positions.push(index);
-      termFrequency.set(token, entry);
+    // ターム頻度と位置を計算（トランザクション外で行いロック時間を短くする）
+    const entries = documents.map(({ documentId, content, attributes = {} }) => {
+      const tokens = tokenizeWithPositions(content, attributes.tokenizer ?? 'text');
+      const termFrequency = new Map<string, { freq: number; positions: number[] }>();
+      for (const { term, position } of tokens) {
+        const entry = termFrequency.get(term) ?? { freq: 0, positions: [] };
+        entry.freq++;
+        entry.positions.push(position);
+        termFrequency.set(term, entry);
+      }
+      return { documentId, attributes, length: tokens.length, termFrequency };
     });
 
-    // トランザクション開始
-    const insertTerm = this.db.prepare(`
+    const deleteTerms = this.prepare(`DELETE FROM inverted_index WHERE document_id = ?`);
+    const insertTerm = this.prepare(`
       INSERT OR REPLACE INTO inverted_index (term, document_id, frequency, positions)
       VALUES (?, ?, ?, ?)
     `);
 
-    const insertStats = this.db.prepare(`
-      INSERT OR REPLACE INTO document_stats (document_id, length)
-      VALUES (?, ?)
+    const insertStats = this.prepare(`
+      INSERT OR REPLACE INTO document_stats
+        (document_id, length, project_id, language, file_path, file_type, symbol_type)
+      VALUES (?, ?, ?, ?, ?, ?, ?)
     `);
 
     const transaction = this.db.transaction(() => {
-      // 転置インデックスに追加
-      for (const [term, data] of termFrequency) {
-        insertTerm.run(term, documentId, data.freq, JSON.stringify(data.positions));
-      }
+      for (const { documentId, attributes, length, termFrequency } of entries) {
+        // 既存のインデックスを削除してから転置インデックスに追加
+        deleteTerms.run(documentId);
+        for (const [term, data] of termFrequency) {
+          insertTerm.run(term, documentId, data.freq, encodePositions(data.positions));
+        }
 
-      // ドキュメント統計を記録
-      insertStats.run(documentId, tokens.length);
+        // ドキュメント統計を記録（既存の行は置き換え）
+        insertStats.run(
+          documentId,
+          length,
+          attributes.projectId ?? null,
+          attributes.language?.toLowerCase() ?? null,
+          attributes.filePath ?? null,
+          attributes.fileType ? normalizeFileType(attributes.fileType) : null,
+          attributes.symbolType ?? null
+        );
+      }
     });
 
     transaction();
+    this.corpusStats = null;
   }
 
   /**
    * BM25検索を実行
+   *
+   * `"..."`で囲んだ部分はフレーズとして扱い、タームが連続して出現するドキュメントのみを返します。
+   * `-term`は除外、`a OR b`はいずれかの出現を必須とします（構文は`bm25-query.ts`を参照）。
+   * 複数のクエリタームが近接して出現するドキュメントはスコアをブーストします。
+   *
    * @param query 検索クエリ
    * @param topK 取得する上位結果数
+   * @param filter 検索フィルタ（オプション、一致するドキュメントのみスコアリング）
    * @returns 検索結果の配列
    */
-  async search(query: string, topK: number): Promise<SearchResult[]> {
+  async search(query: string, topK: number, filter?: BM25SearchFilter): Promise<SearchResult[]> {
     if (!this.db) {
       throw new Error('Database not initialized');
     }
 
-    const queryTokens = this.tokenize(query);
-    if (queryTokens.length === 0) {
+    // クエリは識別子の複合語と部分語の両方を含むcodeトークナイザーで分割（textの結果を包含する）
+    const parsed = parseBM25Query(query);
+    if (parsed.terms.length === 0) {
       return [];
     }
 
@@ -245,44 +614,203 @@ export class BM25Engine {
       return [];
     }
 
+    // 近接ブーストが必要な場合のみ出現位置を読み込む
+    const needsPositions = this.params.proximityWeight > 0 && parsed.groups.length > 1;
+
+    // 全クエリタームの出現をドキュメント長と合わせて1回のクエリで取得
+    const postings = this.getPostings(parsed.terms, filter, needsPositions);
+
+    // IDF計算（文書頻度は絞り込み後、総ドキュメント数はコーパス全体）
+    const documentFrequency = new Map<string, number>();
+    for (const posting of postings) {
+      documentFrequency.set(posting.term, (documentFrequency.get(posting.term) ?? 0) + 1);
+    }
+    const idf = new Map<string, number>();
+    for (const [term, frequency] of documentFrequency) {
+      idf.set(term, this.calculateIDF(frequency, stats.totalDocuments));
+    }
+
+    // 各出現のBM25スコアをドキュメントごとに合計
+    const { k1, b } = this.params;
     const scores = new Map<string, number>();
+    const documentPositions = new Map<string, TermPositions>();
+    for (const posting of postings) {
+      const tf = posting.frequency;
+      const lengthRatio = posting.length / stats.averageDocumentLength;
+      const saturation = (tf * (k1 + 1)) / (tf + k1 * (1 - b + b * lengthRatio));
+      const score = idf.get(posting.term)! * saturation;
+      scores.set(posting.documentId, (scores.get(posting.documentId) ?? 0) + score);
 
-    // 各クエリタームについてBM25スコアを計算
-    for (const term of queryTokens) {
-      const docs = this.getTermDocuments(term);
+      if (posting.positions) {
+        const positions = documentPositions.get(posting.documentId) ?? new Map();
+        positions.set(posting.term, posting.positions);
+        documentPositions.set(posting.documentId, positions);
+      }
+    }
 
-      if (docs.length === 0) {
+    // フレーズ・OR・除外の条件で絞り込み、近接ブーストを適用
+    const allowed = hasConstraints(parsed)
+      ? this.filterByConstraints(parsed, Array.from(scores.keys()))
+      : null;
+    const results: SearchResult[] = [];
+    for (const [documentId, score] of scores) {
+      if (allowed && !allowed.has(documentId)) {
         continue;
       }
 
-      // IDF計算
-      const idf = this.calculateIDF(docs.length, stats.totalDocuments);
+      const positions = documentPositions.get(documentId) ?? new Map();
+      const proximity =
+        this.params.proximityWeight > 0 ? proximityScore(parsed.groups, positions) : 0;
+      results.push({ documentId, score: score * (1 + this.params.proximityWeight * proximity) });
+    }
+
+    // スコアでソート
+    return results.sort((a, b) => b.score - a.score).slice(0, topK);
+  }
+
+  /**
+   * ドキュメントをクエリのフレーズ・OR・除外の条件で絞り込む
+   *
+   * ベクトル検索など、BM25以外で取得したドキュメントに同じ条件を適用するために使用します。
+   *
+   * @param documentIds ドキュメントIDの配列
+   * @param query 検索クエリ
+   * @returns 条件を満たすドキュメントID（入力の順序を維持）
+   */
+  async filterDocuments(documentIds: string[], query: string): Promise<string[]> {
+    if (!this.db) {
+      throw new Error('Database not initialized');
+    }
+
+    const parsed = parseBM25Query(query);
+    if (!hasConstraints(parsed)) {
+      return documentIds;
+    }
+
+    const allowed = this.filterByConstraints(parsed, documentIds);
+    return documentIds.filter((documentId) => allowed.has(documentId));
+  }
+
+  /**
+   * 検索フィルタに一致するドキュメントIDを取得（検索語を使わない絞り込み）
+   *
+   * `lang:rust type:function`のようにフィールド指定のみのクエリに使用します。
+   *
+   * @param filter 検索フィルタ
+   * @param limit 取得する最大件数
+   * @returns ドキュメントID（ドキュメントIDの昇順）
+   */
+  async findDocuments(filter: BM25SearchFilter, limit: number): Promise<string[]> {
+    if (!this.db) {
+      throw new Error('Database not initialized');
+    }
+
+    const { clause, params } = this.buildFilterClause(filter);
+    const rows = this.prepare(
+      `SELECT d.document_id FROM document_stats d
+       WHERE 1 = 1${clause}
+       ORDER BY d.document_id
+       LIMIT ?`
+    ).all(...params, limit) as Array<{ document_id: string }>;
+
+    return rows.map((row) => row.document_id);
+  }
+
+  /**
+   * ドキュメント属性を取得
+   *
+   * BM25のみでヒットした結果にメタデータを補うために使用します。
+   *
+   * @param documentIds ドキュメントIDの配列
+   * @returns ドキュメントIDをキーとした属性（インデックスにないIDは含まない）
+   */
+  async getDocumentAttributes(
+    documentIds: string[]
+  ): Promise<Map<string, BM25DocumentAttributes>> {
+    if (!this.db) {
+      throw new Error('Database not initialized');
+    }
 
-      // 各ドキュメントのスコアを計算
-      for (const doc of docs) {
-        const docLength = this.getDocumentLength(doc.documentId);
-        const tf = doc.frequency;
+    const result = new Map<string, BM25DocumentAttributes>();
 
-        // BM25スコア計算
-        const score =
-          idf *
-          ((tf * (this.params.k1 + 1)) /
-            (tf +
-              this.params.k1 *
-                (1 - this.params.b + this.params.b * (docLength / stats.averageDocumentLength))));
+    // SQLiteのバインド変数の上限を超えないよう分割して取得
+    const chunkSize = 500;
+    for (let i = 0; i < documentIds.length; i += chunkSize) {
+      const chunk = documentIds.slice(i, i + chunkSize);
+      const rows = this.db
+        .prepare(
+          `SELECT document_id, project_id, language, file_path, file_type, symbol_type
+           FROM document_stats
+           WHERE document_id IN (${chunk.map(() => '?').join(', ')})`
+        )
+        .all(...chunk) as Array<{
+        document_id: string;
+        project_id: string | null;
+        language: string | null;
+        file_path: string | null;
+        file_type: string | null;
+        symbol_type: string | null;
+      }>;
 
-        const currentScore = scores.get(doc.documentId) ?? 0;
-        scores.set(doc.documentId, currentScore + score);
+      for (const row of rows) {
+        result.set(row.document_id, {
+          projectId: row.project_id ?? undefined,
+          language: row.language ?? undefined,
+          filePath: row.file_path ?? undefined,
+          fileType: row.file_type ?? undefined,
+          symbolType: row.symbol_type ?? undefined,
+        });
       }
     }
+    return result;
+  }
 
-    // スコアでソート
-    const results = Array.from(scores.entries())
-      .map(([documentId, score]) => ({ documentId, score }))
-      .sort((a, b) => b.score - a.score)
-      .slice(0, topK);
+  /**
+   * 条件を満たすドキュメントIDの集合を取得
+   */
+  private filterByConstraints(parsed: ParsedBM25Query, documentIds: string[]): Set<string> {
+    const positions = this.getTermPositions(documentIds, constraintTerms(parsed));
+    return new Set(
+      documentIds.filter((documentId) =>
+        satisfiesConstraints(parsed, positions.get(documentId) ?? new Map())
+      )
+    );
+  }
 
-    return results;
+  /**
+   * ドキュメントごとのタームの出現位置を取得
+   * @param documentIds ドキュメントIDの配列
+   * @param terms タームの配列
+   * @returns ドキュメントIDをキーとした出現位置
+   */
+  private getTermPositions(documentIds: string[], terms: string[]): Map<string, TermPositions> {
+    if (!this.db) {
+      throw new Error('Database not initialized');
+    }
+
+    const result = new Map<string, TermPositions>();
+    if (documentIds.length === 0 || terms.length === 0) {
+      return result;
+    }
+
+    // SQLiteのバインド変数の上限を超えないよう分割して取得
+    const chunkSize = 500;
+    const termPlaceholders = terms.map(() => '?').join(', ');
+    for (let i = 0; i < documentIds.length; i += chunkSize) {
+      const chunk = documentIds.slice(i, i + chunkSize);
+      const rows = this.prepare(
+        `SELECT document_id, term, positions FROM inverted_index
+         WHERE term IN (${termPlaceholders})
+           AND document_id IN (${chunk.map(() => '?').join(', ')})`
+      ).all(...terms, ...chunk) as Array<{ document_id: string; term: string; positions: Buffer }>;
+
+      for (const row of rows) {
+        const positions = result.get(row.document_id) ?? new Map();
+        positions.set(row.term, decodePositions(row.positions));
+        result.set(row.document_id, positions);
+      }
+    }
+    return result;
   }
 
   /**
@@ -296,50 +824,88 @@ export class BM25Engine {
   }
 
   /**
-   * タームを含むドキュメントを取得
-   * @param term 検索タームterm
-   * @returns ドキュメント情報の配列
+   * タームを含むドキュメントの出現をドキュメント長と合わせて取得
+   *
+   * ターム・ドキュメントごとに問い合わせず、全タームを1回の結合クエリで取得します。
+   *
+   * @param terms 検索ターム
+   * @param filter 検索フィルタ
+   * @param withPositions 出現位置も取得するか
+   * @returns 出現の配列
    */
-  private getTermDocuments(term: string): Array<{ documentId: string; frequency: number }> {
-    if (!this.db) {
-      throw new Error('Database not initialized');
-    }
+  private getPostings(
+    terms: string[],
+    filter?: BM25SearchFilter,
+    withPositions: boolean = false
+  ): Posting[] {
+    const { clause, params } = this.buildFilterClause(filter);
 
-    const stmt = this.db.prepare(`
-      SELECT document_id, frequency
-      FROM inverted_index
-      WHERE term = ?
+    const stmt = this.prepare(`
+      SELECT i.term, i.document_id, i.frequency, d.length${withPositions ? ', i.positions' : ''}
+      FROM inverted_index i
+      JOIN document_stats d ON d.document_id = i.document_id
+      WHERE i.term IN (${terms.map(() => '?').join(', ')})${clause}
     `);
 
-    const rows = stmt.all(term) as Array<{ document_id: string; frequency: number }>;
+    const rows = stmt.all(...terms, ...params) as Array<{
+      term: string;
+      document_id: string;
+      frequency: number;
+      length: number;
+      positions?: Buffer;
+    }>;
     return rows.map((row) => ({
+      term: row.term,
       documentId: row.document_id,
       frequency: row.frequency,
+      length: row.length,
+      positions: row.positions !== undefined ? decodePositions(row.positions) : undefined,
     }));
   }
 
   /**
-   * ドキュメント長を取得
-   * @param documentId ドキュメントID
-   * @returns ドキュメント長
+   * 検索フィルタをdocument_stats（別名d）に対するSQL条件に変換
+   * @param filter 検索フィルタ
+   * @returns ` AND ...`形式の条件とバインドパラメータ
    */
-  private getDocumentLength(documentId: string): number {
-    if (!this.db) {
-      throw new Error('Database not initialized');
+  private buildFilterClause(filter?: BM25SearchFilter): { clause: string; params: unknown[] } {
+    const conditions: string[] = [];
+    const params: unknown[] = [];
+
+    if (filter?.projectId !== undefined) {
+      conditions.push('d.project_id = ?');
+      params.push(filter.projectId);
     }
 
-    const stmt = this.db.prepare(`
-      SELECT length
-      FROM document_stats
-      WHERE document_id = ?
-    `);
+    // 値のいずれかに一致する条件（空配列は条件なし）
+    const addInCondition = (column: string, values: string[] | undefined): void => {
+      if (values && values.length > 0) {
+        conditions.push(`${column} IN (${values.map(() => '?').join(', ')})`);
+        params.push(...values);
+      }
+    };
+
+    addInCondition('d.language', filter?.languages?.map((language) => language.toLowerCase()));
+    addInCondition('d.file_type', filter?.fileTypes?.map(normalizeFileType));
+    addInCondition('d.symbol_type', filter?.types);
+
+    if (filter?.pathPattern) {
+      // LIKEのワイルドカードを避けるため部分一致はinstrで判定
+      conditions.push('instr(d.file_path, ?) > 0');
+      params.push(filter.pathPattern);
+    }
 
-    const row = stmt.get(documentId) as { length: number } | undefined;
-    return row?.length ?? 0;
+    return {
+      clause: conditions.map((condition) => ` AND ${condition}`).join(''),
+      params,
+    };
   }
 
   /**
    * ドキュメント統計を取得
+   *
+   * 結果はインデックスが更新されるまでキャッシュします（このインスタンス経由の更新で破棄）。
+   *
    * @returns ドキュメント統計
    */
   async getDocumentStats(): Promise<DocumentStats> {
@@ -347,17 +913,19 @@ export class BM25Engine {
       throw new Error('Database not initialized');
     }
 
-    const stmt = this.db.prepare(`
-      SELECT COUNT(*) as count, AVG(length) as avgLength
-      FROM document_stats
-    `);
+    if (!this.corpusStats) {
+      const row = this.prepare(`
+        SELECT COUNT(*) as count, AVG(length) as avgLength
+        FROM document_stats
+      `).get() as { count: number; avgLength: number | null } | undefined;
 
-    const row = stmt.get() as { count: number; avgLength: number | null } | undefined;
+      this.corpusStats = {
+        totalDocuments: row?.count ?? 0,
+        averageDocumentLength: row?.avgLength ?? 0,
+      };
+    }
 
-    return {
-      totalDocuments: row?.count ?? 0,
-      averageDocumentLength: row?.avgLength ?? 0,
-    };
+    return { ...this.corpusStats };
   }
 
   /**
@@ -378,7 +946,7 @@ export class BM25Engine {
     `);
 
     const row = stmt.get(term) as
-      | { document_id: string; frequency: number; positions: string }
+      | { document_id: string; frequency: number; positions: Buffer }
       | undefined;
 
     if (!row) {
@@ -388,7 +956,7 @@ export class BM25Engine {
     return {
       documentId: row.document_id,
       frequency: row.frequency,
-      positions: JSON.parse(row.positions),
+      positions: decodePositions(row.positions),
     };
   }
 
@@ -397,16 +965,97 @@ export class BM25Engine {
    * @param documentId ドキュメントID
    */
   async deleteDocument(documentId: string): Promise<void> {
+    await this.deleteDocuments([documentId]);
+  }
+
+  /**
+   * 複数のドキュメントを1つのトランザクションで削除
+   * @param documentIds ドキュメントIDの配列（存在しないIDは無視）
+   * @returns 削除したドキュメント数
+   */
+  async deleteDocuments(documentIds: string[]): Promise<number> {
     if (!this.db) {
       throw new Error('Database not initialized');
     }
 
+    if (documentIds.length === 0) {
+      return 0;
+    }
+
+    const deleteTerms = this.prepare(`DELETE FROM inverted_index WHERE document_id = ?`);
+    const deleteStats = this.prepare(`DELETE FROM document_stats WHERE document_id = ?`);
+
+    let deleted = 0;
     const transaction = this.db.transaction(() => {
-      this.db!.prepare(`DELETE FROM inverted_index WHERE document_id = ?`).run(documentId);
-      this.db!.prepare(`DELETE FROM document_stats WHERE document_id = ?`).run(documentId);
+      for (const documentId of documentIds) {
+        deleteTerms.run(documentId);
+        deleted += deleteStats.run(documentId).changes;
+      }
     });
 
     transaction();
+    this.corpusStats = null;
+
+    return deleted;
+  }
+
+  /**
+   * IDが指定したプレフィックスで始まるドキュメントをすべて削除
+   * @param prefix ドキュメントIDのプレフィックス（例: `${filePath}:`）
+   * @returns 削除したドキュメント数
+   */
+  async deleteByPrefix(prefix: string): Promise<number> {
+    if (!this.db) {
+      throw new Error('Database not initialized');
+    }
+
+    if (prefix.length === 0) {
+      return 0;
+    }
+
+    const rows = this.prepare(
+      `SELECT document_id FROM document_stats WHERE instr(document_id, ?) = 1`
+    ).all(prefix) as Array<{ document_id: string }>;
+
+    return this.deleteDocuments(rows.map((row) => row.document_id));
+  }
+
+  /**
+   * プロジェクトのドキュメントをすべて削除
+   * @param projectId プロジェクトID
+   * @returns 削除したドキュメント数
+   */
+  async deleteByProject(projectId: string): Promise<number> {
+    if (!this.db) {
+      throw new Error('Database not initialized');
+    }
+
+    const rows = this.prepare(`SELECT document_id FROM document_stats WHERE project_id = ?`).all(
+      projectId
+    ) as Array<{ document_id: string }>;
+
+    return this.deleteDocuments(rows.map((row) => row.document_id));
+  }
+
+  /**
+   * プロジェクト内の1ファイルのドキュメントをすべて削除
+   *
+   * 同じファイルを別のプロジェクトとしてインデックス化したドキュメントは削除しません。
+   *
+   * @param projectId プロジェクトID
+   * @param filePath ファイルパス
+   * @returns 削除したドキュメント数
+   */
+  async deleteByFile(projectId: string, filePath: string): Promise<number> {
+    if (!this.db) {
+      throw new Error('Database not initialized');
+    }
+
+    const rows = this.prepare(
+      `SELECT document_id FROM document_stats WHERE project_id = ? AND file_path = ?`
+    ).all(projectId, filePath) as Array<{ document_id: string }>;
+
+    return this.deleteDocuments(rows.map((row) => row.document_id));
   }
 
   /**
@@ -420,9 +1069,183 @@ export class BM25Engine {
     const transaction = this.db.transaction(() => {
       this.db!.exec('DELETE FROM inverted_index');
       this.db!.exec('DELETE FROM document_stats');
+      this.db!.exec('DELETE FROM file_manifest');
+      this.db!.exec('DELETE FROM project_registry');
     });
 
     transaction();
+    this.corpusStats = null;
+  }
+
+  /**
+   * プロジェクトのファイルマニフェストを取得
+   * @param projectId プロジェクトID
+   * @returns マニフェストエントリの配列
+   */
+  async getFileManifest(projectId: string): Promise<FileManifestEntry[]> {
+    if (!this.db) {
+      throw new Error('Database not initialized');
+    }
+
+    const rows = this.db
+      .prepare(
+        `SELECT file_path, project_id, size, mtime, hash, symbols_count, ids, index_version
+         FROM file_manifest
+         WHERE project_id = ?`
+      )
+      .all(projectId) as Array<{
+      file_path: string;
+      project_id: string;
+      size: number;
+      mtime: number;
+      hash: string;
+      symbols_count: number;
+      ids: string;
+      index_version: number;
+    }>;
+
+    return rows.map((row) => ({
+      filePath: row.file_path,
+      projectId: row.project_id,
+      size: row.size,
+      mtime: row.mtime,
+      hash: row.hash,
+      symbolsCount: row.symbols_count,
+      ids: JSON.parse(row.ids),
+      indexVersion: row.index_version,
+    }));
+  }
+
+  /**
+   * ファイルマニフェストのエントリを登録・更新
+   * @param entry マニフェストエントリ
+   */
+  async upsertFileManifest(entry: FileManifestEntry): Promise<void> {
+    if (!this.db) {
+      throw new Error('Database not initialized');
+    }
+
+    this.db
+      .prepare(
+        `INSERT OR REPLACE INTO file_manifest
+           (file_path, project_id, size, mtime, hash, symbols_count, ids, index_version)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)`
+      )
+      .run(
+        entry.filePath,
+        entry.projectId,
+        entry.size,
+        entry.mtime,
+        entry.hash,
+        entry.symbolsCount,
+        JSON.stringify(entry.ids),
+        entry.indexVersion ?? 0
+      );
+  }
+
+  /**
+   * ファイルマニフェストのエントリを削除
+   * @param projectId プロジェクトID
+   * @param filePath ファイルパス
+   */
+  async deleteFileManifest(projectId: string, filePath: string): Promise<void> {
+    if (!this.db) {
+      throw new Error('Database not initialized');
+    }
+
+    this.db
+      .prepare(`DELETE FROM file_manifest WHERE project_id = ? AND file_path = ?`)
+      .run(projectId, filePath);
+  }
+
+  /**
+   * プロジェクトのファイルマニフェストをクリア
+   * @param projectId プロジェクトID
+   */
+  async clearFileManifest(projectId: string): Promise<void> {
+    if (!this.db) {
+      throw new Error('Database not initialized');
+    }
+
+    this.db.prepare(`DELETE FROM file_manifest WHERE project_id = ?`).run(projectId);
+  }
+
+  /**
+   * 登録済みのプロジェクトを取得
+   * @returns プロジェクトレコードの配列
+   */
+  async getProjects(): Promise<ProjectRecord[]> {
+    if (!this.db) {
+      throw new Error('Database not initialized');
+    }
+
+    const rows = this.db
+      .prepare(
+        `SELECT project_id, root_path, options, status, last_indexed,
+                total_files, total_symbols, total_vectors
+         FROM project_registry`
+      )
+      .all() as Array<{
+      project_id: string;
+      root_path: string;
+      options: string;
+      status: string;
+      last_indexed: number;
+      total_files: number;
+      total_symbols: number;
+      total_vectors: number;
+    }>;
+
+    return rows.map((row) => ({
+      projectId: row.project_id,
+      rootPath: row.root_path,
+      options: JSON.parse(row.options),
+      status: row.status,
+      lastIndexed: row.last_indexed,
+      totalFiles: row.total_files,
+      totalSymbols: row.total_symbols,
+      totalVectors: row.total_vectors,
+    }));
+  }
+
+  /**
+   * プロジェクトを登録・更新
+   * @param record プロジェクトレコード
+   */
+  async upsertProject(record: ProjectRecord): Promise<void> {
+    if (!this.db) {
+      throw new Error('Database not initialized');
+    }
+
+    this.db
+      .prepare(
+        `INSERT OR REPLACE INTO project_registry
+           (project_id, root_path, options, status, last_indexed,
+            total_files, total_symbols, total_vectors)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)`
+      )
+      .run(
+        record.projectId,
+        record.rootPath,
+        JSON.stringify(record.options),
+        record.status,
+        record.lastIndexed,
+        record.totalFiles,
+        record.totalSymbols,
+        record.totalVectors
+      );
+  }
+
+  /**
+   * プロジェクトの登録を削除
+   * @param projectId プロジェクトID
+   */
+  async deleteProject(projectId: string): Promise<void> {
+    if (!this.db) {
+      throw new Error('Database not initialized');
+    }
+
+    this.db.prepare(`DELETE FROM project_registry WHERE project_id = ?`).run(projectId);
   }
 
   /**
@@ -432,6 +1255,8 @@ export class BM25Engine {
     if (this.db) {
       this.db.close();
       this.db = null;
+      this.statements.clear();
+      this.corpusStats = null;
       this.isInitialized = false;
     }
   }
@@ -0,0 +1,239 @@
+/**
+ * BM25 Query: フレーズ・近接検索のためのクエリ解析と位置判定
+ *
+ * クエリ中のダブルクォートで囲まれた部分をフレーズとして扱い、
+ * 転置インデックスに保存したターム出現位置で連続性と近さを判定します。
+ *
+ * クエリ構文:
+ * - `"async function"`: フレーズ（連続して出現するドキュメントのみ）
+ * - `-term` / `-"phrase"`: 除外（出現するドキュメントを除く）
+ * - `a OR b`: いずれかが出現するドキュメントのみ（フレーズ・単語のどちらも指定可）
+ * - それ以外の単語: 必須ではなくスコアリングのみに使用
+ */
+
+import { tokenize } from './tokenizer.js';
+
+/**
+ * 同じ位置に出現するタームのグループ（識別子の複合語と部分語）
+ *
+ * ドキュメント側でいずれかのタームがその位置に出現すれば一致とみなします。
+ */
+export type TermGroup = string[];
+
+/**
+ * 連続して出現する必要があるタームグループの列
+ */
+export type Phrase = TermGroup[];
+
+/**
+ * 解析済みのBM25クエリ
+ */
+export interface ParsedBM25Query {
+  /** スコア計算に使う重複のないターム */
+  terms: string[];
+  /** クエリ全体の位置ごとのタームグループ（近接ブースト用） */
+  groups: TermGroup[];
+  /** 必須条件（各条件はいずれかのフレーズが出現すれば満たされる。条件同士はAND） */
+  required: Phrase[][];
+  /** 除外するフレーズ（いずれかが出現するドキュメントを除く） */
+  excluded: Phrase[];
+}
+
+/**
+ * クエリの構成要素（フレーズ・単語・除外・OR）
+ */
+const QUERY_ITEM_PATTERN = /(-?)(?:"([^"]*)"|(\S+))/g;
+
+/**
+ * ドキュメント内のタームの出現位置
+ */
+export type TermPositions = Map<string, number[]>;
+
+/**
+ * テキストを位置ごとのタームグループに分割
+ */
+function toGroups(text: string): TermGroup[] {
+  const groups = new Map<number, TermGroup>();
+  for (const { term, position } of tokenize(text, 'code')) {
+    const group = groups.get(position) ?? [];
+    group.push(term);
+    groups.set(position, group);
+  }
+  return Array.from(groups.values());
+}
+
+/**
+ * クエリを解析
+ *
+ * @example
+ * ```typescript
+ * parseBM25Query('"async function" error -test');
+ * // required: [[[['async'], ['function']]]], excluded: [[['test']]]
+ * // terms: ['async', 'function', 'error']
+ * ```
+ *
+ * @param query 検索クエリ
+ * @returns 解析済みクエリ
+ */
+export function parseBM25Query(query: string): ParsedBM25Query {
+  const groups: TermGroup[] = [];
+  const excluded: Phrase[] = [];
+  const clauses: Array<{ alternatives: Phrase[]; required: boolean }> = [];
+  let last: { alternatives: Phrase[]; required: boolean } | null = null;
+  let pendingOr = false;
+
+  for (const match of query.matchAll(QUERY_ITEM_PATTERN)) {
+    const [, negated, quoted, word] = match;
+    const isPhrase = quoted !== undefined;
+
+    if (!isPhrase && !negated && word === 'OR') {
+      // 前後に項目がある場合のみ連結する
+      pendingOr = last !== null;
+      continue;
+    }
+
+    // 対応しないダブルクォートは区切り文字として扱う
+    const phrase = toGroups((quoted ?? word).replace(/"/g, ' '));
+    if (phrase.length === 0) {
+      continue;
+    }
+
+    if (negated) {
+      // 除外は部分語に広げず、各位置の先頭のターム（複合語）のみで判定
+      excluded.push(phrase.map((group) => [group[0]]));
+      pendingOr = false;
+      continue;
+    }
+
+    groups.push(...phrase);
+    if (pendingOr && last) {
+      last.alternatives.push(phrase);
+      last.required = true;
+    } else {
+      last = { alternatives: [phrase], required: isPhrase };
+      clauses.push(last);
+    }
+    pendingOr = false;
+  }
+
+  const terms = Array.from(new Set(groups.flat()));
+  const required = clauses.filter((clause) => clause.required).map((clause) => clause.alternatives);
+
+  return { terms, groups, required, excluded };
+}
+
+/**
+ * クエリに必須条件・除外条件があるか判定
+ */
+export function hasConstraints(parsed: ParsedBM25Query): boolean {
+  return parsed.required.length > 0 || parsed.excluded.length > 0;
+}
+
+/**
+ * 必須条件・除外条件の判定に必要なターム
+ */
+export function constraintTerms(parsed: ParsedBM25Query): string[] {
+  const phrases = [...parsed.required.flat(), ...parsed.excluded];
+  return Array.from(new Set(phrases.flat(2)));
+}
+
+/**
+ * ドキュメントがクエリの必須条件・除外条件を満たすか判定
+ * @param parsed 解析済みクエリ
+ * @param positions ドキュメント内のタームの出現位置（`constraintTerms`のターム）
+ * @returns 条件を満たす場合true
+ */
+export function satisfiesConstraints(parsed: ParsedBM25Query, positions: TermPositions): boolean {
+  return (
+    parsed.required.every((alternatives) =>
+      alternatives.some((phrase) => matchesPhrase(phrase, positions))
+    ) && !parsed.excluded.some((phrase) => matchesPhrase(phrase, positions))
+  );
+}
+
+/**
+ * タームグループの出現位置（昇順、重複なし）
+ */
+function groupPositions(group: TermGroup, positions: TermPositions): number[] {
+  const merged = new Set<number>();
+  for (const term of group) {
+    for (const position of positions.get(term) ?? []) {
+      merged.add(position);
+    }
+  }
+  return Array.from(merged).sort((a, b) => a - b);
+}
+
+/**
+ * フレーズがドキュメント内で連続して出現するか判定
+ * @param phrase 位置順のタームグループ
+ * @param positions ドキュメント内のタームの出現位置
+ * @returns 連続して出現する場合true
+ */
+export function matchesPhrase(phrase: Phrase, positions: TermPositions): boolean {
+  const groupSets = phrase.map((group) => new Set(groupPositions(group, positions)));
+  if (groupSets.some((set) => set.size === 0)) {
+    return false;
+  }
+
+  for (const start of groupSets[0]) {
+    if (groupSets.every((set, offset) => set.has(start + offset))) {
+      return true;
+    }
+  }
+  return false;
+}
+
+/**
+ * クエリタームの近さを0-1で評価
+ *
+ * ドキュメントに出現するタームグループすべてを含む最小の位置範囲（ウィンドウ）を求め、
+ * `出現グループ数 / ウィンドウ幅`を返します（隣接して出現する場合に1.0）。
+ * 2グループ以上出現しない場合は0を返します。
+ *
+ * @param groups クエリの位置ごとのタームグループ
+ * @param positions ドキュメント内のタームの出現位置
+ * @returns 近接度（0-1）
+ */
+export function proximityScore(groups: TermGroup[], positions: TermPositions): number {
+  // 出現位置を(位置, グループ番号)の列にまとめて位置順に並べる
+  const occurrences: Array<{ position: number; group: number }> = [];
+  let matchedGroups = 0;
+  groups.forEach((group, index) => {
+    const found = groupPositions(group, positions);
+    if (found.length > 0) {
+      matchedGroups++;
+      occurrences.push(...found.map((position) => ({ position, group: index })));
+    }
+  });
+  if (matchedGroups < 2) {
+    return 0;
+  }
+  occurrences.sort((a, b) => a.position - b.position);
+
+  // スライディングウィンドウで全グループを含む最小幅を求める
+  const counts = new Map<number, number>();
+  let covered = 0;
+  let minWidth = Infinity;
+  let left = 0;
+  for (const occurrence of occurrences) {
+    const count = counts.get(occurrence.group) ?? 0;
+    counts.set(occurrence.group, count + 1);
+    if (count === 0) {
+      covered++;
+    }
+
+    while (covered === matchedGroups) {
+      const first = occurrences[left];
+      minWidth = Math.min(minWidth, occurrence.position - first.position + 1);
+      const remaining = counts.get(first.group)! - 1;
+      counts.set(first.group, remaining);
+      if (remaining === 0) {
+        covered--;
+      }
+      left++;
+    }
+  }
+
+  return Math.min(1, matchedGroups / minWidth);
+}
@@ -0,0 +1,607 @@
+/**
+ * Chroma Plugin - Chroma VectorDB プラグイン実装
+ *
+ * ChromaのREST API（v2）を使用し、既にChromaサーバーを運用しているチーム向けに対応
+ */
+
+import type {
+  VectorStorePlugin,
+  VectorStoreConfig,
+  Vector,
+  QueryResult,
+  CollectionStats,
+  MetadataFilter,
+} from './types';
+import { isLikeCondition, matchesMetadataFilter } from './metadata-filter.js';
+import { Logger } from '../utils/logger';
+import { retryWithBackoff, DEFAULT_RETRY_CONFIG } from './retry.js';
+import type { RetryConfig } from './retry.js';
+import { traceVectorDBOperation } from '../telemetry/instrumentation.js';
+import { withTraceContext } from '../telemetry/context-propagation.js';
+
+/**
+ * フィルタを完全に表現できない`$like`条件がある場合に取得する候補の倍率
+ *
+ * 取得後の判定でtopK件に満たない場合は、この倍率で候補数を増やして再検索します。
+ */
+const LIKE_CANDIDATE_FACTOR = 4;
+
+/**
+ * getAPIで1回に取得する件数
+ */
+const GET_PAGE_SIZE = 256;
+
+/**
+ * JSON文字列として保存したメタデータのキー一覧を記録するキー
+ *
+ * Chromaのメタデータは文字列・数値・真偽値のみ保存できるため、
+ * 配列等（`implements`等）はJSON文字列に変換し、読み出し時に復元します。
+ */
+const JSON_KEYS_FIELD = '__json_keys';
+
+/**
+ * Chromaのwhere条件
+ */
+export type ChromaWhere =
+  | { [key: string]: { $eq: string | number | boolean } | { $in: Array<string | number> } }
+  | { $and: ChromaWhere[] };
+
+/**
+ * メタデータフィルタの変換結果
+ */
+export interface ChromaWhereConversion {
+  /** Chromaのwhere条件（条件がない場合はundefined） */
+  where: ChromaWhere | undefined;
+  /**
+   * フィルタを完全に表現できたか
+   *
+   * Chromaのメタデータ条件には部分一致がないため、`$like`（`%`を含むもの）は
+   * where条件に含めず、falseの場合は取得後に`matchesMetadataFilter`で判定する必要があります。
+   * 一致する結果が少ない条件では候補の再検索が増え、最悪の場合はコレクションの全件を取得します。
+   */
+  exact: boolean;
+}
+
+/**
+ * メタデータフィルタをChromaのwhere条件に変換
+ *
+ * @param filter メタデータフィルタ
+ * @returns 変換結果
+ */
+export function buildChromaWhere(filter: MetadataFilter): ChromaWhereConversion {
+  const conditions: ChromaWhere[] = [];
+  let exact = true;
+
+  for (const [key, value] of Object.entries(filter)) {
+    if (Array.isArray(value)) {
+      // 空配列は条件なしとして扱う
+      if (value.length > 0) {
+        conditions.push({ [key]: { $in: value } });
+      }
+    } else if (isLikeCondition(value)) {
+      if (value.$like.includes('%')) {
+        exact = false;
+      } else {
+        conditions.push({ [key]: { $eq: value.$like } });
+      }
+    } else {
+      conditions.push({ [key]: { $eq: value } });
+    }
+  }
+
+  // 複数の条件は$andで結合する（Chromaは$andに2件以上の条件を要求する）
+  const where =
+    conditions.length === 0
+      ? undefined
+      : conditions.length === 1
+        ? conditions[0]
+        : { $and: conditions };
+  return { where, exact };
+}
+
+/**
+ * メタデータをChromaに保存できる形式に変換
+ */
+function toChromaMetadata(
+  metadata: Record<string, unknown> | undefined
+): Record<string, string | number | boolean> | null {
+  const result: Record<string, string | number | boolean> = {};
+  const jsonKeys: string[] = [];
+
+  for (const [key, value] of Object.entries(metadata ?? {})) {
+    if (value === undefined || value === null) {
+      continue;
+    }
+    if (typeof value === 'string' || typeof value === 'number' || typeof value === 'boolean') {
+      result[key] = value;
+    } else {
+      result[key] = JSON.stringify(value);
+      jsonKeys.push(key);
+    }
+  }
+  if (jsonKeys.length > 0) {
+    result[JSON_KEYS_FIELD] = jsonKeys.join(',');
+  }
+
+  // 空のメタデータはnullとして送る
+  return Object.keys(result).length > 0 ? result : null;
+}
+
+/**
+ * Chromaのメタデータを元の形式に復元
+ */
+function fromChromaMetadata(
+  metadata: Record<string, unknown> | null | undefined
+): Record<string, unknown> {
+  const result: Record<string, unknown> = { ...(metadata ?? {}) };
+  const jsonKeys = result[JSON_KEYS_FIELD];
+  delete result[JSON_KEYS_FIELD];
+
+  if (typeof jsonKeys === 'string') {
+    for (const key of jsonKeys.split(',')) {
+      if (typeof result[key] === 'string') {
+        result[key] = JSON.parse(result[key] as string);
+      }
+    }
+  }
+  return result;
+}
+
+/**
+ * コレクション情報
+ */
+interface ChromaCollection {
+  id: string;
+  name: string;
+  metadata?: Record<string, unknown> | null;
+}
+
+/**
+ * レコード操作に使うコレクションの情報
+ */
+interface ResolvedCollection {
+  /** コレクションIDを含むAPIのパス */
+  path: string;
+  /** ベクトル次元数（コレクションのメタデータに記録したもの） */
+  dimension: number;
+}
+
+/**
+ * Chromaプラグイン設定
+ */
+interface ChromaPluginConfig {
+  url: string;
+  token?: string;
+  /** テナント・データベースのコレクション一覧のパス */
+  collectionsPath: string;
+}
+
+/**
+ * Chromaプラグインクラス
+ *
+ * VectorStorePluginインターフェースを実装し、
+ * ChromaのREST API（コサイン距離のコレクション）でベクトルを管理します。
+ *
+ * 設定（`vectorStore.config`）:
+ * - `url`（または`address`）: ChromaのURL（例: `http://localhost:8000`）
+ * - `token`: 認証トークン（`x-chroma-token`ヘッダーで送信）
+ * - `tenant` / `database`: テナントとデータベース（省略時は`default_tenant` / `default_database`）
+ */
+export class ChromaPlugin implements VectorStorePlugin {
+  readonly name = 'chroma';
+
+  private config: ChromaPluginConfig | null = null;
+  private logger: Logger;
+  private retryConfig: RetryConfig;
+
+  constructor(retryConfig: Partial<RetryConfig> = {}) {
+    this.logger = new Logger();
+    this.retryConfig = { ...DEFAULT_RETRY_CONFIG, ...retryConfig };
+  }
+
+  /**
+   * 指数バックオフでリトライ実行
+   */
+  private async retryWithBackoff<T>(
+    operation: () => Promise<T>,
+    operationName: string
+  ): Promise<T> {
+    return retryWithBackoff(operation, operationName, this.retryConfig, this.logger);
+  }
+
+  /**
+   * 接続設定を取得
+   */
+  private ensureConfig(): ChromaPluginConfig {
+    if (!this.config) {
+      throw new Error('Chroma client is not connected. Call connect() first.');
+    }
+    return this.config;
+  }
+
+  /**
+   * REST APIを呼び出す
+   *
+   * 通信エラーと5xxはリトライし、4xxはリトライせずにエラーとします。
+   *
+   * @param method HTTPメソッド
+   * @param path APIのパス（`/api/v2`以降）
+   * @param body リクエストボディ
+   * @param options.allowNotFound trueの場合は404をエラーにしない
+   * @returns レスポンスのJSON（404の場合はundefined）
+   */
+  private async request<T>(
+    method: string,
+    path: string,
+    body?: unknown,
+    options: { allowNotFound?: boolean } = {}
+  ): Promise<T | undefined> {
+    const { url, token } = this.ensureConfig();
+    const headers: Record<string, string> = { 'Content-Type': 'application/json' };
+    if (token) {
+      headers['x-chroma-token'] = token;
+    }
+
+    const response = await this.retryWithBackoff(async () => {
+      const res = await fetch(`${url}/api/v2${path}`, {
+        method,
+        headers,
+        body: body === undefined ? undefined : JSON.stringify(body),
+      });
+      if (res.status >= 500) {
+        throw new Error(`Chroma request failed (${res.status}): ${await res.text()}`);
+      }
+      return res;
+    }, `${method} ${path}`);
+
+    if (response.status === 404 && options.allowNotFound) {
+      return undefined;
+    }
+
+    const json = (await response.json().catch(() => undefined)) as unknown;
+    if (!response.ok) {
+      const error = json as { error?: string; message?: string } | undefined;
+      const message = error?.message ?? error?.error ?? 'unknown error';
+      throw new Error(`Chroma request failed (${response.status}): ${message}`);
+    }
+    return json as T;
+  }
+
+  /**
+   * コレクション一覧のパス
+   */
+  private collectionsPath(): string {
+    return this.ensureConfig().collectionsPath;
+  }
+
+  /**
+   * コレクション名でコレクション情報を取得
+   */
+  private async findCollection(name: string): Promise<ChromaCollection | undefined> {
+    return this.request<ChromaCollection>(
+      'GET',
+      `${this.collectionsPath()}/${encodeURIComponent(name)}`,
+      undefined,
+      { allowNotFound: true }
+    );
+  }
+
+  /**
+   * コレクション情報を取得
+   * @throws コレクションが存在しない場合
+   */
+  private async getCollection(collectionName: string): Promise<ResolvedCollection> {
+    const collection = await this.findCollection(collectionName);
+    if (!collection) {
+      throw new Error(`Collection ${collectionName} does not exist`);
+    }
+    return {
+      // レコードの操作はコレクションIDで行う
+      path: `${this.collectionsPath()}/${collection.id}`,
+      dimension: Number(collection.metadata?.['dimension'] ?? 0),
+    };
+  }
+
+  /**
+   * ベクトルの次元数を検証
+   */
+  private ensureDimension(dimension: number, vector: number[]): void {
+    if (dimension > 0 && vector.length !== dimension) {
+      throw new Error(`Vector dimension mismatch: expected ${dimension}, got ${vector.length}`);
+    }
+  }
+
+  /**
+   * Chromaに接続
+   */
+  async connect(config: VectorStoreConfig): Promise<void> {
+    return await traceVectorDBOperation('connect' as any, 'chroma', async () => {
+      return await withTraceContext(async () => {
+        this.logger.info('Connecting to Chroma...');
+
+        const chromaConfig = config.config as Record<string, unknown>;
+        const address = String(chromaConfig['url'] ?? chromaConfig['address'] ?? 'localhost:8000');
+        const tenant = encodeURIComponent(String(chromaConfig['tenant'] ?? 'default_tenant'));
+        const database = encodeURIComponent(String(chromaConfig['database'] ?? 'default_database'));
+        this.config = {
+          url: (/^https?:\/\//.test(address) ? address : `http://${address}`).replace(/\/+$/, ''),
+          token: chromaConfig['token'] as string | undefined,
+          collectionsPath: `/tenants/${tenant}/databases/${database}/collections`,
+        };
+
+        try {
+          // 接続テスト: バージョン情報を取得
+          const version = await this.request<string>('GET', '/version');
+          this.logger.info(`Connected to Chroma version: ${version}`);
+        } catch (error) {
+          this.config = null;
+          throw error;
+        }
+      });
+    });
+  }
+
+  /**
+   * Chromaから切断
+   */
+  async disconnect(): Promise<void> {
+    if (this.config) {
+      this.logger.info('Disconnecting from Chroma...');
+      this.config = null;
+      this.logger.info('Disconnected from Chroma');
+    }
+  }
+
+  /**
+   * コレクションを作成
+   */
+  async createCollection(name: string, dimension: number): Promise<void> {
+    this.ensureConfig();
+    this.logger.info(`Creating collection: ${name} (dimension: ${dimension})`);
+
+    // コレクションが既に存在するか確認
+    if (await this.findCollection(name)) {
+      throw new Error(`Collection ${name} already exists`);
+    }
+
+    // 次元数はコレクションのメタデータに記録し、upsert・検索時の検証と統計情報に使う
+    await this.request('POST', this.collectionsPath(), {
+      name,
+      metadata: { 'hnsw:space': 'cosine', dimension },
+    });
+
+    this.logger.info(`Collection ${name} created successfully`);
+  }
+
+  /**
+   * コレクションを削除
+   */
+  async deleteCollection(name: string): Promise<void> {
+    this.ensureConfig();
+    this.logger.info(`Deleting collection: ${name}`);
+
+    if (!(await this.findCollection(name))) {
+      this.logger.debug(`Collection ${name} does not exist, skipping deletion`);
+      return;
+    }
+
+    await this.request('DELETE', `${this.collectionsPath()}/${encodeURIComponent(name)}`);
+    this.logger.info(`Collection ${name} deleted successfully`);
+  }
+
+  /**
+   * ベクトルを挿入または更新
+   */
+  async upsert(collectionName: string, vectors: Vector[]): Promise<void> {
+    return await traceVectorDBOperation('upsert', 'chroma', async () => {
+      return await withTraceContext(async () => {
+        this.ensureConfig();
+        this.logger.debug(`Upserting ${vectors.length} vectors to collection: ${collectionName}`);
+
+        // コレクションの存在確認
+        const collection = await this.getCollection(collectionName);
+
+        // 1件でも次元数が異なる場合は何も書き込まない
+        for (const v of vectors) {
+          this.ensureDimension(collection.dimension, v.vector);
+        }
+        if (vectors.length === 0) {
+          return;
+        }
+
+        await this.request('POST', `${collection.path}/upsert`, {
+          ids: vectors.map((v) => v.id),
+          embeddings: vectors.map((v) => v.vector),
+          metadatas: vectors.map((v) => toChromaMetadata(v.metadata)),
+        });
+
+        this.logger.debug(`Upserted ${vectors.length} vectors successfully`);
+      });
+    });
+  }
+
+  /**
+   * 類似ベクトルを検索
+   */
+  async query(
+    collectionName: string,
+    vector: number[],
+    topK: number,
+    filter?: MetadataFilter
+  ): Promise<QueryResult[]> {
+    return await traceVectorDBOperation('query', 'chroma', async () => {
+      return await withTraceContext(async () => {
+        this.ensureConfig();
+        this.logger.debug(
+          `Querying collection: ${collectionName} (topK: ${topK}, filter: ${JSON.stringify(filter)})`
+        );
+
+        // コレクションの存在確認
+        const collection = await this.getCollection(collectionName);
+        this.ensureDimension(collection.dimension, vector);
+
+        const conversion = filter ? buildChromaWhere(filter) : { where: undefined, exact: true };
+        if (conversion.exact) {
+          return await this.queryCandidates(collection.path, vector, topK, conversion.where);
+        }
+
+        // フィルタを完全に表現できない場合は候補を多めに取得して取得後に判定し、
+        // topK件に満たなければ候補数を増やして再検索する（コレクションの全件に達するまで）
+        const vectorCount = (await this.request<number>('GET', `${collection.path}/count`)) ?? 0;
+        for (let candidates = topK * LIKE_CANDIDATE_FACTOR; ; candidates *= LIKE_CANDIDATE_FACTOR) {
+          const nResults = Math.min(candidates, vectorCount);
+          const results =
+            nResults > 0
+              ? await this.queryCandidates(collection.path, vector, nResults, conversion.where)
+              : [];
+          const matched = results.filter((r) => matchesMetadataFilter(r.metadata, filter!));
+
+          if (matched.length >= topK || nResults >= vectorCount) {
+            return matched.slice(0, topK);
+          }
+        }
+      });
+    });
+  }
+
+  /**
+   * 類似ベクトルの候補をスコアの高い順に取得
+   */
+  private async queryCandidates(
+    collectionPath: string,
+    vector: number[],
+    nResults: number,
+    where: ChromaWhere | undefined
+  ): Promise<QueryResult[]> {
+    const response = await this.request<{
+      ids: string[][];
+      distances?: Array<Array<number | null>> | null;
+      metadatas?: Array<Array<Record<string, unknown> | null>> | null;
+    }>('POST', `${collectionPath}/query`, {
+      query_embeddings: [vector],
+      n_results: nResults,
+      where,
+      include: ['metadatas', 'distances'],
+    });
+
+    const ids = response?.ids[0] ?? [];
+    return ids
+      .map((id, i) => ({
+        id,
+        // コサイン距離（0〜2）を0-1の類似度に変換
+        score: 1 - (response?.distances?.[0]?.[i] ?? 2) / 2,
+        metadata: fromChromaMetadata(response?.metadatas?.[0]?.[i]),
+      }))
+      .sort((a, b) => b.score - a.score);
+  }
+
+  /**
+   * ベクトルを削除
+   */
+  async delete(collectionName: string, ids: string[]): Promise<void> {
+    return await traceVectorDBOperation('delete', 'chroma', async () => {
+      return await withTraceContext(async () => {
+        this.ensureConfig();
+        this.logger.debug(`Deleting ${ids.length} vectors from collection: ${collectionName}`);
+
+        // コレクションの存在確認
+        const collection = await this.getCollection(collectionName);
+
+        if (ids.length === 0) {
+          return;
+        }
+
+        // 存在しないIDは無視される
+        await this.request('POST', `${collection.path}/delete`, { ids });
+
+        this.logger.debug(`Deleted ${ids.length} vectors successfully`);
+      });
+    });
+  }
+
+  /**
+   * メタデータが一致するベクトルをすべて削除
+   */
+  async deleteByFilter(collectionName: string, filter: MetadataFilter): Promise<void> {
+    return await traceVectorDBOperation('delete', 'chroma', async () => {
+      return await withTraceContext(async () => {
+        this.ensureConfig();
+        this.logger.debug(
+          `Deleting vectors from collection: ${collectionName} (filter: ${JSON.stringify(filter)})`
+        );
+
+        // コレクションの存在確認
+        const collection = await this.getCollection(collectionName);
+
+        // 空のフィルタで全件削除しないようにする
+        const conversion = buildChromaWhere(filter);
+        if (!conversion.where && conversion.exact) {
+          throw new Error('deleteByFilter requires at least one filter condition');
+        }
+
+        if (conversion.exact) {
+          await this.request('POST', `${collection.path}/delete`, { where: conversion.where });
+        } else {
+          // 候補を取得し、条件を満たすレコードのみIDで削除する
+          const ids = await this.getMatchingIds(collection.path, conversion.where, filter);
+          if (ids.length > 0) {
+            await this.request('POST', `${collection.path}/delete`, { ids });
+          }
+        }
+
+        this.logger.debug(`Deleted vectors matching filter: ${JSON.stringify(filter)}`);
+      });
+    });
+  }
+
+  /**
+   * where条件の候補をページ単位で取得し、メタデータフィルタを満たすIDを返す
+   */
+  private async getMatchingIds(
+    collectionPath: string,
+    where: ChromaWhere | undefined,
+    filter: MetadataFilter
+  ): Promise<string[]> {
+    const ids: string[] = [];
+
+    for (let offset = 0; ; offset += GET_PAGE_SIZE) {
+      const page = await this.request<{
+        ids: string[];
+        metadatas?: Array<Record<string, unknown> | null> | null;
+      }>('POST', `${collectionPath}/get`, {
+        where,
+        limit: GET_PAGE_SIZE,
+        offset,
+        include: ['metadatas'],
+      });
+
+      const pageIds = page?.ids ?? [];
+      pageIds.forEach((id, i) => {
+        if (matchesMetadataFilter(fromChromaMetadata(page?.metadatas?.[i]), filter)) {
+          ids.push(id);
+        }
+      });
+      if (pageIds.length < GET_PAGE_SIZE) {
+        return ids;
+      }
+    }
+  }
+
+  /**
+   * コレクションの統計情報を取得
+   */
+  async getStats(collectionName: string): Promise<CollectionStats> {
+    this.ensureConfig();
+    this.logger.debug(`Getting stats for collection: ${collectionName}`);
+
+    const collection = await this.getCollection(collectionName);
+    const vectorCount = (await this.request<number>('GET', `${collection.path}/count`)) ?? 0;
+
+    return {
+      vectorCount,
+      dimension: collection.dimension,
+      // インデックスサイズを概算（ベクトル数 * 次元数 * 4バイト）
+      indexSize: vectorCount * collection.dimension * 4,
+    };
+  }
+}
@@ -0,0 +1,306 @@
+/**
+ * Embedded Plugin - 組み込みベクターストアプラグイン実装
+ *
+ * 外部のベクターDBサーバーを使わず、ベクトルをSQLiteファイル（BM25のDBと同じディレクトリ）に
+ * 永続化します。検索はコレクションごとにメモリへ読み込んだベクトルとの全件比較
+ * （`FlatIndex`、インメモリプラグインと共通）でコサイン類似度を計算するため、ノートPCやCIでも
+ * 外部サービスなしで動作します。
+ */
+
+import Database from 'better-sqlite3';
+import * as fs from 'fs/promises';
+import * as path from 'path';
+import type {
+  VectorStorePlugin,
+  VectorStoreConfig,
+  Vector,
+  QueryResult,
+  CollectionStats,
+  MetadataFilter,
+} from './types';
+import { hasFilterConditions } from './metadata-filter.js';
+import { FlatIndex } from './flat-index.js';
+import { Logger } from '../utils/logger';
+import { traceVectorDBOperation } from '../telemetry/instrumentation.js';
+import { withTraceContext } from '../telemetry/context-propagation.js';
+
+/**
+ * データベースファイルのデフォルトパス（BM25のDBと同じディレクトリ）
+ */
+export const DEFAULT_EMBEDDED_DB_PATH = './tmp/vectors.db';
+
+/**
+ * Float32ArrayをBLOBに変換
+ */
+function toBlob(vector: number[]): Buffer {
+  return Buffer.from(Float32Array.from(vector).buffer);
+}
+
+/**
+ * BLOBをFloat32Arrayに変換
+ */
+function fromBlob(blob: Buffer): Float32Array {
+  // Bufferはプール上の任意のオフセットにあるため、コピーしてから解釈する
+  return new Float32Array(blob.buffer.slice(blob.byteOffset, blob.byteOffset + blob.byteLength));
+}
+
+/**
+ * 組み込みプラグインクラス
+ *
+ * VectorStorePluginインターフェースを実装し、SQLiteファイルにベクトルを永続化します。
+ *
+ * 設定（`vectorStore.config`）:
+ * - `path`: データベースファイルのパス（省略時は`./tmp/vectors.db`）
+ */
+export class EmbeddedPlugin implements VectorStorePlugin {
+  readonly name = 'embedded';
+
+  private db: Database.Database | null = null;
+  private logger: Logger;
+  /** コレクションごとのフラットインデックス（初回アクセス時にDBから読み込む） */
+  private indexes: Map<string, FlatIndex> = new Map();
+
+  constructor() {
+    this.logger = new Logger();
+  }
+
+  /**
+   * データベースを開く
+   */
+  async connect(config: VectorStoreConfig): Promise<void> {
+    const dbPath = (config.config['path'] as string | undefined) || DEFAULT_EMBEDDED_DB_PATH;
+    this.logger.info(`Opening embedded vector store: ${dbPath}`);
+
+    await fs.mkdir(path.dirname(dbPath), { recursive: true });
+    this.db = new Database(dbPath);
+    this.db.pragma('journal_mode = WAL');
+
+    this.db.exec(`
+      CREATE TABLE IF NOT EXISTS collections (
+        name TEXT PRIMARY KEY,
+        dimension INTEGER NOT NULL
+      );
+
+      CREATE TABLE IF NOT EXISTS vectors (
+        collection TEXT NOT NULL,
+        id TEXT NOT NULL,
+        vector BLOB NOT NULL,
+        metadata TEXT NOT NULL,
+        PRIMARY KEY (collection, id)
+      );
+    `);
+
+    this.logger.info('Embedded vector store opened');
+  }
+
+  /**
+   * データベースを閉じる
+   */
+  async disconnect(): Promise<void> {
+    if (this.db) {
+      this.logger.info('Closing embedded vector store...');
+      this.db.close();
+      this.db = null;
+      this.indexes.clear();
+      this.logger.info('Embedded vector store closed');
+    }
+  }
+
+  /**
+   * データベースが開かれているか確認
+   */
+  private ensureDb(): Database.Database {
+    if (!this.db) {
+      throw new Error('Embedded vector store is not connected. Call connect() first.');
+    }
+    return this.db;
+  }
+
+  /**
+   * コレクションのフラットインデックスを取得（未読み込みの場合はDBから読み込む）
+   * @throws コレクションが存在しない場合
+   */
+  private getIndex(collectionName: string): FlatIndex {
+    const db = this.ensureDb();
+    const cached = this.indexes.get(collectionName);
+    if (cached) {
+      return cached;
+    }
+
+    const collection = db
+      .prepare('SELECT dimension FROM collections WHERE name = ?')
+      .get(collectionName) as { dimension: number } | undefined;
+    if (!collection) {
+      throw new Error(`Collection ${collectionName} does not exist`);
+    }
+
+    const rows = db
+      .prepare('SELECT id, vector, metadata FROM vectors WHERE collection = ?')
+      .all(collectionName) as Array<{ id: string; vector: Buffer; metadata: string }>;
+
+    const index = new FlatIndex(collection.dimension);
+    for (const row of rows) {
+      index.set(row.id, fromBlob(row.vector), JSON.parse(row.metadata));
+    }
+
+    this.indexes.set(collectionName, index);
+    this.logger.debug(`Loaded ${index.size} vectors for collection: ${collectionName}`);
+    return index;
+  }
+
+  /**
+   * ベクトルIDを削除（DBとインデックスの両方）
+   */
+  private removeIds(collectionName: string, index: FlatIndex, ids: string[]): void {
+    const db = this.ensureDb();
+    const statement = db.prepare('DELETE FROM vectors WHERE collection = ? AND id = ?');
+
+    db.transaction(() => {
+      for (const id of ids) {
+        statement.run(collectionName, id);
+      }
+    })();
+
+    index.delete(ids);
+  }
+
+  /**
+   * コレクションを作成
+   */
+  async createCollection(name: string, dimension: number): Promise<void> {
+    const db = this.ensureDb();
+    this.logger.info(`Creating collection: ${name} (dimension: ${dimension})`);
+
+    const exists = db.prepare('SELECT 1 FROM collections WHERE name = ?').get(name);
+    if (exists) {
+      throw new Error(`Collection ${name} already exists`);
+    }
+
+    db.prepare('INSERT INTO collections (name, dimension) VALUES (?, ?)').run(name, dimension);
+    this.indexes.set(name, new FlatIndex(dimension));
+
+    this.logger.info(`Collection ${name} created successfully`);
+  }
+
+  /**
+   * コレクションを削除
+   */
+  async deleteCollection(name: string): Promise<void> {
+    const db = this.ensureDb();
+    this.logger.info(`Deleting collection: ${name}`);
+
+    const exists = db.prepare('SELECT 1 FROM collections WHERE name = ?').get(name);
+    if (!exists) {
+      this.logger.debug(`Collection ${name} does not exist, skipping deletion`);
+      return;
+    }
+
+    db.transaction(() => {
+      db.prepare('DELETE FROM vectors WHERE collection = ?').run(name);
+      db.prepare('DELETE FROM collections WHERE name = ?').run(name);
+    })();
+    this.indexes.delete(name);
+
+    this.logger.info(`Collection ${name} deleted successfully`);
+  }
+
+  /**
+   * ベクトルを挿入または更新
+   */
+  async upsert(collectionName: string, vectors: Vector[]): Promise<void> {
+    return await traceVectorDBOperation('upsert', 'embedded', async () => {
+      return await withTraceContext(async () => {
+        const db = this.ensureDb();
+        const index = this.getIndex(collectionName);
+        this.logger.debug(`Upserting ${vectors.length} vectors to collection: ${collectionName}`);
+
+        // 1件でも次元数が異なる場合は何も書き込まない
+        for (const v of vectors) {
+          index.ensureDimension(v.vector);
+        }
+
+        const statement = db.prepare(
+          'INSERT OR REPLACE INTO vectors (collection, id, vector, metadata) VALUES (?, ?, ?, ?)'
+        );
+        db.transaction(() => {
+          for (const v of vectors) {
+            statement.run(collectionName, v.id, toBlob(v.vector), JSON.stringify(v.metadata || {}));
+          }
+        })();
+
+        index.upsert(vectors);
+
+        this.logger.debug(`Upserted ${vectors.length} vectors successfully`);
+      });
+    });
+  }
+
+  /**
+   * 類似ベクトルを検索
+   */
+  async query(
+    collectionName: string,
+    vector: number[],
+    topK: number,
+    filter?: MetadataFilter
+  ): Promise<QueryResult[]> {
+    return await traceVectorDBOperation('query', 'embedded', async () => {
+      return await withTraceContext(async () => {
+        const index = this.getIndex(collectionName);
+        this.logger.debug(
+          `Querying collection: ${collectionName} (topK: ${topK}, filter: ${JSON.stringify(filter)})`
+        );
+
+        return index.query(vector, topK, filter);
+      });
+    });
+  }
+
+  /**
+   * ベクトルを削除
+   */
+  async delete(collectionName: string, ids: string[]): Promise<void> {
+    return await traceVectorDBOperation('delete', 'embedded', async () => {
+      return await withTraceContext(async () => {
+        const index = this.getIndex(collectionName);
+        this.logger.debug(`Deleting ${ids.length} vectors from collection: ${collectionName}`);
+
+        this.removeIds(collectionName, index, ids);
+
+        this.logger.debug(`Deleted ${ids.length} vectors successfully`);
+      });
+    });
+  }
+
+  /**
+   * メタデータが一致するベクトルをすべて削除
+   */
+  async deleteByFilter(collectionName: string, filter: MetadataFilter): Promise<void> {
+    return await traceVectorDBOperation('delete', 'embedded', async () => {
+      return await withTraceContext(async () => {
+        const index = this.getIndex(collectionName);
+        this.logger.debug(
+          `Deleting vectors from collection: ${collectionName} (filter: ${JSON.stringify(filter)})`
+        );
+
+        // 空のフィルタで全件削除しないようにする
+        if (!hasFilterConditions(filter)) {
+          throw new Error('deleteByFilter requires at least one filter condition');
+        }
+
+        const ids = index.matchingIds(filter);
+        this.removeIds(collectionName, index, ids);
+
+        this.logger.debug(`Deleted ${ids.length} vectors matching filter`);
+      });
+    });
+  }
+
+  /**
+   * コレクションの統計情報を取得
+   */
+  async getStats(collectionName: string): Promise<CollectionStats> {
+    this.logger.debug(`Getting stats for collection: ${collectionName}`);
+    return this.getIndex(collectionName).getStats();
+  }
+}
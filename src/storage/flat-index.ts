@@ -0,0 +1,133 @@
+/**
+ * Flat Index: 全件比較で検索するベクトルインデックス
+ *
+ * コレクション1つ分のベクトルをメモリ上に保持し、コサイン類似度の全件比較で検索します。
+ * インメモリプラグインはこのインデックスのみで、組み込みプラグインはSQLiteへの永続化と
+ * 組み合わせて使用します。
+ */
+
+import type { Vector, QueryResult, CollectionStats, MetadataFilter } from './types';
+import { matchesMetadataFilter } from './metadata-filter.js';
+import { vectorNorm, cosineScore } from './vector-math.js';
+
+/**
+ * フラットインデックスのエントリ
+ */
+interface FlatIndexEntry {
+  /** 埋め込みベクトル（呼び出し元の配列とは別のコピー） */
+  vector: Float32Array;
+  /** ベクトルのノルム（類似度計算用） */
+  norm: number;
+  /** メタデータ */
+  metadata: Record<string, unknown>;
+}
+
+/**
+ * フラットインデックスクラス
+ */
+export class FlatIndex {
+  /** ベクトルIDごとのエントリ */
+  private entries: Map<string, FlatIndexEntry> = new Map();
+
+  /**
+   * @param dimension ベクトル次元数
+   */
+  constructor(readonly dimension: number) {}
+
+  /**
+   * 保持しているベクトル数
+   */
+  get size(): number {
+    return this.entries.size;
+  }
+
+  /**
+   * ベクトルの次元数を検証
+   * @throws 次元数が異なる場合
+   */
+  ensureDimension(vector: ArrayLike<number>): void {
+    if (vector.length !== this.dimension) {
+      throw new Error(
+        `Vector dimension mismatch: expected ${this.dimension}, got ${vector.length}`
+      );
+    }
+  }
+
+  /**
+   * ベクトルを挿入または更新
+   *
+   * 1件でも次元数が異なる場合は何も書き込みません。
+   *
+   * @throws 次元数が異なるベクトルを含む場合
+   */
+  upsert(vectors: Vector[]): void {
+    for (const v of vectors) {
+      this.ensureDimension(v.vector);
+    }
+    for (const v of vectors) {
+      this.set(v.id, v.vector, v.metadata);
+    }
+  }
+
+  /**
+   * ベクトルを1件設定（次元数は検証済みであること）
+   */
+  set(id: string, vector: ArrayLike<number>, metadata?: Record<string, unknown>): void {
+    // 呼び出し元が後から配列を変更しても影響しないようコピーして保持
+    const copy = Float32Array.from(vector);
+    this.entries.set(id, { vector: copy, norm: vectorNorm(copy), metadata: { ...metadata } });
+  }
+
+  /**
+   * 類似ベクトルを検索
+   *
+   * フィルタを満たすベクトルのみスコアを計算し、フィルタ後の上位K件を返します。
+   *
+   * @throws 次元数が異なる場合
+   */
+  query(vector: number[], topK: number, filter?: MetadataFilter): QueryResult[] {
+    this.ensureDimension(vector);
+
+    const queryNorm = vectorNorm(vector);
+    const results: QueryResult[] = [];
+    for (const [id, entry] of this.entries) {
+      if (filter && !matchesMetadataFilter(entry.metadata, filter)) {
+        continue;
+      }
+      const score = cosineScore(vector, queryNorm, entry.vector, entry.norm);
+      results.push({ id, score, metadata: { ...entry.metadata } });
+    }
+
+    return results.sort((a, b) => b.score - a.score).slice(0, topK);
+  }
+
+  /**
+   * メタデータフィルタに一致するベクトルIDを取得
+   */
+  matchingIds(filter: MetadataFilter): string[] {
+    return Array.from(this.entries)
+      .filter(([, entry]) => matchesMetadataFilter(entry.metadata, filter))
+      .map(([id]) => id);
+  }
+
+  /**
+   * ベクトルを削除（存在しないIDは無視）
+   */
+  delete(ids: string[]): void {
+    for (const id of ids) {
+      this.entries.delete(id);
+    }
+  }
+
+  /**
+   * 統計情報を取得
+   */
+  getStats(): CollectionStats {
+    return {
+      vectorCount: this.entries.size,
+      dimension: this.dimension,
+      // ベクトルを32ビット浮動小数点数で保持したサイズ（ベクトル数 * 次元数 * 4バイト）
+      indexSize: this.entries.size * this.dimension * 4,
+    };
+  }
+}
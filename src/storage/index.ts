@@ -10,9 +10,24 @@ export type {
   CollectionStats,
   VectorStoreConfig,
   VectorStorePlugin,
+  MetadataFilter,
+  MetadataFilterValue,
 } from './types';
 
 export { VectorStorePluginRegistry } from './types';
-export { MilvusPlugin } from './milvus-plugin';
+export { matchesMetadataFilter, hasFilterConditions } from './metadata-filter';
+export { MilvusPlugin, buildFilterExpression, buildIdExpression } from './milvus-plugin';
+export { EmbeddedPlugin, DEFAULT_EMBEDDED_DB_PATH } from './embedded-plugin';
+export { MemoryPlugin } from './memory-plugin';
+export { FlatIndex } from './flat-index';
+export { vectorNorm, cosineScore } from './vector-math';
+export { QdrantPlugin, buildQdrantFilter, toPointId } from './qdrant-plugin';
+export type { QdrantFilter, QdrantCondition, QdrantFilterConversion } from './qdrant-plugin';
+export { retryWithBackoff, DEFAULT_RETRY_CONFIG } from './retry';
+export type { RetryConfig } from './retry';
+export { ChromaPlugin, buildChromaWhere } from './chroma-plugin';
+export type { ChromaWhere, ChromaWhereConversion } from './chroma-plugin';
 export { BM25Engine } from './bm25-engine';
 export type { BM25Params, SearchResult, InvertedIndexEntry, DocumentStats } from './bm25-engine';
+export { tokenize, splitIdentifier, STOP_WORDS } from './tokenizer';
+export type { Token, TokenizerType } from './tokenizer';
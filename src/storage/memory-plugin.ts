@@ -0,0 +1,194 @@
+/**
+ * Memory Plugin - インメモリベクターストアプラグイン実装
+ *
+ * ベクトルをプロセスのメモリ上にのみ保持します（切断・終了時に破棄）。
+ * 一時的なセッションやテストでの利用に加え、VectorStorePluginの契約を満たす
+ * 参照実装として適合性テストの基準に使用します。
+ */
+
+import type {
+  VectorStorePlugin,
+  VectorStoreConfig,
+  Vector,
+  QueryResult,
+  CollectionStats,
+  MetadataFilter,
+} from './types';
+import { hasFilterConditions } from './metadata-filter.js';
+import { FlatIndex } from './flat-index.js';
+import { Logger } from '../utils/logger';
+import { traceVectorDBOperation } from '../telemetry/instrumentation.js';
+import { withTraceContext } from '../telemetry/context-propagation.js';
+
+/**
+ * インメモリプラグインクラス
+ *
+ * VectorStorePluginインターフェースを実装し、コサイン類似度の全件比較で検索します。
+ */
+export class MemoryPlugin implements VectorStorePlugin {
+  readonly name = 'memory';
+
+  /** コレクションごとのフラットインデックス */
+  private collections: Map<string, FlatIndex> | null = null;
+  private logger: Logger;
+
+  constructor() {
+    this.logger = new Logger();
+  }
+
+  /**
+   * ストアを初期化（設定は使用しない）
+   */
+  async connect(_config: VectorStoreConfig): Promise<void> {
+    this.logger.info('Initializing in-memory vector store (data is not persisted)');
+    this.collections = new Map();
+  }
+
+  /**
+   * ストアを破棄
+   */
+  async disconnect(): Promise<void> {
+    if (this.collections) {
+      this.logger.info('Discarding in-memory vector store...');
+      this.collections = null;
+    }
+  }
+
+  /**
+   * ストアが初期化されているか確認
+   */
+  private ensureCollections(): Map<string, FlatIndex> {
+    if (!this.collections) {
+      throw new Error('In-memory vector store is not connected. Call connect() first.');
+    }
+    return this.collections;
+  }
+
+  /**
+   * コレクションを取得
+   * @throws コレクションが存在しない場合
+   */
+  private getCollection(collectionName: string): FlatIndex {
+    const collection = this.ensureCollections().get(collectionName);
+    if (!collection) {
+      throw new Error(`Collection ${collectionName} does not exist`);
+    }
+    return collection;
+  }
+
+  /**
+   * コレクションを作成
+   */
+  async createCollection(name: string, dimension: number): Promise<void> {
+    const collections = this.ensureCollections();
+    this.logger.info(`Creating collection: ${name} (dimension: ${dimension})`);
+
+    if (collections.has(name)) {
+      throw new Error(`Collection ${name} already exists`);
+    }
+    collections.set(name, new FlatIndex(dimension));
+
+    this.logger.info(`Collection ${name} created successfully`);
+  }
+
+  /**
+   * コレクションを削除
+   */
+  async deleteCollection(name: string): Promise<void> {
+    const collections = this.ensureCollections();
+    this.logger.info(`Deleting collection: ${name}`);
+
+    if (!collections.delete(name)) {
+      this.logger.debug(`Collection ${name} does not exist, skipping deletion`);
+      return;
+    }
+
+    this.logger.info(`Collection ${name} deleted successfully`);
+  }
+
+  /**
+   * ベクトルを挿入または更新
+   */
+  async upsert(collectionName: string, vectors: Vector[]): Promise<void> {
+    return await traceVectorDBOperation('upsert', 'memory', async () => {
+      return await withTraceContext(async () => {
+        const collection = this.getCollection(collectionName);
+        this.logger.debug(`Upserting ${vectors.length} vectors to collection: ${collectionName}`);
+
+        collection.upsert(vectors);
+
+        this.logger.debug(`Upserted ${vectors.length} vectors successfully`);
+      });
+    });
+  }
+
+  /**
+   * 類似ベクトルを検索
+   */
+  async query(
+    collectionName: string,
+    vector: number[],
+    topK: number,
+    filter?: MetadataFilter
+  ): Promise<QueryResult[]> {
+    return await traceVectorDBOperation('query', 'memory', async () => {
+      return await withTraceContext(async () => {
+        const collection = this.getCollection(collectionName);
+        this.logger.debug(
+          `Querying collection: ${collectionName} (topK: ${topK}, filter: ${JSON.stringify(filter)})`
+        );
+
+        return collection.query(vector, topK, filter);
+      });
+    });
+  }
+
+  /**
+   * ベクトルを削除
+   */
+  async delete(collectionName: string, ids: string[]): Promise<void> {
+    return await traceVectorDBOperation('delete', 'memory', async () => {
+      return await withTraceContext(async () => {
+        const collection = this.getCollection(collectionName);
+        this.logger.debug(`Deleting ${ids.length} vectors from collection: ${collectionName}`);
+
+        // 存在しないIDは無視する
+        collection.delete(ids);
+
+        this.logger.debug(`Deleted ${ids.length} vectors successfully`);
+      });
+    });
+  }
+
+  /**
+   * メタデータが一致するベクトルをすべて削除
+   */
+  async deleteByFilter(collectionName: string, filter: MetadataFilter): Promise<void> {
+    return await traceVectorDBOperation('delete', 'memory', async () => {
+      return await withTraceContext(async () => {
+        const collection = this.getCollection(collectionName);
+        this.logger.debug(
+          `Deleting vectors from collection: ${collectionName} (filter: ${JSON.stringify(filter)})`
+        );
+
+        // 空のフィルタで全件削除しないようにする
+        if (!hasFilterConditions(filter)) {
+          throw new Error('deleteByFilter requires at least one filter condition');
+        }
+
+        const ids = collection.matchingIds(filter);
+        collection.delete(ids);
+
+        this.logger.debug(`Deleted ${ids.length} vectors matching filter`);
+      });
+    });
+  }
+
+  /**
+   * コレクションの統計情報を取得
+   */
+  async getStats(collectionName: string): Promise<CollectionStats> {
+    this.logger.debug(`Getting stats for collection: ${collectionName}`);
+    return this.getCollection(collectionName).getStats();
+  }
+}
@@ -0,0 +1,64 @@
+/**
+ * Metadata Filter: メタデータフィルタの評価
+ *
+ * フィルタ式に変換できないバックエンドやテスト用プラグインが、
+ * `MetadataFilter`の意味（完全一致 / in / $like）を共通の実装で評価するためのユーティリティ
+ */
+
+import type { MetadataFilter, MetadataFilterValue } from './types.js';
+
+/**
+ * `$like`条件かどうか判定
+ */
+export function isLikeCondition(value: MetadataFilterValue): value is { $like: string } {
+  return typeof value === 'object' && value !== null && !Array.isArray(value) && '$like' in value;
+}
+
+/**
+ * `$like`パターンを正規表現に変換（`%`は任意の文字列）
+ */
+export function likePatternToRegExp(pattern: string): RegExp {
+  const escaped = pattern
+    .split('%')
+    .map((part) => part.replace(/[.*+?^${}()|[\]\\]/g, '\\$&'))
+    .join('.*');
+  return new RegExp(`^${escaped}$`, 's');
+}
+
+/**
+ * メタデータが単一の条件を満たすか判定
+ */
+function matchesCondition(actual: unknown, expected: MetadataFilterValue): boolean {
+  if (Array.isArray(expected)) {
+    // 空配列は条件なしとして扱う
+    return expected.length === 0 || expected.some((value) => value === actual);
+  }
+  if (isLikeCondition(expected)) {
+    return typeof actual === 'string' && likePatternToRegExp(expected.$like).test(actual);
+  }
+  return actual === expected;
+}
+
+/**
+ * フィルタに条件が含まれるか判定（空配列は条件として数えない）
+ * @param filter メタデータフィルタ
+ * @returns 条件が1つ以上ある場合true
+ */
+export function hasFilterConditions(filter: MetadataFilter): boolean {
+  return Object.values(filter).some((value) => !Array.isArray(value) || value.length > 0);
+}
+
+/**
+ * メタデータがフィルタのすべての条件を満たすか判定
+ * @param metadata ベクトルのメタデータ
+ * @param filter メタデータフィルタ
+ * @returns すべての条件を満たす場合true
+ */
+export function matchesMetadataFilter(
+  metadata: Record<string, unknown> | undefined,
+  filter: MetadataFilter
+): boolean {
+  return Object.entries(filter).every(([key, expected]) =>
+    matchesCondition(metadata?.[key], expected)
+  );
+}
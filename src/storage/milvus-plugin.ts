@@ -11,30 +11,123 @@ import type {
   Vector,
   QueryResult,
   CollectionStats,
+  MetadataFilter,
 } from './types';
+import { isLikeCondition } from './metadata-filter.js';
 import { Logger } from '../utils/logger';
+import { retryWithBackoff, DEFAULT_RETRY_CONFIG } from './retry.js';
+import type { RetryConfig } from './retry.js';
 import { traceVectorDBOperation } from '../telemetry/instrumentation.js';
 import { withTraceContext } from '../telemetry/context-propagation.js';
 
 /**
- * リトライ設定
+ * スカラーフィールドとして宣言するメタデータキーと最大長
+ *
+ * JSONフィールド内のキーはインデックスが効かないため、検索フィルタで頻繁に使う
+ * キーはスカラーフィールドとして持たせ、フィルタ式で直接参照します。
+ */
+export const SCALAR_METADATA_FIELDS: Readonly<Record<string, number>> = {
+  project_id: 512,
+  file_path: 1024,
+  language: 64,
+  type: 64,
+  file_type: 32,
+};
+
+/**
+ * フィルタ式の文字列リテラルをエスケープ
  */
-interface RetryConfig {
-  maxRetries: number;
-  initialDelay: number;
-  maxDelay: number;
-  backoffMultiplier: number;
+function quoteString(value: string): string {
+  return `"${value.replace(/\\/g, '\\\\').replace(/"/g, '\\"')}"`;
 }
 
 /**
- * デフォルトリトライ設定
+ * フィルタ式のリテラルを生成
  */
-const DEFAULT_RETRY_CONFIG: RetryConfig = {
-  maxRetries: 3,
-  initialDelay: 1000, // 1秒
-  maxDelay: 10000, // 10秒
-  backoffMultiplier: 2,
-};
+function formatLiteral(value: string | number | boolean): string {
+  return typeof value === 'string' ? quoteString(value) : String(value);
+}
+
+/**
+ * ベクトルIDを指定するフィルタ式を生成
+ *
+ * IDには見出しや修飾名がそのまま含まれるため、`"`や`\`をエスケープする
+ */
+export function buildIdExpression(ids: string[]): string {
+  return `id in [${ids.map(quoteString).join(', ')}]`;
+}
+
+/**
+ * メタデータフィルタをMilvusのフィルタ式に変換
+ *
+ * スカラーフィールドとして宣言済みのキーはフィールドを直接参照し、
+ * それ以外（または旧スキーマのコレクション）は`metadata["key"]`を参照します。
+ *
+ * @param filter メタデータフィルタ
+ * @param scalarFields コレクションに存在するスカラーフィールド名
+ * @returns フィルタ式（条件がない場合は空文字列）
+ */
+export function buildFilterExpression(
+  filter: MetadataFilter,
+  scalarFields: ReadonlySet<string> = new Set()
+): string {
+  const conditions = Object.entries(filter).map(([key, value]) => {
+    const field = scalarFields.has(key) ? key : `metadata[${quoteString(key)}]`;
+
+    if (Array.isArray(value)) {
+      // 空配列は条件なしとして扱う
+      if (value.length === 0) {
+        return '';
+      }
+      return `${field} in [${value.map(formatLiteral).join(', ')}]`;
+    }
+    if (isLikeCondition(value)) {
+      return `${field} like ${quoteString(value.$like)}`;
+    }
+    if (typeof value === 'string' || typeof value === 'number' || typeof value === 'boolean') {
+      return `${field} == ${formatLiteral(value)}`;
+    }
+    return '';
+  });
+  return conditions.filter((c) => c).join(' && ');
+}
+
+/**
+ * 最大長（UTF-8のバイト数）を超える値を持つスカラーフィールド名を取得
+ * @param row 挿入する行
+ * @param scalarFields コレクションに存在するスカラーフィールド名
+ */
+function findOversizedScalarFields(
+  row: Record<string, unknown>,
+  scalarFields: Iterable<string>
+): string[] {
+  return Array.from(scalarFields).filter(
+    (field) =>
+      field in SCALAR_METADATA_FIELDS &&
+      Buffer.byteLength(String(row[field] ?? ''), 'utf8') > SCALAR_METADATA_FIELDS[field]
+  );
+}
+
+/**
+ * ベクトルを単位ベクトルに正規化
+ *
+ * コレクションはL2距離のインデックスを使用するため、単位ベクトル同士の二乗距離 d から
+ * コサイン類似度（1 - d/2）を求められるよう、保存・検索するベクトルを正規化します。
+ */
+function normalizeVector(vector: number[]): number[] {
+  const norm = Math.sqrt(vector.reduce((sum, x) => sum + x * x, 0));
+  return norm === 0 ? vector : vector.map((x) => x / norm);
+}
+
+/**
+ * コレクションのスキーマ情報
+ */
+interface CollectionSchemaInfo {
+  /** ベクトルの次元数 */
+  dimension: number;
+  /** コレクションに存在するスカラーフィールド名（旧スキーマでは空集合） */
+  scalarFields: Set<string>;
+}
 
 /**
  * Milvusプラグイン設定
@@ -61,6 +154,8 @@ export class MilvusPlugin implements VectorStorePlugin {
   private config: MilvusPluginConfig | null = null;
   private logger: Logger;
   private retryConfig: RetryConfig;
+  /** コレクションごとのスキーマ情報（次元数の検証と旧スキーマ判定用キャッシュ） */
+  private schemaCache: Map<string, CollectionSchemaInfo> = new Map();
 
   constructor(retryConfig: Partial<RetryConfig> = {}) {
     this.logger = new Logger();
@@ -74,31 +169,7 @@ export class MilvusPlugin implements VectorStorePlugin {
     operation: () => Promise<T>,
     operationName: string
   ): Promise<T> {
-    let lastError: Error | null = null;
-    let delay = this.retryConfig.initialDelay;
-
-    for (let attempt = 0; attempt <= this.retryConfig.maxRetries; attempt++) {
-      try {
-        return await operation();
-      } catch (error) {
-        lastError = error as Error;
-
-        if (attempt < this.retryConfig.maxRetries) {
-          this.logger.warn(
-            `${operationName} failed (attempt ${attempt + 1}/${
-              this.retryConfig.maxRetries + 1
-            }), retrying in ${delay}ms...`
-          );
-
-          await new Promise((resolve) => setTimeout(resolve, delay));
-          delay = Math.min(delay * this.retryConfig.backoffMultiplier, this.retryConfig.maxDelay);
-        }
-      }
-    }
-
-    throw new Error(
-      `${operationName} failed after ${this.retryConfig.maxRetries + 1} attempts: ${lastError?.message}`
-    );
+    return retryWithBackoff(operation, operationName, this.retryConfig, this.logger);
   }
 
   /**
@@ -144,6 +215,7 @@ export class MilvusPlugin implements VectorStorePlugin {
       this.logger.info('Disconnecting from Milvus...');
       this.client = null;
       this.config = null;
+      this.schemaCache.clear();
       this.logger.info('Disconnected from Milvus');
     }
   }
@@ -179,6 +251,7 @@ export class MilvusPlugin implements VectorStorePlugin {
           name: 'id',
           data_type: DataType.VarChar,
           is_primary_key: true,
+          // IDはVectorIdAllocatorで512バイト以内に短縮される
           max_length: 512,
         },
         {
@@ -186,6 +259,11 @@ export class MilvusPlugin implements VectorStorePlugin {
           data_type: DataType.FloatVector,
           dim: dimension,
         },
+        ...Object.entries(SCALAR_METADATA_FIELDS).map(([fieldName, maxLength]) => ({
+          name: fieldName,
+          data_type: DataType.VarChar,
+          max_length: maxLength,
+        })),
         {
           name: 'metadata',
           data_type: DataType.JSON,
@@ -194,6 +272,7 @@ export class MilvusPlugin implements VectorStorePlugin {
     };
 
     await client.createCollection(schema);
+    this.schemaCache.delete(name);
 
     // インデックスを作成（IVF_FLAT）
     await client.createIndex({
@@ -224,11 +303,15 @@ export class MilvusPlugin implements VectorStorePlugin {
     }
 
     await client.dropCollection({ collection_name: name });
+    this.schemaCache.delete(name);
     this.logger.info(`Collection ${name} deleted successfully`);
   }
 
   /**
    * ベクトルを挿入または更新
+   *
+   * スカラーフィールドの最大長（`SCALAR_METADATA_FIELDS`）を超えるメタデータを持つベクトルは
+   * 警告して保存しません（同じIDの既存のベクトルは削除されます）。
    */
   async upsert(collectionName: string, vectors: Vector[]): Promise<void> {
     return await traceVectorDBOperation('upsert', 'milvus', async () => {
@@ -242,32 +325,54 @@ export class MilvusPlugin implements VectorStorePlugin {
           throw new Error(`Collection ${collectionName} does not exist`);
         }
 
-        // データを整形
-        const data = vectors.map((v) => ({
-          id: v.id,
-          vector: v.vector,
-          metadata: v.metadata || {},
-        }));
+        // 次元数を検証（既存のベクトルを削除する前に、1件でも異なれば何も書き込まない）
+        const { dimension, scalarFields } = await this.getCollectionSchema(collectionName);
+        for (const v of vectors) {
+          this.ensureDimension(v.vector, dimension);
+        }
 
-        // 既存のベクトルを削除（upsert動作のため）
-        const ids = vectors.map((v) => v.id);
-        try {
-          await client.delete({
-            collection_name: collectionName,
-            expr: `id in [${ids.map((id) => `"${id}"`).join(', ')}]`,
-          } as any);
-        } catch (error) {
-          // 削除エラーは無視（エンティティが存在しない場合）
-          this.logger.debug(`Delete before upsert failed (ignoring): ${error}`);
+        // データを整形（スカラーフィールドはメタデータから転記）
+        // 最大長を超える値が1件でもあるとバッチ全体の挿入が失敗するため、その行は警告して除外する
+        // （切り詰めるとfile_path・project_idによる絞り込みや削除の対象から外れるため）
+        const data: Array<Record<string, unknown>> = [];
+        for (const v of vectors) {
+          const metadata = v.metadata || {};
+          const row: Record<string, unknown> = {
+            id: v.id,
+            vector: normalizeVector(v.vector),
+            metadata,
+          };
+          for (const field of scalarFields) {
+            const value = metadata[field];
+            row[field] = value === undefined || value === null ? '' : String(value);
+          }
+
+          const oversized = findOversizedScalarFields(row, scalarFields);
+          if (oversized.length > 0) {
+            this.logger.warn(
+              `Skipping vector ${v.id}: ${oversized.join(', ')} exceeds the maximum length`
+            );
+            continue;
+          }
+          data.push(row);
         }
 
-        // 新しいデータを挿入
-        await client.insert({
+        // 既存のベクトルを削除（upsert動作のため）
+        // 削除に失敗したまま挿入すると主キーが重複するため、エラーは呼び出し元に伝える
+        await client.delete({
           collection_name: collectionName,
-          data,
-        });
+          expr: buildIdExpression(vectors.map((v) => v.id)),
+        } as any);
+
+        // 新しいデータを挿入
+        if (data.length > 0) {
+          await client.insert({
+            collection_name: collectionName,
+            data,
+          });
+        }
 
-        this.logger.debug(`Upserted ${vectors.length} vectors successfully`);
+        this.logger.debug(`Upserted ${data.length} vectors successfully`);
       });
     });
   }
@@ -279,7 +384,7 @@ export class MilvusPlugin implements VectorStorePlugin {
     collectionName: string,
     vector: number[],
     topK: number,
-    filter?: Record<string, unknown>
+    filter?: MetadataFilter
   ): Promise<QueryResult[]> {
     return await traceVectorDBOperation('query', 'milvus', async () => {
       return await withTraceContext(async () => {
@@ -294,26 +399,16 @@ export class MilvusPlugin implements VectorStorePlugin {
           throw new Error(`Collection ${collectionName} does not exist`);
         }
 
-        // フィルタ式を構築
-        let expr: string | undefined;
-        if (filter) {
-          const conditions = Object.entries(filter).map(([key, value]) => {
-            if (typeof value === 'string') {
-              return `metadata["${key}"] == "${value}"`;
-            } else if (typeof value === 'number') {
-              return `metadata["${key}"] == ${value}`;
-            } else if (typeof value === 'boolean') {
-              return `metadata["${key}"] == ${value}`;
-            }
-            return '';
-          });
-          expr = conditions.filter((c) => c).join(' && ');
-        }
+        const { dimension, scalarFields } = await this.getCollectionSchema(collectionName);
+        this.ensureDimension(vector, dimension);
+
+        // フィルタ式を構築（検索時に適用し、フィルタ後の上位K件を取得する）
+        const expr = filter ? buildFilterExpression(filter, scalarFields) || undefined : undefined;
 
         // 検索実行
         const results = await client.search({
           collection_name: collectionName,
-          data: [vector],
+          data: [normalizeVector(vector)],
           limit: topK,
           output_fields: ['id', 'metadata'],
           filter: expr,
@@ -326,7 +421,8 @@ export class MilvusPlugin implements VectorStorePlugin {
 
         return results.results.map((result: any) => ({
           id: result.id as string,
-          score: 1 - (result.score as number), // L2距離を類似度に変換（0-1の範囲）
+          // 単位ベクトル同士の二乗L2距離（0-4）を(1 + コサイン類似度) / 2（0-1）に変換
+          score: Math.min(1, Math.max(0, 1 - (result.score as number) / 4)),
           metadata: result.metadata as Record<string, unknown>,
         }));
       });
@@ -355,7 +451,7 @@ export class MilvusPlugin implements VectorStorePlugin {
         // 削除実行
         await client.delete({
           collection_name: collectionName,
-          expr: `id in [${ids.map((id) => `"${id}"`).join(', ')}]`,
+          expr: buildIdExpression(ids),
         } as any);
 
         this.logger.debug(`Deleted ${ids.length} vectors successfully`);
@@ -363,6 +459,88 @@ export class MilvusPlugin implements VectorStorePlugin {
     });
   }
 
+  /**
+   * メタデータが一致するベクトルをすべて削除
+   */
+  async deleteByFilter(collectionName: string, filter: MetadataFilter): Promise<void> {
+    return await traceVectorDBOperation('delete', 'milvus', async () => {
+      return await withTraceContext(async () => {
+        const client = this.ensureClient();
+        this.logger.debug(
+          `Deleting vectors from collection: ${collectionName} (filter: ${JSON.stringify(filter)})`
+        );
+
+        // コレクションの存在確認
+        const hasCollection = await client.hasCollection({ collection_name: collectionName });
+        if (!hasCollection.value) {
+          throw new Error(`Collection ${collectionName} does not exist`);
+        }
+
+        // 空のフィルタで全件削除しないようにする
+        const { scalarFields } = await this.getCollectionSchema(collectionName);
+        const expr = buildFilterExpression(filter, scalarFields);
+        if (!expr) {
+          throw new Error('deleteByFilter requires at least one filter condition');
+        }
+
+        // 削除実行
+        await client.delete({
+          collection_name: collectionName,
+          expr,
+        } as any);
+
+        this.logger.debug(`Deleted vectors matching filter: ${expr}`);
+      });
+    });
+  }
+
+  /**
+   * コレクションのスキーマ情報を取得
+   *
+   * スカラーフィールド導入前に作成されたコレクションではスカラーフィールドが空集合となり、
+   * フィルタは`metadata["key"]`で評価されます。
+   */
+  private async getCollectionSchema(collectionName: string): Promise<CollectionSchemaInfo> {
+    const cached = this.schemaCache.get(collectionName);
+    if (cached) {
+      return cached;
+    }
+
+    const client = this.ensureClient();
+    const collectionInfo = await client.describeCollection({ collection_name: collectionName });
+    const fields: any[] = collectionInfo?.schema?.fields ?? [];
+
+    // ベクトルフィールドの次元数（SDKは型パラメータを文字列で返す）
+    const dimValue = fields.find((f) => f.name === 'vector')?.dim;
+    const dimension =
+      typeof dimValue === 'number'
+        ? dimValue
+        : typeof dimValue === 'string'
+          ? parseInt(dimValue, 10)
+          : 0;
+
+    const info: CollectionSchemaInfo = {
+      dimension,
+      scalarFields: new Set<string>(
+        fields
+          .map((f) => f.name as string)
+          .filter((fieldName: string) => fieldName in SCALAR_METADATA_FIELDS)
+      ),
+    };
+    this.schemaCache.set(collectionName, info);
+    return info;
+  }
+
+  /**
+   * ベクトルの次元数を検証
+   * @throws 次元数が異なる場合
+   */
+  private ensureDimension(vector: number[], dimension: number): void {
+    if (vector.length !== dimension) {
+      throw new Error(`Vector dimension mismatch: expected ${dimension}, got ${vector.length}`);
+    }
+  }
+
   /**
    * コレクションの統計情報を取得
    */
@@ -376,32 +554,13 @@ export class MilvusPlugin implements VectorStorePlugin {
       throw new Error(`Collection ${collectionName} does not exist`);
     }
 
-    // コレクション情報を取得
-    const collectionInfo = await client.describeCollection({ collection_name: collectionName });
-
-    // エンティティ数を取得
-    const stats = await client.getCollectionStatistics({ collection_name: collectionName });
+    const { dimension } = await this.getCollectionSchema(collectionName);
 
-    // ベクトルフィールドの次元数を取得
-    const vectorField = collectionInfo.schema.fields.find(
-      (f: any) => f.data_type === DataType.FloatVector
-    );
-    const dimValue = vectorField?.dim;
-    const dimension =
-      typeof dimValue === 'number'
-        ? dimValue
-        : typeof dimValue === 'string'
-          ? parseInt(dimValue, 10)
-          : 0;
+    // エンティティ数を取得（getCollectionStatisticsの行数はコンパクションまで削除済みの行を含む）
+    const counted = await client.count({ collection_name: collectionName });
+    const vectorCount = Number(counted.data ?? 0);
 
     // インデックスサイズを概算（エンティティ数 * 次元数 * 4バイト）
-    const rowCount = stats.data.row_count;
-    const vectorCount =
-      typeof rowCount === 'string'
-        ? parseInt(rowCount, 10)
-        : typeof rowCount === 'number'
-          ? rowCount
-          : 0;
     const indexSize = vectorCount * dimension * 4;
 
     return {
@@ -0,0 +1,73 @@
+/**
+ * Position Codec: 転置インデックスのターム出現位置のエンコード
+ *
+ * 昇順の出現位置を直前の位置との差分（デルタ）に変換し、各差分を可変長整数（varint、
+ * LEB128形式: 下位7ビットずつ、継続する場合は最上位ビットを1）で連結したバイナリとして保存します。
+ * 位置の多くは直前と近いため、ほとんどの差分が1バイトに収まります。
+ */
+
+/**
+ * 出現位置をデルタ+varintのバイナリにエンコード
+ *
+ * @example
+ * ```typescript
+ * encodePositions([3, 10, 200]); // <Buffer 03 07 be 01>
+ * ```
+ *
+ * @param positions 昇順（重複可）の出現位置
+ * @returns エンコードしたバイナリ
+ * @throws {RangeError} 負の値・整数以外・降順の位置が含まれる場合
+ */
+export function encodePositions(positions: number[]): Buffer {
+  const bytes: number[] = [];
+  let previous = 0;
+
+  for (const position of positions) {
+    const delta = position - previous;
+    if (!Number.isSafeInteger(position) || delta < 0) {
+      throw new RangeError(`Positions must be ascending non-negative integers: ${positions}`);
+    }
+    previous = position;
+
+    // 32ビットを超える値も扱えるようビット演算ではなく除算で分割
+    let remaining = delta;
+    while (remaining >= 0x80) {
+      bytes.push((remaining % 0x80) | 0x80);
+      remaining = Math.floor(remaining / 0x80);
+    }
+    bytes.push(remaining);
+  }
+
+  return Buffer.from(bytes);
+}
+
+/**
+ * デルタ+varintのバイナリを出現位置にデコード
+ * @param encoded `encodePositions`でエンコードしたバイナリ
+ * @returns 昇順の出現位置
+ * @throws {RangeError} varintが途中で終わっている場合
+ */
+export function decodePositions(encoded: Uint8Array): number[] {
+  const positions: number[] = [];
+  let previous = 0;
+  let delta = 0;
+  let multiplier = 1;
+
+  for (const byte of encoded) {
+    delta += (byte & 0x7f) * multiplier;
+    if (byte & 0x80) {
+      multiplier *= 0x80;
+      continue;
+    }
+
+    previous += delta;
+    positions.push(previous);
+    delta = 0;
+    multiplier = 1;
+  }
+
+  if (multiplier !== 1) {
+    throw new RangeError('Truncated varint in encoded positions');
+  }
+  return positions;
+}
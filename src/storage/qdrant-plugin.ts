@@ -0,0 +1,572 @@
+/**
+ * Qdrant Plugin - Qdrant VectorDB プラグイン実装
+ *
+ * QdrantのREST APIを使用し、セルフホストのQdrantとQdrant Cloudの両方に対応
+ */
+
+import { createHash } from 'crypto';
+import type {
+  VectorStorePlugin,
+  VectorStoreConfig,
+  Vector,
+  QueryResult,
+  CollectionStats,
+  MetadataFilter,
+} from './types';
+import { isLikeCondition, matchesMetadataFilter } from './metadata-filter.js';
+import { Logger } from '../utils/logger';
+import { retryWithBackoff, DEFAULT_RETRY_CONFIG } from './retry.js';
+import type { RetryConfig } from './retry.js';
+import { traceVectorDBOperation } from '../telemetry/instrumentation.js';
+import { withTraceContext } from '../telemetry/context-propagation.js';
+
+/**
+ * ペイロードインデックス（keyword）を作成するメタデータキー
+ *
+ * 検索フィルタで完全一致・inに使うキーのみ対象とし、部分一致（`$like`）で使う
+ * `file_path`は全文インデックスなしの部分文字列一致で評価させるため除外します。
+ */
+export const KEYWORD_PAYLOAD_FIELDS: readonly string[] = [
+  'project_id',
+  'language',
+  'type',
+  'file_type',
+];
+
+/**
+ * フィルタを完全に表現できない`$like`条件がある場合に取得する候補の倍率
+ */
+const LIKE_CANDIDATE_FACTOR = 4;
+
+/**
+ * スクロールAPIで1回に取得する件数
+ */
+const SCROLL_PAGE_SIZE = 256;
+
+/**
+ * Qdrantのフィルタ条件
+ */
+export type QdrantCondition =
+  | { key: string; match: { value: string | number | boolean } }
+  | { key: string; match: { any: Array<string | number> } }
+  | { key: string; match: { text: string } };
+
+/**
+ * Qdrantのフィルタ（すべての条件をANDで結合）
+ */
+export interface QdrantFilter {
+  must: QdrantCondition[];
+}
+
+/**
+ * メタデータフィルタの変換結果
+ */
+export interface QdrantFilterConversion {
+  /** Qdrantのフィルタ（条件がない場合はundefined） */
+  filter: QdrantFilter | undefined;
+  /**
+   * フィルタを完全に表現できたか
+   *
+   * 前方一致・後方一致等の`$like`は固定部分の部分文字列一致で代用するため、
+   * falseの場合は取得後に`matchesMetadataFilter`で判定し直す必要があります。
+   */
+  exact: boolean;
+}
+
+/**
+ * メタデータフィルタをQdrantのフィルタに変換
+ *
+ * メタデータはペイロードの`metadata`以下に保存するため、キーは`metadata.<key>`を参照します。
+ *
+ * @param filter メタデータフィルタ
+ * @returns 変換結果
+ */
+export function buildQdrantFilter(filter: MetadataFilter): QdrantFilterConversion {
+  const must: QdrantCondition[] = [];
+  let exact = true;
+
+  for (const [name, value] of Object.entries(filter)) {
+    const key = `metadata.${name}`;
+
+    if (Array.isArray(value)) {
+      // 空配列は条件なしとして扱う
+      if (value.length > 0) {
+        must.push({ key, match: { any: value } });
+      }
+    } else if (isLikeCondition(value)) {
+      const parts = value.$like.split('%');
+      if (parts.length === 1) {
+        must.push({ key, match: { value: value.$like } });
+      } else if (parts.length === 3 && parts[0] === '' && parts[2] === '') {
+        // `%text%`は部分文字列一致でそのまま表現できる
+        must.push({ key, match: { text: parts[1] } });
+      } else {
+        exact = false;
+        const literal = parts.reduce((a, b) => (b.length > a.length ? b : a), '');
+        if (literal) {
+          must.push({ key, match: { text: literal } });
+        }
+      }
+    } else {
+      must.push({ key, match: { value } });
+    }
+  }
+
+  return { filter: must.length > 0 ? { must } : undefined, exact };
+}
+
+/**
+ * ベクトルIDをQdrantのポイントID（UUID）に変換
+ *
+ * QdrantのポイントIDは符号なし整数かUUIDのみ受け付けるため、
+ * ベクトルIDのSHA-1から決定的なUUID（バージョン5形式）を生成します。
+ */
+export function toPointId(id: string): string {
+  const hex = createHash('sha1').update(id).digest('hex').slice(0, 32).split('');
+  hex[12] = '5';
+  hex[16] = ((parseInt(hex[16], 16) & 0x3) | 0x8).toString(16);
+  const uuid = hex.join('');
+  return [
+    uuid.slice(0, 8),
+    uuid.slice(8, 12),
+    uuid.slice(12, 16),
+    uuid.slice(16, 20),
+    uuid.slice(20, 32),
+  ].join('-');
+}
+
+/**
+ * ポイントのペイロード
+ */
+interface QdrantPayload {
+  /** 元のベクトルID */
+  vector_id: string;
+  /** メタデータ */
+  metadata: Record<string, unknown>;
+}
+
+/**
+ * 検索・スクロールで返されるポイント
+ */
+interface QdrantPoint {
+  id: string | number;
+  score?: number;
+  payload?: QdrantPayload;
+}
+
+/**
+ * コレクション情報（統計情報の取得に使う項目のみ）
+ */
+interface QdrantCollectionInfo {
+  points_count?: number;
+  config?: { params?: { vectors?: { size?: number } } };
+}
+
+/**
+ * Qdrantプラグイン設定
+ */
+interface QdrantPluginConfig {
+  url: string;
+  apiKey?: string;
+}
+
+/**
+ * Qdrantプラグインクラス
+ *
+ * VectorStorePluginインターフェースを実装し、
+ * QdrantのREST API（コサイン距離のコレクション）でベクトルを管理します。
+ *
+ * 設定（`vectorStore.config`）:
+ * - `url`（または`address`）: QdrantのURL（例: `http://localhost:6333`）
+ * - `apiKey`（または`token`）: APIキー（Qdrant Cloudの場合）
+ */
+export class QdrantPlugin implements VectorStorePlugin {
+  readonly name = 'qdrant';
+
+  private config: QdrantPluginConfig | null = null;
+  private logger: Logger;
+  private retryConfig: RetryConfig;
+
+  constructor(retryConfig: Partial<RetryConfig> = {}) {
+    this.logger = new Logger();
+    this.retryConfig = { ...DEFAULT_RETRY_CONFIG, ...retryConfig };
+  }
+
+  /**
+   * 指数バックオフでリトライ実行
+   */
+  private async retryWithBackoff<T>(
+    operation: () => Promise<T>,
+    operationName: string
+  ): Promise<T> {
+    return retryWithBackoff(operation, operationName, this.retryConfig, this.logger);
+  }
+
+  /**
+   * 接続設定を取得
+   */
+  private ensureConfig(): QdrantPluginConfig {
+    if (!this.config) {
+      throw new Error('Qdrant client is not connected. Call connect() first.');
+    }
+    return this.config;
+  }
+
+  /**
+   * REST APIを呼び出す
+   *
+   * 通信エラーと5xxはリトライし、4xxはリトライせずにエラーとします。
+   *
+   * @param method HTTPメソッド
+   * @param path APIのパス
+   * @param body リクエストボディ
+   * @param options.allowNotFound trueの場合は404をエラーにしない
+   * @returns レスポンスの`result`（404の場合はundefined）
+   */
+  private async request<T>(
+    method: string,
+    path: string,
+    body?: unknown,
+    options: { allowNotFound?: boolean } = {}
+  ): Promise<T | undefined> {
+    const { url, apiKey } = this.ensureConfig();
+    const headers: Record<string, string> = { 'Content-Type': 'application/json' };
+    if (apiKey) {
+      headers['api-key'] = apiKey;
+    }
+
+    const response = await this.retryWithBackoff(async () => {
+      const res = await fetch(`${url}${path}`, {
+        method,
+        headers,
+        body: body === undefined ? undefined : JSON.stringify(body),
+      });
+      if (res.status >= 500) {
+        throw new Error(`Qdrant request failed (${res.status}): ${await res.text()}`);
+      }
+      return res;
+    }, `${method} ${path}`);
+
+    if (response.status === 404 && options.allowNotFound) {
+      return undefined;
+    }
+
+    const json = (await response.json().catch(() => ({}))) as {
+      result?: T;
+      status?: { error?: string } | string;
+    };
+    if (!response.ok) {
+      const error = typeof json.status === 'object' ? json.status.error : json.status;
+      throw new Error(`Qdrant request failed (${response.status}): ${error ?? 'unknown error'}`);
+    }
+    return json.result;
+  }
+
+  /**
+   * コレクションのパス
+   */
+  private collectionPath(collectionName: string): string {
+    return `/collections/${encodeURIComponent(collectionName)}`;
+  }
+
+  /**
+   * コレクション情報を取得
+   * @throws コレクションが存在しない場合
+   */
+  private async getCollectionInfo(collectionName: string): Promise<QdrantCollectionInfo> {
+    const info = await this.request<QdrantCollectionInfo>(
+      'GET',
+      this.collectionPath(collectionName),
+      undefined,
+      { allowNotFound: true }
+    );
+    if (!info) {
+      throw new Error(`Collection ${collectionName} does not exist`);
+    }
+    return info;
+  }
+
+  /**
+   * Qdrantに接続
+   */
+  async connect(config: VectorStoreConfig): Promise<void> {
+    return await traceVectorDBOperation('connect' as any, 'qdrant', async () => {
+      return await withTraceContext(async () => {
+        this.logger.info('Connecting to Qdrant...');
+
+        const qdrantConfig = config.config as Record<string, unknown>;
+        const address = (qdrantConfig['url'] ?? qdrantConfig['address']) as string | undefined;
+        if (!address) {
+          throw new Error('Qdrant url is required');
+        }
+        this.config = {
+          url: (/^https?:\/\//.test(address) ? address : `http://${address}`).replace(/\/+$/, ''),
+          apiKey: (qdrantConfig['apiKey'] ?? qdrantConfig['token']) as string | undefined,
+        };
+
+        try {
+          // 接続テスト: バージョン情報を取得
+          const res = await this.retryWithBackoff(async () => {
+            const response = await fetch(`${this.config!.url}/`, {
+              headers: this.config!.apiKey ? { 'api-key': this.config!.apiKey } : {},
+            });
+            if (!response.ok) {
+              throw new Error(`Qdrant request failed (${response.status})`);
+            }
+            return (await response.json()) as { version?: string };
+          }, 'connect');
+          this.logger.info(`Connected to Qdrant version: ${res.version}`);
+        } catch (error) {
+          this.config = null;
+          throw error;
+        }
+      });
+    });
+  }
+
+  /**
+   * Qdrantから切断
+   */
+  async disconnect(): Promise<void> {
+    if (this.config) {
+      this.logger.info('Disconnecting from Qdrant...');
+      this.config = null;
+      this.logger.info('Disconnected from Qdrant');
+    }
+  }
+
+  /**
+   * コレクションを作成
+   */
+  async createCollection(name: string, dimension: number): Promise<void> {
+    this.ensureConfig();
+    this.logger.info(`Creating collection: ${name} (dimension: ${dimension})`);
+
+    // コレクションが既に存在するか確認
+    const existing = await this.request('GET', this.collectionPath(name), undefined, {
+      allowNotFound: true,
+    });
+    if (existing) {
+      throw new Error(`Collection ${name} already exists`);
+    }
+
+    await this.request('PUT', this.collectionPath(name), {
+      vectors: { size: dimension, distance: 'Cosine' },
+    });
+
+    // フィルタで頻繁に使うキーにペイロードインデックスを作成
+    for (const field of KEYWORD_PAYLOAD_FIELDS) {
+      await this.request('PUT', `${this.collectionPath(name)}/index?wait=true`, {
+        field_name: `metadata.${field}`,
+        field_schema: 'keyword',
+      });
+    }
+
+    this.logger.info(`Collection ${name} created successfully`);
+  }
+
+  /**
+   * コレクションを削除
+   */
+  async deleteCollection(name: string): Promise<void> {
+    this.ensureConfig();
+    this.logger.info(`Deleting collection: ${name}`);
+
+    const existing = await this.request('GET', this.collectionPath(name), undefined, {
+      allowNotFound: true,
+    });
+    if (!existing) {
+      this.logger.debug(`Collection ${name} does not exist, skipping deletion`);
+      return;
+    }
+
+    await this.request('DELETE', this.collectionPath(name));
+    this.logger.info(`Collection ${name} deleted successfully`);
+  }
+
+  /**
+   * ベクトルを挿入または更新
+   */
+  async upsert(collectionName: string, vectors: Vector[]): Promise<void> {
+    return await traceVectorDBOperation('upsert', 'qdrant', async () => {
+      return await withTraceContext(async () => {
+        this.ensureConfig();
+        this.logger.debug(`Upserting ${vectors.length} vectors to collection: ${collectionName}`);
+
+        // コレクションの存在確認
+        await this.getCollectionInfo(collectionName);
+
+        // 同じポイントIDへのupsertは上書きになる
+        const points = vectors.map((v) => {
+          const payload: QdrantPayload = { vector_id: v.id, metadata: v.metadata || {} };
+          return { id: toPointId(v.id), vector: v.vector, payload };
+        });
+        await this.request('PUT', `${this.collectionPath(collectionName)}/points?wait=true`, {
+          points,
+        });
+
+        this.logger.debug(`Upserted ${vectors.length} vectors successfully`);
+      });
+    });
+  }
+
+  /**
+   * 類似ベクトルを検索
+   */
+  async query(
+    collectionName: string,
+    vector: number[],
+    topK: number,
+    filter?: MetadataFilter
+  ): Promise<QueryResult[]> {
+    return await traceVectorDBOperation('query', 'qdrant', async () => {
+      return await withTraceContext(async () => {
+        this.ensureConfig();
+        this.logger.debug(
+          `Querying collection: ${collectionName} (topK: ${topK}, filter: ${JSON.stringify(filter)})`
+        );
+
+        // コレクションの存在確認
+        await this.getCollectionInfo(collectionName);
+
+        // フィルタを完全に表現できない場合は多めに取得して取得後に判定する
+        const conversion = filter ? buildQdrantFilter(filter) : { filter: undefined, exact: true };
+        const points =
+          (await this.request<QdrantPoint[]>(
+            'POST',
+            `${this.collectionPath(collectionName)}/points/search`,
+            {
+              vector,
+              limit: conversion.exact ? topK : topK * LIKE_CANDIDATE_FACTOR,
+              filter: conversion.filter,
+              with_payload: true,
+            }
+          )) ?? [];
+
+        return points
+          .filter((p) => conversion.exact || matchesMetadataFilter(p.payload?.metadata, filter!))
+          .slice(0, topK)
+          .map((p) => ({
+            id: p.payload?.vector_id ?? String(p.id),
+            score: ((p.score ?? 0) + 1) / 2, // コサイン類似度（-1〜1）を0-1の範囲に変換
+            metadata: p.payload?.metadata,
+          }));
+      });
+    });
+  }
+
+  /**
+   * ベクトルを削除
+   */
+  async delete(collectionName: string, ids: string[]): Promise<void> {
+    return await traceVectorDBOperation('delete', 'qdrant', async () => {
+      return await withTraceContext(async () => {
+        this.ensureConfig();
+        this.logger.debug(`Deleting ${ids.length} vectors from collection: ${collectionName}`);
+
+        // コレクションの存在確認
+        await this.getCollectionInfo(collectionName);
+
+        if (ids.length === 0) {
+          return;
+        }
+
+        // 存在しないIDは無視される
+        await this.request(
+          'POST',
+          `${this.collectionPath(collectionName)}/points/delete?wait=true`,
+          { points: ids.map(toPointId) }
+        );
+
+        this.logger.debug(`Deleted ${ids.length} vectors successfully`);
+      });
+    });
+  }
+
+  /**
+   * メタデータが一致するベクトルをすべて削除
+   */
+  async deleteByFilter(collectionName: string, filter: MetadataFilter): Promise<void> {
+    return await traceVectorDBOperation('delete', 'qdrant', async () => {
+      return await withTraceContext(async () => {
+        this.ensureConfig();
+        this.logger.debug(
+          `Deleting vectors from collection: ${collectionName} (filter: ${JSON.stringify(filter)})`
+        );
+
+        // コレクションの存在確認
+        await this.getCollectionInfo(collectionName);
+
+        // 空のフィルタで全件削除しないようにする
+        const conversion = buildQdrantFilter(filter);
+        if (!conversion.filter) {
+          throw new Error('deleteByFilter requires at least one filter condition');
+        }
+
+        const deletePath = `${this.collectionPath(collectionName)}/points/delete?wait=true`;
+        if (conversion.exact) {
+          await this.request('POST', deletePath, { filter: conversion.filter });
+        } else {
+          // 候補をスクロールで取得し、条件を満たすポイントのみIDで削除する
+          const ids = await this.scrollMatchingPointIds(collectionName, conversion.filter, filter);
+          if (ids.length > 0) {
+            await this.request('POST', deletePath, { points: ids });
+          }
+        }
+
+        this.logger.debug(`Deleted vectors matching filter: ${JSON.stringify(filter)}`);
+      });
+    });
+  }
+
+  /**
+   * フィルタの候補をスクロールで取得し、メタデータフィルタを満たすポイントIDを返す
+   */
+  private async scrollMatchingPointIds(
+    collectionName: string,
+    qdrantFilter: QdrantFilter,
+    filter: MetadataFilter
+  ): Promise<Array<string | number>> {
+    const ids: Array<string | number> = [];
+    let offset: string | number | null | undefined = undefined;
+
+    do {
+      const page: { points: QdrantPoint[]; next_page_offset?: string | number | null } =
+        (await this.request('POST', `${this.collectionPath(collectionName)}/points/scroll`, {
+          filter: qdrantFilter,
+          limit: SCROLL_PAGE_SIZE,
+          offset,
+          with_payload: true,
+          with_vector: false,
+        })) ?? { points: [] };
+
+      for (const point of page.points) {
+        if (matchesMetadataFilter(point.payload?.metadata, filter)) {
+          ids.push(point.id);
+        }
+      }
+      offset = page.next_page_offset;
+    } while (offset !== null && offset !== undefined);
+
+    return ids;
+  }
+
+  /**
+   * コレクションの統計情報を取得
+   */
+  async getStats(collectionName: string): Promise<CollectionStats> {
+    this.ensureConfig();
+    this.logger.debug(`Getting stats for collection: ${collectionName}`);
+
+    const info = await this.getCollectionInfo(collectionName);
+    const vectorCount = info.points_count ?? 0;
+    const dimension = info.config?.params?.vectors?.size ?? 0;
+
+    return {
+      vectorCount,
+      dimension,
+      // インデックスサイズを概算（ポイント数 * 次元数 * 4バイト）
+      indexSize: vectorCount * dimension * 4,
+    };
+  }
+}
@@ -0,0 +1,70 @@
+/**
+ * Retry: ベクターDBプラグイン共通のリトライ処理
+ *
+ * ネットワーク越しのベクターDB（Milvus、Qdrant等）への操作を指数バックオフでリトライします。
+ */
+
+import type { Logger } from '../utils/logger';
+
+/**
+ * リトライ設定
+ */
+export interface RetryConfig {
+  maxRetries: number;
+  initialDelay: number;
+  maxDelay: number;
+  backoffMultiplier: number;
+}
+
+/**
+ * デフォルトリトライ設定
+ */
+export const DEFAULT_RETRY_CONFIG: RetryConfig = {
+  maxRetries: 3,
+  initialDelay: 1000, // 1秒
+  maxDelay: 10000, // 10秒
+  backoffMultiplier: 2,
+};
+
+/**
+ * 指数バックオフでリトライ実行
+ *
+ * @param operation 実行する操作
+ * @param operationName ログ・エラーメッセージに使う操作名
+ * @param retryConfig リトライ設定
+ * @param logger リトライ時の警告を出力するロガー
+ * @returns 操作の結果
+ * @throws すべての試行が失敗した場合（最後のエラーメッセージを含む）
+ */
+export async function retryWithBackoff<T>(
+  operation: () => Promise<T>,
+  operationName: string,
+  retryConfig: RetryConfig,
+  logger: Logger
+): Promise<T> {
+  let lastError: Error | null = null;
+  let delay = retryConfig.initialDelay;
+
+  for (let attempt = 0; attempt <= retryConfig.maxRetries; attempt++) {
+    try {
+      return await operation();
+    } catch (error) {
+      lastError = error as Error;
+
+      if (attempt < retryConfig.maxRetries) {
+        logger.warn(
+          `${operationName} failed (attempt ${attempt + 1}/${
+            retryConfig.maxRetries + 1
+          }), retrying in ${delay}ms...`
+        );
+
+        await new Promise((resolve) => setTimeout(resolve, delay));
+        delay = Math.min(delay * retryConfig.backoffMultiplier, retryConfig.maxDelay);
+      }
+    }
+  }
+
+  throw new Error(
+    `${operationName} failed after ${retryConfig.maxRetries + 1} attempts: ${lastError?.message}`
+  );
+}
@@ -0,0 +1,139 @@
+/**
+ * Tokenizer: BM25用のトークナイザー
+ *
+ * 自然言語向けの`text`と、識別子を分割する`code`の2種類を提供します。
+ * ドキュメントの種類ごとに選択し、検索クエリには両方と互換のある`code`を使用します。
+ */
+
+/**
+ * トークナイザーの種類
+ *
+ * - text: 小文字化して記号で分割（自然言語・ドキュメント向け）
+ * - code: 識別子をcamelCase/snake_caseの境界で分割し、元の複合語も保持（ソースコード向け）
+ */
+export type TokenizerType = 'text' | 'code';
+
+/**
+ * トークン
+ */
+export interface Token {
+  /** ターム（小文字） */
+  term: string;
+  /** 出現位置（複合語と分割した部分語は同じ位置） */
+  position: number;
+}
+
+/**
+ * 英語の一般的なストップワード
+ */
+export const STOP_WORDS = new Set([
+  'a',
+  'an',
+  'and',
+  'are',
+  'as',
+  'at',
+  'be',
+  'by',
+  'for',
+  'from',
+  'has',
+  'he',
+  'in',
+  'is',
+  'it',
+  'its',
+  'of',
+  'on',
+  'that',
+  'the',
+  'this',
+  'to',
+  'was',
+  'will',
+  'with',
+]);
+
+/**
+ * 識別子として扱う文字の並び（`::`・`.`・`/`等の区切りはパスの要素ごとに分割される）
+ */
+const IDENTIFIER_PATTERN = /[\p{L}\p{N}_]+/gu;
+
+/**
+ * camelCase/PascalCaseの境界（`getUser` → `get|User`、`HTTPServer` → `HTTP|Server`）
+ */
+const CASE_BOUNDARY = /(?<=[a-z0-9])(?=[A-Z])|(?<=[A-Z])(?=[A-Z][a-z])/;
+
+/**
+ * 識別子を部分語に分割
+ * @param identifier 識別子（例: `getUserById`, `get_user_by_id`）
+ * @returns 小文字化した部分語（例: `['get', 'user', 'by', 'id']`）
+ */
+export function splitIdentifier(identifier: string): string[] {
+  return identifier
+    .split('_')
+    .flatMap((part) => part.split(CASE_BOUNDARY))
+    .filter((part) => part.length > 0)
+    .map((part) => part.toLowerCase());
+}
+
+/**
+ * 自然言語向けのトークン化
+ */
+function tokenizeText(text: string): Token[] {
+  return text
+    .toLowerCase()
+    .split(/[\s\-_@.,:;!?(){}[\]<>/\\|"'`~]+/)
+    .filter((token) => token.length > 0)
+    .filter((token) => !STOP_WORDS.has(token))
+    .map((term, position) => ({ term, position }));
+}
+
+/**
+ * ソースコード向けのトークン化
+ *
+ * 識別子ごとに、部分語を連結した複合語（`getUserById`と`get_user_by_id`はどちらも`getuserbyid`）と
+ * ストップワードを除いた部分語（`get`, `user`, `id`）を同じ位置に出力します。
+ */
+function tokenizeCode(text: string): Token[] {
+  const tokens: Token[] = [];
+  let position = 0;
+
+  for (const [identifier] of text.matchAll(IDENTIFIER_PATTERN)) {
+    const parts = splitIdentifier(identifier);
+    if (parts.length === 0) {
+      continue;
+    }
+
+    if (parts.length === 1) {
+      // 単独の単語は自然言語と同様にストップワードを除外
+      if (STOP_WORDS.has(parts[0])) {
+        continue;
+      }
+      tokens.push({ term: parts[0], position });
+    } else {
+      tokens.push({ term: parts.join(''), position });
+      for (const part of parts) {
+        if (!STOP_WORDS.has(part)) {
+          tokens.push({ term: part, position });
+        }
+      }
+    }
+    position++;
+  }
+
+  return tokens;
+}
+
+/**
+ * テキストをトークン化
+ * @param text 入力テキスト
+ * @param type トークナイザーの種類（デフォルト: text）
+ * @returns 出現順のトークン配列
+ */
+export function tokenize(text: string, type: TokenizerType = 'text'): Token[] {
+  if (!text || text.trim().length === 0) {
+    return [];
+  }
+  return type === 'code' ? tokenizeCode(text) : tokenizeText(text);
+}
@@ -41,6 +41,35 @@ export interface CollectionStats {
   indexSize: number;
 }
 
+/**
+ * メタデータフィルタの条件値
+ *
+ * - string / number / boolean: 完全一致
+ * - 配列: いずれかの値に一致（in）。空配列は条件なしとして扱う（すべてのベクトルに一致し、
+ *   `deleteByFilter`では条件として数えない）
+ * - `{ $like: pattern }`: パターン一致（`%`は任意の文字列。例: `%src/services%`）
+ */
+export type MetadataFilterValue =
+  | string
+  | number
+  | boolean
+  | Array<string | number>
+  | { $like: string };
+
+/**
+ * メタデータフィルタ（すべての条件をANDで結合）
+ *
+ * @example
+ * ```typescript
+ * const filter: MetadataFilter = {
+ *   project_id: '/work/app',
+ *   language: ['typescript', 'javascript'],
+ *   file_path: { $like: '%src/services%' },
+ * };
+ * ```
+ */
+export type MetadataFilter = Record<string, MetadataFilterValue>;
+
 /**
  * ベクターストア設定
  */
@@ -116,7 +145,7 @@ export interface VectorStorePlugin {
    * @param collectionName コレクション名
    * @param vector クエリベクトル
    * @param topK 取得する上位K件
-   * @param filter メタデータフィルタ（オプション）
+   * @param filter メタデータフィルタ（オプション、検索時に適用しフィルタ後の上位K件を返す）
    * @returns 類似度順にソートされた結果配列
    * @throws コレクションが存在しない場合
    */
@@ -124,7 +153,7 @@ export interface VectorStorePlugin {
     collectionName: string,
     vector: number[],
     topK: number,
-    filter?: Record<string, unknown>
+    filter?: MetadataFilter
   ): Promise<QueryResult[]>;
 
   /**
@@ -135,6 +164,15 @@ export interface VectorStorePlugin {
    */
   delete(collectionName: string, ids: string[]): Promise<void>;
 
+  /**
+   * メタデータが一致するベクトルをすべて削除
+   * @param collectionName コレクション名
+   * @param filter メタデータフィルタ（例: `{ file_path: '/path/to/file.ts' }`）
+   * @throws コレクションが存在しない場合
+   * @throws 条件がない場合（空のフィルタ、または空配列の条件のみ）
+   */
+  deleteByFilter(collectionName: string, filter: MetadataFilter): Promise<void>;
+
   /**
    * コレクションの統計情報を取得
    * @param collectionName コレクション名
@@ -0,0 +1,42 @@
+/**
+ * Vector Math: フラットインデックス用のベクトル演算
+ *
+ * 全件比較で検索するフラットインデックス（`flat-index.ts`）のスコア計算に使うユーティリティ
+ */
+
+/**
+ * ベクトルのノルムを計算
+ */
+export function vectorNorm(vector: ArrayLike<number>): number {
+  let sum = 0;
+  for (let i = 0; i < vector.length; i++) {
+    sum += vector[i] * vector[i];
+  }
+  return Math.sqrt(sum);
+}
+
+/**
+ * コサイン類似度を0-1のスコアに変換して計算
+ *
+ * @param a ベクトル
+ * @param aNorm `a`のノルム
+ * @param b ベクトル（`a`と同じ次元数）
+ * @param bNorm `b`のノルム
+ * @returns スコア（コサイン類似度-1〜1を0-1に変換したもの、ゼロベクトルの場合は0）
+ */
+export function cosineScore(
+  a: ArrayLike<number>,
+  aNorm: number,
+  b: ArrayLike<number>,
+  bNorm: number
+): number {
+  if (aNorm === 0 || bNorm === 0) {
+    return 0;
+  }
+
+  let dot = 0;
+  for (let i = 0; i < a.length; i++) {
+    dot += a[i] * b[i];
+  }
+  return (dot / (aNorm * bNorm) + 1) / 2;
+}
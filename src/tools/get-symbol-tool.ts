@@ -53,6 +53,10 @@ export interface SymbolDefinition {
   docstring?: string;
   parameters?: Array<{ name: string; type?: string }>;
   returnType?: string;
+  /** メソッドを所有する型名 */
+  parent?: string;
+  /** 実装しているトレイト・インターフェース */
+  implements?: string[];
 }
 
 /**
@@ -186,6 +190,8 @@ async function searchSymbolDefinitions(
         docstring: metadata['docstring'] as string | undefined,
         parameters: metadata['parameters'] as Array<{ name: string; type?: string }> | undefined,
         returnType: metadata['return_type'] as string | undefined,
+        parent: metadata['parent'] as string | undefined,
+        implements: metadata['implements'] as string[] | undefined,
       });
     }
 
@@ -40,6 +40,11 @@ export const InputSchema = z.object({
     .optional()
     .default(true)
     .describe('Markdownファイルを含めるか（デフォルト: true）'),
+  watch: z
+    .boolean()
+    .optional()
+    .default(false)
+    .describe('インデックス化後にファイル変更を監視して自動更新するか（デフォルト: false）'),
 });
 
 /**
@@ -57,11 +62,15 @@ export interface IndexProjectOutput {
     totalFiles: number;
     processedFiles: number;
     failedFiles: number;
+    skippedFiles: number; // 変更がないためスキップ
+    reembeddedFiles: number; // 変更を検出して再埋め込み
+    purgedFiles: number; // 削除されたためインデックスから除去
     totalSymbols: number;
     totalVectors: number;
     processingTime: number; // ミリ秒
   };
   errors?: Array<{ file: string; error: string }>;
+  watching: boolean; // File Watcherが有効か
 }
 
 /**
@@ -75,7 +84,7 @@ export async function handleIndexProject(
   // パラメータバリデーション
   const validatedInput = InputSchema.parse(input);
 
-  const { rootPath, languages, excludePatterns, includeDocuments } = validatedInput;
+  const { rootPath, languages, excludePatterns, includeDocuments, watch } = validatedInput;
 
   // ルートパスの存在確認
   try {
@@ -129,6 +138,11 @@ export async function handleIndexProject(
       indexingService.removeAllListeners('progressUpdate');
     }
 
+    // ファイル監視を開始（以降の変更はバックグラウンドでインデックスに反映）
+    if (watch) {
+      await indexingService.enableWatcher(projectId);
+    }
+
     // レスポンスを整形
     return {
       success: result.success,
@@ -137,6 +151,9 @@ export async function handleIndexProject(
         totalFiles: result.totalFiles,
         processedFiles: result.indexedFiles,
         failedFiles: result.failedFiles,
+        skippedFiles: result.skippedFiles,
+        reembeddedFiles: result.reembeddedFiles,
+        purgedFiles: result.purgedFiles,
         totalSymbols: result.totalSymbols,
         totalVectors: result.totalVectors,
         processingTime: result.processingTime,
@@ -145,6 +162,7 @@ export async function handleIndexProject(
         result.errors.length > 0
           ? result.errors.map((e) => ({ file: e.filePath, error: e.error }))
           : undefined,
+      watching: indexingService.isWatching(projectId),
     };
   } catch (error: any) {
     // エラーハンドリング
@@ -178,6 +196,11 @@ export function getInputSchemaJSON(): Record<string, any> {
         description: 'Markdownファイルを含めるか（デフォルト: true）',
         default: true,
       },
+      watch: {
+        type: 'boolean',
+        description: 'インデックス化後にファイル変更を監視して自動更新するか（デフォルト: false）',
+        default: false,
+      },
     },
     required: ['rootPath'],
   };
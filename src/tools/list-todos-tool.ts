@@ -0,0 +1,222 @@
+/**
+ * list_todos MCPツール
+ *
+ * インデックス化されたコメントからTODO/FIXME等のマーカー付きコメントを一覧するMCPツール。
+ * マーカー、ファイルパス、プロジェクトで絞り込み、技術的負債の棚卸しに利用します。
+ */
+
+import { z } from 'zod';
+import type { VectorStorePlugin, QueryResult, MetadataFilter } from '../storage/types.js';
+import { CommentMarker } from '../parser/types.js';
+
+/**
+ * ツール名
+ */
+export const TOOL_NAME = 'list_todos';
+
+/**
+ * ツール説明
+ */
+export const TOOL_DESCRIPTION =
+  'TODO/FIXME/HACK/BUG/XXX/NOTEなどのマーカー付きコメントを一覧します。マーカー、ファイルパス、プロジェクトで絞り込めます。';
+
+/**
+ * マーカーごとの初回の検索件数
+ */
+const INITIAL_QUERY_SIZE = 1000;
+
+/**
+ * マーカーごとの検索件数の上限（MilvusのtopK上限16384以下）
+ */
+export const MAX_TODOS_PER_MARKER = 16000;
+
+/**
+ * 入力パラメータスキーマ（Zod）
+ */
+export const InputSchema = z.object({
+  marker: z
+    .nativeEnum(CommentMarker)
+    .optional()
+    .describe('マーカー（例: "TODO", "FIXME"、未指定時は全マーカー）'),
+  pathPattern: z
+    .string()
+    .optional()
+    .describe('ファイルパスに含まれる文字列（例: "src/services"）'),
+  projectId: z
+    .string()
+    .optional()
+    .describe('プロジェクトID（オプション、未指定時は全プロジェクト）'),
+  limit: z.number().int().positive().optional().default(100).describe('返す件数（デフォルト: 100）'),
+});
+
+/**
+ * 入力パラメータ型
+ */
+export type ListTodosInput = z.infer<typeof InputSchema>;
+
+/**
+ * TODOアイテムの型
+ */
+export interface TodoItem {
+  marker: string;
+  content: string;
+  filePath: string;
+  language: string;
+  lineStart: number;
+  lineEnd: number;
+  associatedSymbol?: string;
+  projectId?: string;
+}
+
+/**
+ * 出力レスポンス型
+ */
+export interface ListTodosOutput {
+  todos: TodoItem[];
+  totalTodos: number;
+  countsByMarker: Record<string, number>;
+  /** 検索件数の上限に達したマーカーがあるか（trueの場合、総数と件数は下限値） */
+  truncated: boolean;
+  /** 検索件数の上限に達したマーカー */
+  truncatedMarkers: string[];
+}
+
+/**
+ * マーカー付きコメントを検索
+ *
+ * ベクトル検索はオフセットを指定できないため、結果が検索件数に達した場合は
+ * 件数を倍にして上限まで再検索する
+ */
+async function queryComments(
+  vectorStore: VectorStorePlugin,
+  collectionName: string,
+  vector: number[],
+  filter: MetadataFilter
+): Promise<{ results: QueryResult[]; truncated: boolean }> {
+  for (let topK = INITIAL_QUERY_SIZE; ; topK = Math.min(topK * 2, MAX_TODOS_PER_MARKER)) {
+    const results = await vectorStore.query(collectionName, vector, topK, filter);
+    if (results.length < topK) {
+      return { results, truncated: false };
+    }
+    if (topK >= MAX_TODOS_PER_MARKER) {
+      return { results, truncated: true };
+    }
+  }
+}
+
+/**
+ * list_todosツールハンドラー
+ */
+export async function handleListTodos(
+  input: ListTodosInput,
+  vectorStore: VectorStorePlugin,
+  dimension: number = 384,
+  collectionName: string = 'code_vectors'
+): Promise<ListTodosOutput> {
+  // パラメータバリデーション
+  const validatedInput = InputSchema.parse(input);
+
+  const { marker, pathPattern, projectId, limit } = validatedInput;
+  const markers = marker ? [marker] : Object.values(CommentMarker);
+
+  // ダミーベクトルで検索（メタデータフィルタのみ使用）
+  const dummyVector = new Array(dimension).fill(0);
+
+  const todos: TodoItem[] = [];
+  const truncatedMarkers: string[] = [];
+
+  for (const target of markers) {
+    const filter: MetadataFilter = {
+      type: 'comment',
+      marker: target,
+    };
+
+    if (projectId) {
+      filter.project_id = projectId;
+    }
+
+    // 検索エラーは「TODOなし」と区別できるよう呼び出し元に伝える
+    const { results, truncated } = await queryComments(
+      vectorStore,
+      collectionName,
+      dummyVector,
+      filter
+    );
+    if (truncated) {
+      truncatedMarkers.push(target);
+    }
+
+    for (const result of results) {
+      const metadata = result.metadata || {};
+
+      // フィルタ未対応のベクターストアでも正しく絞り込めるよう再確認
+      if (metadata['type'] !== 'comment' || metadata['marker'] !== target) {
+        continue;
+      }
+      if (projectId && metadata['project_id'] !== projectId) {
+        continue;
+      }
+
+      const filePath = metadata['file_path'] as string;
+      if (!filePath || (pathPattern && !filePath.includes(pathPattern))) {
+        continue;
+      }
+
+      todos.push({
+        marker: target,
+        content: (metadata['content'] as string) || '',
+        filePath,
+        language: (metadata['language'] as string) || 'unknown',
+        lineStart: metadata['line_start'] as number,
+        lineEnd: metadata['line_end'] as number,
+        associatedSymbol: metadata['associated_symbol'] as string | undefined,
+        projectId: metadata['project_id'] as string | undefined,
+      });
+    }
+  }
+
+  // ファイルパス・行番号順に並べる
+  todos.sort((a, b) => a.filePath.localeCompare(b.filePath) || a.lineStart - b.lineStart);
+
+  const countsByMarker: Record<string, number> = {};
+  for (const todo of todos) {
+    countsByMarker[todo.marker] = (countsByMarker[todo.marker] ?? 0) + 1;
+  }
+
+  return {
+    todos: todos.slice(0, limit),
+    totalTodos: todos.length,
+    countsByMarker,
+    truncated: truncatedMarkers.length > 0,
+    truncatedMarkers,
+  };
+}
+
+/**
+ * JSON Schemaに変換（MCPツール登録用）
+ */
+export function getInputSchemaJSON(): Record<string, any> {
+  return {
+    type: 'object',
+    properties: {
+      marker: {
+        type: 'string',
+        enum: Object.values(CommentMarker),
+        description: 'マーカー（例: "TODO", "FIXME"、未指定時は全マーカー）',
+      },
+      pathPattern: {
+        type: 'string',
+        description: 'ファイルパスに含まれる文字列（例: "src/services"）',
+      },
+      projectId: {
+        type: 'string',
+        description: 'プロジェクトID（オプション、未指定時は全プロジェクト）',
+      },
+      limit: {
+        type: 'number',
+        description: '返す件数（デフォルト: 100）',
+        default: 100,
+      },
+    },
+  };
+}
@@ -7,7 +7,16 @@
  */
 
 import { z } from 'zod';
-import { HybridSearchEngine } from '../services/hybrid-search-engine.js';
+import {
+  HybridSearchEngine,
+  type HybridSearchResult,
+  type SearchFilter,
+} from '../services/hybrid-search-engine.js';
+import {
+  parseSearchQuery,
+  matchesSearchConditions,
+  normalizeLanguage,
+} from '../services/search-query-parser.js';
 import type { EmbeddingEngine } from '../embedding/types.js';
 import * as fs from 'fs/promises';
 
@@ -20,13 +29,24 @@ export const TOOL_NAME = 'search_code';
  * ツール説明
  */
 export const TOOL_DESCRIPTION =
-  'セマンティックコード検索を実行します。BM25全文検索とベクトル検索を組み合わせたハイブリッド検索により、高精度な検索結果を提供します。';
+  'セマンティックコード検索を実行します。BM25全文検索とベクトル検索を組み合わせたハイブリッド検索により、高精度な検索結果を提供します。クエリでは lang:ts、path:src/storage/**、type:function、name:parse*、-除外語、"フレーズ"、OR が使用できます。';
+
+/**
+ * クエリの説明
+ */
+const QUERY_DESCRIPTION =
+  '検索クエリ（必須）。lang:/path:/type:/name:で絞り込み、-で除外、"..."でフレーズ、ORでいずれかを指定';
+
+/**
+ * 取得後の条件で絞り込む場合の取得件数の倍率（条件を満たす結果が不足する場合は再検索ごとに掛ける）
+ */
+const POST_FILTER_CANDIDATE_FACTOR = 4;
 
 /**
  * 入力パラメータスキーマ（Zod）
  */
 export const InputSchema = z.object({
-  query: z.string().describe('検索クエリ（必須）'),
+  query: z.string().describe(QUERY_DESCRIPTION),
   projectId: z
     .string()
     .optional()
@@ -40,6 +60,11 @@ export const InputSchema = z.object({
     .optional()
     .describe('言語フィルタ（例: ["TypeScript", "Python"]）'),
   topK: z.number().int().positive().optional().default(10).describe('返す結果数（デフォルト: 10）'),
+  fusion: z
+    .enum(['linear', 'rrf', 'zscore'])
+    .optional()
+    .describe('スコア統合方式（オプション、未指定時は設定ファイルの値）'),
+  rrfK: z.number().positive().optional().describe('RRFの定数k（オプション、fusionがrrfの場合）'),
 });
 
 /**
@@ -85,10 +110,19 @@ export async function handleSearchCode(
   // パラメータバリデーション
   const validatedInput = InputSchema.parse(input);
 
-  const { query, projectId, fileTypes, languages, topK } = validatedInput;
+  const { query, projectId, fileTypes, languages, topK, fusion, rrfK } = validatedInput;
+
+  // クエリ構文を解析（パラメータとクエリで指定された言語は両方を満たす必要がある）
+  const parsedQuery = parseSearchQuery(query);
+  const searchLanguages = intersectLanguages(languages, parsedQuery.filter.languages);
+  const { conditions } = parsedQuery;
+
+  // 検索語がなくフィールド指定のみのクエリ（lang:rust type:function等）はフィルタのみで検索する
+  const filterOnly = !parsedQuery.semanticQuery;
+  const hasFields = Object.keys(parsedQuery.filter).length > 0 || conditions.length > 0;
 
-  // 空クエリの場合は空結果を返す
-  if (!query.trim()) {
+  // 空クエリ（検索語もフィールド指定もない）や、言語の指定が矛盾する場合は空結果を返す
+  if ((filterOnly && !hasFields) || searchLanguages?.length === 0) {
     return {
       results: [],
       totalResults: 0,
@@ -97,38 +131,58 @@ export async function handleSearchCode(
   }
 
   try {
-    // クエリを埋め込みベクトルに変換
-    const queryVector = await embeddingEngine.embed(query);
-
-    // ハイブリッド検索を実行
-    const searchFilter = {
+    const searchFilter: SearchFilter = {
+      ...parsedQuery.filter,
       fileTypes: fileTypes?.map((ft) => ft.replace(/^\./, '')), // 先頭の"."を除去
-      languages,
-      pathPattern: projectId,
+      languages: searchLanguages,
+      projectId,
     };
 
-    const hybridResults = await hybridSearchEngine.search(
-      collectionName,
-      query,
-      queryVector,
-      topK,
-      searchFilter
-    );
+    // 演算子・フィールド指定を除いた自然文を埋め込みベクトルに変換
+    const queryVector = filterOnly
+      ? undefined
+      : await embeddingEngine.embed(parsedQuery.semanticQuery);
+    const fetchCandidates = (count: number): Promise<HybridSearchResult[]> =>
+      queryVector
+        ? hybridSearchEngine.search(
+            collectionName,
+            parsedQuery.keywordQuery,
+            queryVector,
+            count,
+            searchFilter,
+            { strategy: fusion, rrfK }
+          )
+        : hybridSearchEngine.searchByFilter(searchFilter, count, parsedQuery.keywordQuery);
+
+    // globの判定は取得後に行うため、条件がある場合は多めに取得し、
+    // 条件を満たす結果がtopK件に満たなければ候補がなくなるまで取得件数を増やして再検索する
+    let candidateCount = conditions.length > 0 ? topK * POST_FILTER_CANDIDATE_FACTOR : topK;
+    let matched: HybridSearchResult[];
+    for (;;) {
+      const candidates = await fetchCandidates(candidateCount);
+      matched = candidates.filter((result) => matchesSearchConditions(conditions, result));
+      if (matched.length >= topK || candidates.length < candidateCount) {
+        break;
+      }
+      candidateCount *= POST_FILTER_CANDIDATE_FACTOR;
+    }
+    const hybridResults = matched.slice(0, topK);
 
     // 検索結果をフォーマット
     const formattedResults = await Promise.all(
       hybridResults.map(async (result) => {
         const metadata = result.metadata || {};
+        const filePath = (metadata['file_path'] as string | undefined) ?? '';
 
         // スニペットを取得（前後3行を含む）
         const snippet = await getCodeSnippet(
-          result.id,
+          filePath,
           metadata['lineStart'] as number | undefined,
           metadata['lineEnd'] as number | undefined
         );
 
         return {
-          filePath: result.id.split(':')[0] || result.id,
+          filePath: filePath || result.id,
           language: (metadata['language'] as string) || 'unknown',
           snippet,
           score: result.score,
@@ -158,22 +212,34 @@ export async function handleSearchCode(
   }
 }
 
+/**
+ * パラメータとクエリで指定された言語の共通部分を取得（略称・大文字小文字を正規化して比較）
+ */
+function intersectLanguages(
+  fromParams: string[] | undefined,
+  fromQuery: string[] | undefined
+): string[] | undefined {
+  if (!fromParams || !fromQuery) {
+    return fromParams ?? fromQuery;
+  }
+  const allowed = new Set(fromQuery);
+  return fromParams.filter((language) => allowed.has(normalizeLanguage(language)));
+}
+
 /**
  * コードスニペットを取得（前後3行を含む）
  *
- * @param documentId ドキュメントID（filePath:lineStart形式）
+ * @param filePath ファイルパス
  * @param lineStart 開始行番号
  * @param lineEnd 終了行番号
  * @returns コードスニペット
  */
 async function getCodeSnippet(
-  documentId: string,
+  filePath: string,
   lineStart?: number,
   lineEnd?: number
 ): Promise<string> {
   try {
-    const filePath = documentId.split(':')[0];
-
     // ファイルパスが取得できない場合は空文字列を返す
     if (!filePath) {
       return '';
@@ -215,7 +281,7 @@ export function getInputSchemaJSON(): Record<string, any> {
     properties: {
       query: {
         type: 'string',
-        description: '検索クエリ（必須）',
+        description: QUERY_DESCRIPTION,
       },
       projectId: {
         type: 'string',
@@ -236,6 +302,15 @@ export function getInputSchemaJSON(): Record<string, any> {
         description: '返す結果数（デフォルト: 10）',
         default: 10,
       },
+      fusion: {
+        type: 'string',
+        enum: ['linear', 'rrf', 'zscore'],
+        description: 'スコア統合方式（オプション、未指定時は設定ファイルの値）',
+      },
+      rrfK: {
+        type: 'number',
+        description: 'RRFの定数k（オプション、fusionがrrfの場合）',
+      },
     },
     required: ['query'],
   };
@@ -3,14 +3,16 @@
  */
 
 import { EventEmitter } from 'events';
+import type { Stats } from 'fs';
 import chokidar, { FSWatcher } from 'chokidar';
-import { FileWatcherEvent, FileWatcherOptions, IFileWatcher } from './types.js';
+import { FileWatcherEvent, FileWatcherOptions, IFileWatcher, IgnoreMatcher } from './types.js';
+import { createIgnoreMatcher } from './ignore-matcher.js';
 import { logger } from '../utils/logger.js';
 
 /**
  * Default ignore patterns
  */
-const DEFAULT_IGNORE_PATTERNS = [
+export const DEFAULT_IGNORE_PATTERNS = [
   'node_modules/**',
   '.git/**',
   'dist/**',
@@ -31,6 +33,7 @@ const DEFAULT_DEBOUNCE_MS = 500;
 export class FileWatcher extends EventEmitter implements IFileWatcher {
   private options: Required<FileWatcherOptions>;
   private watcher: FSWatcher | null = null;
+  private isIgnored: IgnoreMatcher;
   private debounceTimers: Map<string, NodeJS.Timeout> = new Map();
   private watching = false;
 
@@ -52,6 +55,7 @@ export class FileWatcher extends EventEmitter implements IFileWatcher {
       watchUnlink: options.watchUnlink ?? true,
       ignoreInitial: options.ignoreInitial ?? true,
     };
+    this.isIgnored = createIgnoreMatcher(this.options.rootPath, this.options.ignorePatterns);
 
     logger.debug('FileWatcher created', {
       rootPath: this.options.rootPath,
@@ -74,7 +78,8 @@ export class FileWatcher extends EventEmitter implements IFileWatcher {
     try {
       // Create chokidar watcher
       this.watcher = chokidar.watch(this.options.rootPath, {
-        ignored: this.options.ignorePatterns,
+        ignored: (filePath: string, stats?: Stats) =>
+          this.isIgnored(filePath, stats?.isDirectory()),
         persistent: true,
         ignoreInitial: this.options.ignoreInitial,
         awaitWriteFinish: {
@@ -0,0 +1,41 @@
+/**
+ * Ignore Pattern Matcher
+ */
+
+import path from 'path';
+import ignore from 'ignore';
+import type { IgnoreMatcher } from './types.js';
+
+/**
+ * Create a matcher for ignore patterns
+ *
+ * Patterns are interpreted in .gitignore syntax by `ignore`, the same way FileScanner
+ * interprets exclude patterns, and are matched against paths relative to the root path.
+ * Chokidar v4 no longer supports globs, so the watcher passes this matcher as a function.
+ *
+ * @param rootPath Root path the patterns are relative to
+ * @param patterns Ignore patterns
+ * @returns Matcher that returns true for ignored paths
+ */
+export function createIgnoreMatcher(rootPath: string, patterns: string[]): IgnoreMatcher {
+  const root = path.resolve(rootPath);
+  const matcher = ignore().add(patterns);
+
+  return (filePath: string, isDirectory = false): boolean => {
+    const relativePath = path.relative(root, path.resolve(root, filePath));
+
+    // The root itself and paths outside the root are never ignored
+    // (`ignore` rejects empty and non-relative paths)
+    if (
+      relativePath === '' ||
+      relativePath === '..' ||
+      relativePath.startsWith(`..${path.sep}`) ||
+      path.isAbsolute(relativePath)
+    ) {
+      return false;
+    }
+
+    const normalizedPath = relativePath.split(path.sep).join('/');
+    return matcher.ignores(isDirectory ? `${normalizedPath}/` : normalizedPath);
+  };
+}
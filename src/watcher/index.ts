@@ -3,5 +3,11 @@
  */
 
 export { FileWatcher } from './file-watcher.js';
+export { createIgnoreMatcher } from './ignore-matcher.js';
 export { FileWatcherEvent } from './types.js';
-export type { FileWatcherOptions, FileWatcherEventListeners, IFileWatcher } from './types.js';
+export type {
+  FileWatcherOptions,
+  FileWatcherEventListeners,
+  IFileWatcher,
+  IgnoreMatcher,
+} from './types.js';
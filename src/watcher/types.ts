@@ -23,7 +23,7 @@ export interface FileWatcherOptions {
   rootPath: string;
 
   /**
-   * Glob patterns to ignore
+   * Patterns to ignore (.gitignore syntax, relative to rootPath)
    * @default ['node_modules/**', '.git/**', 'dist/**', 'build/**']
    */
   ignorePatterns?: string[];
@@ -59,6 +59,11 @@ export interface FileWatcherOptions {
   ignoreInitial?: boolean;
 }
 
+/**
+ * Returns true if the path matches an ignore pattern
+ */
+export type IgnoreMatcher = (filePath: string, isDirectory?: boolean) => boolean;
+
 /**
  * File watcher event listeners
  */
@@ -0,0 +1,238 @@
+/**
+ * Chroma REST API（v2）のインプロセス代替サーバー（テスト用）
+ *
+ * ChromaPluginが使用するエンドポイントのみをメモリ上で実装します。
+ */
+
+import * as http from 'http';
+import type { AddressInfo } from 'net';
+
+type Metadata = Record<string, string | number | boolean>;
+
+interface StoredRecord {
+  embedding: number[];
+  metadata: Metadata | null;
+}
+
+interface StoredCollection {
+  id: string;
+  name: string;
+  metadata: Metadata;
+  records: Map<string, StoredRecord>;
+}
+
+type Where = Record<string, any>;
+
+const COLLECTIONS_PATH = '/api/v2/tenants/default_tenant/databases/default_database/collections';
+
+export interface ChromaStandIn {
+  /** サーバーのURL */
+  url: string;
+  /** 受信したリクエストのボディ（メソッドとパスごと） */
+  requests: Array<{ method: string; path: string; body: any }>;
+  /** 次のn回のリクエストに503を返す */
+  failNext(count: number): void;
+  /** サーバーを停止 */
+  close(): Promise<void>;
+}
+
+/**
+ * where条件の形式を検証（Chromaと同様に不正な形式はエラーとする）
+ *
+ * 各階層はちょうど1つのキー（`$and`またはメタデータのキー）を持ち、`$and`は2件以上の条件、
+ * メタデータのキーはちょうど1つの演算子を持つ必要があります。
+ * 代替サーバーが評価できない演算子（`$eq`・`$in`以外）もエラーとします。
+ *
+ * @returns エラーメッセージ（正しい形式の場合はundefined）
+ */
+function validateWhere(where: unknown): string | undefined {
+  if (typeof where !== 'object' || where === null || Array.isArray(where)) {
+    return `Expected where to be a dict, got ${JSON.stringify(where)}`;
+  }
+  const entries = Object.entries(where);
+  if (entries.length !== 1) {
+    return `Expected where to have exactly one operator, got ${JSON.stringify(where)}`;
+  }
+  const [key, condition] = entries[0];
+  if (key === '$and') {
+    if (!Array.isArray(condition) || condition.length < 2) {
+      return `Expected where value for $and to be a list with at least two where expressions`;
+    }
+    return condition.map(validateWhere).find((error) => error !== undefined);
+  }
+  if (key.startsWith('$')) {
+    return `Unsupported where operator: ${key}`;
+  }
+  if (typeof condition !== 'object' || condition === null) {
+    return `Expected operator expression for ${key}, got ${JSON.stringify(condition)}`;
+  }
+  const operators = Object.keys(condition);
+  if (operators.length !== 1 || !['$eq', '$in'].includes(operators[0])) {
+    return `Expected exactly one of $eq or $in for ${key}, got ${JSON.stringify(condition)}`;
+  }
+  if (operators[0] === '$in' && (!Array.isArray(condition.$in) || condition.$in.length === 0)) {
+    return `Expected where value for $in to be a non-empty list`;
+  }
+  return undefined;
+}
+
+function matches(metadata: Metadata | null, where?: Where): boolean {
+  if (!where) {
+    return true;
+  }
+  if (where['$and']) {
+    return (where['$and'] as Where[]).every((w) => matches(metadata, w));
+  }
+  return Object.entries(where).every(([key, condition]) => {
+    const value = metadata?.[key];
+    if ('$in' in condition) {
+      return condition.$in.includes(value);
+    }
+    return value === condition.$eq;
+  });
+}
+
+function cosineDistance(a: number[], b: number[]): number {
+  const dot = a.reduce((sum, x, i) => sum + x * b[i], 0);
+  const norm = Math.sqrt(a.reduce((s, x) => s + x * x, 0) * b.reduce((s, x) => s + x * x, 0));
+  return norm === 0 ? 1 : 1 - dot / norm;
+}
+
+/**
+ * 代替サーバーを起動
+ */
+export async function startChromaStandIn(): Promise<ChromaStandIn> {
+  const collections = new Map<string, StoredCollection>();
+  const requests: ChromaStandIn['requests'] = [];
+  let failures = 0;
+  let nextId = 1;
+
+  const findById = (id: string): StoredCollection | undefined =>
+    Array.from(collections.values()).find((c) => c.id === id);
+
+  const handle = (method: string, path: string, body: any): [number, unknown] => {
+    if (method === 'GET' && path === '/api/v2/version') {
+      return [200, '1.0.0'];
+    }
+
+    if (!path.startsWith(COLLECTIONS_PATH)) {
+      return [404, { error: 'NotFoundError', message: `Unknown path: ${path}` }];
+    }
+    const [encodedTarget, action] = path.slice(COLLECTIONS_PATH.length).split('/').slice(1);
+    const target = encodedTarget ? decodeURIComponent(encodedTarget) : undefined;
+
+    if (!target) {
+      if (collections.has(body.name)) {
+        return [409, { error: 'UniqueConstraintError', message: `Collection already exists` }];
+      }
+      const collection = {
+        id: `c${nextId++}`,
+        name: body.name,
+        metadata: body.metadata,
+        records: new Map(),
+      };
+      collections.set(body.name, collection);
+      return [200, { id: collection.id, name: collection.name, metadata: collection.metadata }];
+    }
+
+    if (!action) {
+      const collection = collections.get(target);
+      if (!collection) {
+        return [404, { error: 'NotFoundError', message: `Collection [${target}] does not exist` }];
+      }
+      if (method === 'DELETE') {
+        collections.delete(target);
+        return [200, {}];
+      }
+      return [200, { id: collection.id, name: collection.name, metadata: collection.metadata }];
+    }
+
+    const collection = findById(target);
+    if (!collection) {
+      return [404, { error: 'NotFoundError', message: `Collection [${target}] does not exist` }];
+    }
+
+    // where条件を送るリクエストは形式を検証
+    if (body?.where !== undefined) {
+      const error = validateWhere(body.where);
+      if (error) {
+        return [400, { error: 'InvalidArgumentError', message: error }];
+      }
+    }
+
+    switch (action) {
+      case 'upsert':
+        (body.ids as string[]).forEach((id, i) => {
+          const metadata = body.metadatas[i];
+          collection.records.set(id, { embedding: body.embeddings[i], metadata });
+        });
+        return [200, {}];
+      case 'query': {
+        const [query] = body.query_embeddings as number[][];
+        const hits = Array.from(collection.records)
+          .filter(([, r]) => matches(r.metadata, body.where))
+          .map(([id, r]) => ({ id, distance: cosineDistance(query, r.embedding), r }))
+          .sort((a, b) => a.distance - b.distance)
+          .slice(0, body.n_results);
+        return [
+          200,
+          {
+            ids: [hits.map((h) => h.id)],
+            distances: [hits.map((h) => h.distance)],
+            metadatas: [hits.map((h) => h.r.metadata)],
+          },
+        ];
+      }
+      case 'get': {
+        const all = Array.from(collection.records)
+          .filter(([, r]) => matches(r.metadata, body.where))
+          .slice(body.offset ?? 0, (body.offset ?? 0) + (body.limit ?? Infinity));
+        return [200, { ids: all.map(([id]) => id), metadatas: all.map(([, r]) => r.metadata) }];
+      }
+      case 'delete':
+        for (const [id, record] of Array.from(collection.records)) {
+          if (body.ids ? body.ids.includes(id) : matches(record.metadata, body.where)) {
+            collection.records.delete(id);
+          }
+        }
+        return [200, {}];
+      case 'count':
+        return [200, collection.records.size];
+      default:
+        return [404, { error: 'NotFoundError', message: `Unknown action: ${action}` }];
+    }
+  };
+
+  const server = http.createServer((req, res) => {
+    let raw = '';
+    req.on('data', (chunk) => (raw += chunk));
+    req.on('end', () => {
+      const path = (req.url ?? '/').split('?')[0];
+      const body = raw ? JSON.parse(raw) : undefined;
+      requests.push({ method: req.method ?? 'GET', path, body });
+
+      if (failures > 0) {
+        failures--;
+        res.writeHead(503, { 'Content-Type': 'text/plain' });
+        res.end('Service Unavailable');
+        return;
+      }
+
+      const [status, response] = handle(req.method ?? 'GET', path, body);
+      res.writeHead(status, { 'Content-Type': 'application/json' });
+      res.end(JSON.stringify(response));
+    });
+  });
+
+  await new Promise<void>((resolve) => server.listen(0, '127.0.0.1', resolve));
+  const { port } = server.address() as AddressInfo;
+
+  return {
+    url: `http://127.0.0.1:${port}`,
+    requests,
+    failNext(count: number) {
+      failures = count;
+    },
+    close: () => new Promise<void>((resolve) => server.close(() => resolve())),
+  };
+}
@@ -2,10 +2,21 @@
  * Mock BM25 Engine for Testing
  */
 
-import type { SearchResult, DocumentStats } from '../../src/storage/bm25-engine';
+import type {
+  BM25Document,
+  BM25DocumentAttributes,
+  BM25SearchFilter,
+  SearchResult,
+  DocumentStats,
+  FileManifestEntry,
+  ProjectRecord,
+} from '../../src/storage/bm25-engine';
 
 export class MockBM25Engine {
   private documents: Map<string, string> = new Map();
+  private documentAttributes: Map<string, BM25DocumentAttributes> = new Map();
+  private manifest: Map<string, FileManifestEntry> = new Map();
+  private projects: Map<string, ProjectRecord> = new Map();
   private initialized = false;
 
   async initialize(): Promise<void> {
@@ -16,25 +27,94 @@ export class MockBM25Engine {
     this.documents.set(documentId, text);
   }
 
-  async indexDocument(documentId: string, text: string): Promise<void> {
+  async indexDocument(
+    documentId: string,
+    text: string,
+    attributes: BM25DocumentAttributes = {}
+  ): Promise<void> {
     this.documents.set(documentId, text);
+    this.documentAttributes.set(documentId, attributes);
+  }
+
+  async indexDocuments(documents: BM25Document[]): Promise<void> {
+    for (const { documentId, content, attributes } of documents) {
+      await this.indexDocument(documentId, content, attributes);
+    }
   }
 
   async deleteDocument(documentId: string): Promise<void> {
     this.documents.delete(documentId);
   }
 
+  async deleteDocuments(documentIds: string[]): Promise<number> {
+    let deleted = 0;
+    for (const documentId of documentIds) {
+      if (this.documents.delete(documentId)) {
+        deleted++;
+      }
+    }
+    return deleted;
+  }
+
   async removeDocument(documentId: string): Promise<void> {
     this.documents.delete(documentId);
   }
 
-  async search(query: string, topK: number = 10): Promise<SearchResult[]> {
+  async deleteByPrefix(prefix: string): Promise<number> {
+    let deleted = 0;
+    for (const documentId of Array.from(this.documents.keys())) {
+      if (documentId.startsWith(prefix)) {
+        this.documents.delete(documentId);
+        deleted++;
+      }
+    }
+    return deleted;
+  }
+
+  async deleteByProject(projectId: string): Promise<number> {
+    let deleted = 0;
+    for (const [documentId, attributes] of Array.from(this.documentAttributes.entries())) {
+      if (attributes.projectId === projectId && this.documents.delete(documentId)) {
+        this.documentAttributes.delete(documentId);
+        deleted++;
+      }
+    }
+    return deleted;
+  }
+
+  async deleteByFile(projectId: string, filePath: string): Promise<number> {
+    let deleted = 0;
+    for (const [documentId, attributes] of Array.from(this.documentAttributes.entries())) {
+      if (
+        attributes.projectId === projectId &&
+        attributes.filePath === filePath &&
+        this.documents.delete(documentId)
+      ) {
+        this.documentAttributes.delete(documentId);
+        deleted++;
+      }
+    }
+    return deleted;
+  }
+
+  async search(
+    query: string,
+    topK: number = 10,
+    filter?: BM25SearchFilter
+  ): Promise<SearchResult[]> {
     // 簡易的な検索結果を返す
     const results: SearchResult[] = [];
     let index = 0;
 
     for (const [documentId] of this.documents) {
       if (index >= topK) break;
+      const attributes = this.documentAttributes.get(documentId) ?? {};
+      if (filter?.projectId !== undefined && attributes.projectId !== filter.projectId) {
+        continue;
+      }
+      if (filter?.types && !filter.types.includes(attributes.symbolType ?? '')) {
+        continue;
+      }
       results.push({
         documentId,
         score: 1.0 - index * 0.1, // スコアを徐々に下げる
@@ -45,19 +125,90 @@ export class MockBM25Engine {
     return results;
   }
 
-  async getStats(): Promise<DocumentStats> {
+  async findDocuments(filter: BM25SearchFilter, limit: number): Promise<string[]> {
+    return Array.from(this.documents.keys())
+      .filter((documentId) => {
+        const attributes = this.documentAttributes.get(documentId) ?? {};
+        if (filter.projectId !== undefined && attributes.projectId !== filter.projectId) {
+          return false;
+        }
+        return !filter.types || filter.types.includes(attributes.symbolType ?? '');
+      })
+      .sort()
+      .slice(0, limit);
+  }
+
+  async filterDocuments(documentIds: string[], _query: string): Promise<string[]> {
+    // 条件による絞り込みは行わない
+    return documentIds;
+  }
+
+  async getDocumentAttributes(
+    documentIds: string[]
+  ): Promise<Map<string, BM25DocumentAttributes>> {
+    const result = new Map<string, BM25DocumentAttributes>();
+    for (const documentId of documentIds) {
+      const attributes = this.documentAttributes.get(documentId);
+      if (this.documents.has(documentId) && attributes) {
+        result.set(documentId, attributes);
+      }
+    }
+    return result;
+  }
+
+    async getStats(): Promise<DocumentStats> {
     return {
       totalDocuments: this.documents.size,
       averageDocumentLength: 100, // 仮の値
     };
   }
 
+  async getFileManifest(projectId: string): Promise<FileManifestEntry[]> {
+    return Array.from(this.manifest.values()).filter((entry) => entry.projectId === projectId);
+  }
+
+  async upsertFileManifest(entry: FileManifestEntry): Promise<void> {
+    this.manifest.set(`${entry.projectId}\0${entry.filePath}`, { ...entry, ids: [...entry.ids] });
+  }
+
+  async deleteFileManifest(projectId: string, filePath: string): Promise<void> {
+    this.manifest.delete(`${projectId}\0${filePath}`);
+  }
+
+  async clearFileManifest(projectId: string): Promise<void> {
+    for (const [key, entry] of Array.from(this.manifest.entries())) {
+      if (entry.projectId === projectId) {
+        this.manifest.delete(key);
+      }
+    }
+  }
+
   async close(): Promise<void> {
     this.documents.clear();
     this.initialized = false;
   }
 
+  async getProjects(): Promise<ProjectRecord[]> {
+    return Array.from(this.projects.values()).map((record) => ({ ...record }));
+  }
+
+  async upsertProject(record: ProjectRecord): Promise<void> {
+    this.projects.set(record.projectId, { ...record });
+  }
+
+  async deleteProject(projectId: string): Promise<void> {
+    this.projects.delete(projectId);
+  }
+
+  async clearIndex(): Promise<void> {
+    this.documents.clear();
+    this.manifest.clear();
+    this.projects.clear();
+  }
+
   async clearAll(): Promise<void> {
     this.documents.clear();
+    this.manifest.clear();
+    this.projects.clear();
   }
 }
@@ -0,0 +1,365 @@
+/**
+ * Milvus SDK（@zilliz/milvus2-sdk-node）のモック（テスト用）
+ *
+ * MilvusPluginが使用するクライアントのメソッドのみをメモリ上で実装します。
+ * スキーマの検証（VarCharの最大長、ベクトルの次元数）、フィルタ式（`==`・`in`・`like`・`&&`）、
+ * L2距離（二乗）による検索を再現しますが、整合性レベルは再現しません。
+ * コンパクションは行わないため、統計情報の行数には削除済みの行が含まれます。
+ * サーバー側のエラーはステータスを返さず例外として送出します。
+ *
+ * @example
+ * ```typescript
+ * jest.mock('@zilliz/milvus2-sdk-node', () =>
+ *   jest.requireActual('../__mocks__/mock-milvus-client')
+ * );
+ * ```
+ */
+
+export enum DataType {
+  VarChar = 21,
+  JSON = 23,
+  FloatVector = 101,
+}
+
+interface FieldSchema {
+  name: string;
+  data_type: DataType;
+  is_primary_key?: boolean;
+  dim?: number;
+  max_length?: number;
+}
+
+interface StoredCollection {
+  fields: FieldSchema[];
+  metricType: string;
+  rows: Map<string, Record<string, unknown>>;
+  /** 削除済みの行数（コンパクションを再現しないため累積する） */
+  deletedRows: number;
+}
+
+type Literal = string | number | boolean;
+
+type Condition =
+  | { field: string; key?: string; op: '=='; value: Literal }
+  | { field: string; key?: string; op: 'in'; values: Literal[] }
+  | { field: string; key?: string; op: 'like'; pattern: RegExp };
+
+/**
+ * フィルタ式を条件の配列に変換
+ *
+ * MilvusPluginが生成する式（`field == "v"`、`metadata["key"] in [...]`、`field like "p"`を
+ * `&&`で結合したもの）のみを解釈し、それ以外は構文エラーとします。
+ */
+function parseExpression(expr: string): Condition[] {
+  let position = 0;
+
+  const fail = (): never => {
+    throw new Error(`cannot parse expression: ${expr}`);
+  };
+  const skipSpaces = (): void => {
+    while (expr[position] === ' ') {
+      position++;
+    }
+  };
+  const consume = (token: string): boolean => {
+    skipSpaces();
+    if (expr.startsWith(token, position)) {
+      position += token.length;
+      return true;
+    }
+    return false;
+  };
+  const readString = (): string => {
+    skipSpaces();
+    if (expr[position] !== '"') {
+      fail();
+    }
+    let value = '';
+    position++;
+    while (position < expr.length && expr[position] !== '"') {
+      if (expr[position] === '\\') {
+        position++;
+      }
+      value += expr[position++];
+    }
+    if (expr[position] !== '"') {
+      fail();
+    }
+    position++;
+    return value;
+  };
+  const readLiteral = (): Literal => {
+    skipSpaces();
+    if (expr[position] === '"') {
+      return readString();
+    }
+    const match = /^(true|false|-?\d+(?:\.\d+)?)/.exec(expr.slice(position));
+    if (!match) {
+      return fail();
+    }
+    position += match[0].length;
+    return match[0] === 'true' ? true : match[0] === 'false' ? false : Number(match[0]);
+  };
+  const readCondition = (): Condition => {
+    skipSpaces();
+    const name = /^[A-Za-z_][A-Za-z0-9_]*/.exec(expr.slice(position));
+    if (!name) {
+      return fail();
+    }
+    position += name[0].length;
+    let key: string | undefined;
+    if (consume('[')) {
+      key = readString();
+      if (!consume(']')) {
+        fail();
+      }
+    }
+    const field = name[0];
+
+    if (consume('==')) {
+      return { field, key, op: '==', value: readLiteral() };
+    }
+    if (consume('in ')) {
+      if (!consume('[')) {
+        fail();
+      }
+      const values: Literal[] = [];
+      while (!consume(']')) {
+        if (values.length > 0 && !consume(',')) {
+          fail();
+        }
+        values.push(readLiteral());
+      }
+      return { field, key, op: 'in', values };
+    }
+    if (consume('like ')) {
+      // `%`は任意の文字列、`_`は任意の1文字
+      const source = readString()
+        .split('')
+        .map((char) =>
+          char === '%' ? '.*' : char === '_' ? '.' : char.replace(/[.*+?^${}()|[\]\\]/g, '\\$&')
+        )
+        .join('');
+      return { field, key, op: 'like', pattern: new RegExp(`^${source}$`, 's') };
+    }
+    return fail();
+  };
+
+  const conditions = [readCondition()];
+  while (consume('&&')) {
+    conditions.push(readCondition());
+  }
+  skipSpaces();
+  if (position !== expr.length) {
+    fail();
+  }
+  return conditions;
+}
+
+/**
+ * 行がフィルタ式の条件をすべて満たすか判定
+ */
+function matchesExpression(row: Record<string, unknown>, conditions: Condition[]): boolean {
+  return conditions.every((condition) => {
+    const fieldValue = row[condition.field];
+    const value =
+      condition.key === undefined
+        ? fieldValue
+        : (fieldValue as Record<string, unknown> | undefined)?.[condition.key];
+    switch (condition.op) {
+      case '==':
+        return value === condition.value;
+      case 'in':
+        return condition.values.includes(value as Literal);
+      case 'like':
+        return typeof value === 'string' && condition.pattern.test(value);
+    }
+  });
+}
+
+/**
+ * L2距離（二乗）
+ */
+function squaredL2(a: number[], b: number[]): number {
+  return a.reduce((sum, x, i) => sum + (x - b[i]) ** 2, 0);
+}
+
+/**
+ * MilvusClientのモック
+ */
+export class MilvusClient {
+  private collections: Map<string, StoredCollection> = new Map();
+
+  constructor(readonly config: Record<string, unknown>) {}
+
+  private getCollection(name: string): StoredCollection {
+    const collection = this.collections.get(name);
+    if (!collection) {
+      throw new Error(`can't find collection: ${name}`);
+    }
+    return collection;
+  }
+
+  async getVersion(): Promise<{ version: string }> {
+    return { version: 'v2.4.0-mock' };
+  }
+
+  async hasCollection(params: { collection_name: string }): Promise<{ value: boolean }> {
+    return { value: this.collections.has(params.collection_name) };
+  }
+
+  async createCollection(params: { collection_name: string; fields: FieldSchema[] }) {
+    if (this.collections.has(params.collection_name)) {
+      throw new Error(`collection already exists: ${params.collection_name}`);
+    }
+    this.collections.set(params.collection_name, {
+      fields: params.fields,
+      metricType: 'L2',
+      rows: new Map(),
+      deletedRows: 0,
+    });
+    return { error_code: 'Success' };
+  }
+
+  async createIndex(params: { collection_name: string; metric_type: string }) {
+    this.getCollection(params.collection_name).metricType = params.metric_type;
+    return { error_code: 'Success' };
+  }
+
+  async loadCollection(params: { collection_name: string }) {
+    this.getCollection(params.collection_name);
+    return { error_code: 'Success' };
+  }
+
+  async dropCollection(params: { collection_name: string }) {
+    this.getCollection(params.collection_name);
+    this.collections.delete(params.collection_name);
+    return { error_code: 'Success' };
+  }
+
+  /**
+   * SDKと同様に、型パラメータ（dim、max_length）を文字列として返す
+   */
+  async describeCollection(params: { collection_name: string }) {
+    const { fields } = this.getCollection(params.collection_name);
+    return {
+      schema: {
+        fields: fields.map((field) => ({
+          name: field.name,
+          data_type: DataType[field.data_type],
+          dataType: field.data_type,
+          is_primary_key: field.is_primary_key ?? false,
+          ...(field.dim !== undefined ? { dim: String(field.dim) } : {}),
+          ...(field.max_length !== undefined ? { max_length: String(field.max_length) } : {}),
+        })),
+      },
+    };
+  }
+
+  async insert(params: { collection_name: string; data: Array<Record<string, unknown>> }) {
+    const collection = this.getCollection(params.collection_name);
+
+    // 1行でも不正な場合は何も書き込まない
+    for (const row of params.data) {
+      for (const field of collection.fields) {
+        const value = row[field.name];
+        if (value === undefined) {
+          throw new Error(`Insert fail: missing field ${field.name}`);
+        }
+        if (field.data_type === DataType.FloatVector && (value as number[]).length !== field.dim) {
+          throw new Error(
+            `the dim (${(value as number[]).length}) of field data(${field.name}) ` +
+              `is not equal to schema dim (${field.dim})`
+          );
+        }
+        if (
+          field.data_type === DataType.VarChar &&
+          Buffer.byteLength(String(value), 'utf8') > field.max_length!
+        ) {
+          throw new Error(
+            `length of varchar field ${field.name} exceeds max length, ` +
+              `length: ${Buffer.byteLength(String(value), 'utf8')}, max length: ${field.max_length}`
+          );
+        }
+      }
+    }
+
+    for (const row of params.data) {
+      collection.rows.set(row['id'] as string, JSON.parse(JSON.stringify(row)));
+    }
+    return { status: { error_code: 'Success' }, insert_cnt: String(params.data.length) };
+  }
+
+  async delete(params: { collection_name: string; expr?: string; filter?: string }) {
+    const collection = this.getCollection(params.collection_name);
+    const conditions = parseExpression(params.expr ?? params.filter ?? '');
+
+    let deleted = 0;
+    for (const [id, row] of Array.from(collection.rows)) {
+      if (matchesExpression(row, conditions)) {
+        collection.rows.delete(id);
+        deleted++;
+      }
+    }
+    collection.deletedRows += deleted;
+    return { status: { error_code: 'Success' }, delete_cnt: String(deleted) };
+  }
+
+  async search(params: {
+    collection_name: string;
+    data: number[][];
+    limit: number;
+    output_fields?: string[];
+    filter?: string;
+  }) {
+    const collection = this.getCollection(params.collection_name);
+    if (collection.metricType !== 'L2') {
+      throw new Error(`metric type ${collection.metricType} is not supported by the mock`);
+    }
+    const conditions = params.filter ? parseExpression(params.filter) : [];
+    const [vector] = params.data;
+    const dim = collection.fields.find((field) => field.data_type === DataType.FloatVector)?.dim;
+    if (vector.length !== dim) {
+      throw new Error(`vector dimension mismatch, expected vector size(byte) ${dim! * 4}`);
+    }
+
+    const results = Array.from(collection.rows.values())
+      .filter((row) => matchesExpression(row, conditions))
+      .map((row) => ({ row, score: squaredL2(vector, row['vector'] as number[]) }))
+      .sort((a, b) => a.score - b.score)
+      .slice(0, params.limit)
+      .map(({ row, score }) => {
+        const result: Record<string, unknown> = { score };
+        for (const field of params.output_fields ?? ['id']) {
+          result[field] = JSON.parse(JSON.stringify(row[field]));
+        }
+        return result;
+      });
+    return { status: { error_code: 'Success' }, results };
+  }
+
+  /**
+   * 削除済みの行を含まない件数（`count(*)`）
+   */
+  async count(params: { collection_name: string; expr?: string }) {
+    const collection = this.getCollection(params.collection_name);
+    const conditions = params.expr ? parseExpression(params.expr) : [];
+    const rows = Array.from(collection.rows.values());
+    return {
+      status: { error_code: 'Success' },
+      data: rows.filter((row) => matchesExpression(row, conditions)).length,
+    };
+  }
+
+  /**
+   * Milvusと同様に、削除済みの行もコンパクションまで件数に含まれる
+   */
+  async getCollectionStatistics(params: { collection_name: string }) {
+    const collection = this.getCollection(params.collection_name);
+    return {
+      status: { error_code: 'Success' },
+      stats: [],
+      data: { row_count: String(collection.rows.size + collection.deletedRows) },
+    };
+  }
+}
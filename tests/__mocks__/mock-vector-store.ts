@@ -8,7 +8,9 @@ import type {
   QueryResult,
   CollectionStats,
   VectorStoreConfig,
+  MetadataFilter,
 } from '../../src/storage/types';
+import { hasFilterConditions, matchesMetadataFilter } from '../../src/storage/metadata-filter';
 
 export class MockVectorStore implements VectorStorePlugin {
   readonly name = 'mock';
@@ -54,12 +56,17 @@ export class MockVectorStore implements VectorStorePlugin {
     collectionName: string,
     _vector: number[],
     topK: number,
-    _filter?: Record<string, unknown>
+    filter?: MetadataFilter
   ): Promise<QueryResult[]> {
     const collection = this.collections.get(collectionName) || [];
 
+    // メタデータフィルタで絞り込み
+    const matched = filter
+      ? collection.filter((v) => matchesMetadataFilter(v.metadata, filter))
+      : collection;
+
     // 簡易的な類似度計算（常に0.9を返す）
-    return collection.slice(0, topK).map((v) => ({
+    return matched.slice(0, topK).map((v) => ({
       id: v.id,
       score: 0.9,
       metadata: v.metadata,
@@ -72,6 +79,15 @@ export class MockVectorStore implements VectorStorePlugin {
     this.collections.set(collectionName, filtered);
   }
 
+  async deleteByFilter(collectionName: string, filter: MetadataFilter): Promise<void> {
+    if (!hasFilterConditions(filter)) {
+      throw new Error('deleteByFilter requires at least one filter condition');
+    }
+    const collection = this.collections.get(collectionName) || [];
+    const filtered = collection.filter((v) => !matchesMetadataFilter(v.metadata, filter));
+    this.collections.set(collectionName, filtered);
+  }
+
   async getStats(collectionName: string): Promise<CollectionStats> {
     const collection = this.collections.get(collectionName) || [];
     return {
@@ -0,0 +1,212 @@
+/**
+ * Qdrant REST APIのインプロセス代替サーバー（テスト用）
+ *
+ * QdrantPluginが使用するエンドポイントのみをメモリ上で実装します。
+ */
+
+import * as http from 'http';
+import type { AddressInfo } from 'net';
+
+interface StoredPoint {
+  id: string | number;
+  vector: number[];
+  payload: Record<string, unknown>;
+}
+
+interface StoredCollection {
+  size: number;
+  points: Map<string | number, StoredPoint>;
+}
+
+interface Condition {
+  key: string;
+  match: { value?: unknown; any?: unknown[]; text?: string };
+}
+
+export interface QdrantStandIn {
+  /** サーバーのURL */
+  url: string;
+  /** 受信したリクエスト（メソッドとパス） */
+  requests: string[];
+  /** 次のn回のリクエストに503を返す */
+  failNext(count: number): void;
+  /** サーバーを停止 */
+  close(): Promise<void>;
+}
+
+function payloadValue(payload: Record<string, unknown>, key: string): unknown {
+  return key
+    .split('.')
+    .reduce<unknown>((value, part) => (value as Record<string, unknown>)?.[part], payload);
+}
+
+function matches(point: StoredPoint, filter?: { must?: Condition[] }): boolean {
+  return (filter?.must ?? []).every(({ key, match }) => {
+    const value = payloadValue(point.payload, key);
+    if (match.any) {
+      return match.any.includes(value);
+    }
+    if (match.text !== undefined) {
+      return typeof value === 'string' && value.includes(match.text);
+    }
+    return value === match.value;
+  });
+}
+
+function cosine(a: number[], b: number[]): number {
+  const dot = a.reduce((sum, x, i) => sum + x * b[i], 0);
+  const norm = Math.sqrt(a.reduce((s, x) => s + x * x, 0) * b.reduce((s, x) => s + x * x, 0));
+  return norm === 0 ? 0 : dot / norm;
+}
+
+/**
+ * 代替サーバーを起動
+ */
+export async function startQdrantStandIn(): Promise<QdrantStandIn> {
+  const collections = new Map<string, StoredCollection>();
+  const requests: string[] = [];
+  let failures = 0;
+
+  const handle = (method: string, path: string, body: any): [number, unknown] => {
+    if (method === 'GET' && path === '/') {
+      return [200, { title: 'qdrant - vector search engine', version: '1.12.0' }];
+    }
+
+    const match = path.match(/^\/collections\/([^/]+)(\/.*)?$/);
+    if (!match) {
+      return [404, { status: { error: 'Not found' } }];
+    }
+    const name = decodeURIComponent(match[1]);
+    const action = match[2] ?? '';
+    const collection = collections.get(name);
+
+    if (method === 'PUT' && action === '') {
+      if (collection) {
+        return [400, { status: { error: `Wrong input: Collection \`${name}\` already exists!` } }];
+      }
+      collections.set(name, { size: body.vectors.size, points: new Map() });
+      return [200, { result: true, status: 'ok' }];
+    }
+    if (!collection) {
+      return [404, { status: { error: `Not found: Collection \`${name}\` doesn't exist!` } }];
+    }
+
+    const checkDimension = (vector: number[]): [number, unknown] | null =>
+      vector.length === collection.size
+        ? null
+        : [
+            400,
+            {
+              status: {
+                error: `Wrong input: Vector dimension error: expected dim: ${collection.size}, got ${vector.length}`,
+              },
+            },
+          ];
+
+    switch (`${method} ${action}`) {
+      case 'GET ':
+        return [
+          200,
+          {
+            result: {
+              points_count: collection.points.size,
+              config: { params: { vectors: { size: collection.size, distance: 'Cosine' } } },
+            },
+            status: 'ok',
+          },
+        ];
+      case 'DELETE ':
+        collections.delete(name);
+        return [200, { result: true, status: 'ok' }];
+      case 'PUT /index':
+        return [200, { result: { status: 'completed' }, status: 'ok' }];
+      case 'PUT /points': {
+        for (const point of body.points as StoredPoint[]) {
+          const error = checkDimension(point.vector);
+          if (error) {
+            return error;
+          }
+        }
+        for (const point of body.points as StoredPoint[]) {
+          collection.points.set(point.id, point);
+        }
+        return [200, { result: { status: 'completed' }, status: 'ok' }];
+      }
+      case 'POST /points/search': {
+        const error = checkDimension(body.vector);
+        if (error) {
+          return error;
+        }
+        const result = Array.from(collection.points.values())
+          .filter((p) => matches(p, body.filter))
+          .map((p) => ({ id: p.id, score: cosine(body.vector, p.vector), payload: p.payload }))
+          .sort((a, b) => b.score - a.score)
+          .slice(0, body.limit);
+        return [200, { result, status: 'ok' }];
+      }
+      case 'POST /points/scroll': {
+        const all = Array.from(collection.points.values()).filter((p) => matches(p, body.filter));
+        const offset = typeof body.offset === 'number' ? body.offset : 0;
+        const next = offset + body.limit;
+        return [
+          200,
+          {
+            result: {
+              points: all.slice(offset, next).map((p) => ({ id: p.id, payload: p.payload })),
+              next_page_offset: next < all.length ? next : null,
+            },
+            status: 'ok',
+          },
+        ];
+      }
+      case 'POST /points/delete': {
+        if (body.points) {
+          for (const id of body.points) {
+            collection.points.delete(id);
+          }
+        } else {
+          for (const point of Array.from(collection.points.values())) {
+            if (matches(point, body.filter)) {
+              collection.points.delete(point.id);
+            }
+          }
+        }
+        return [200, { result: { status: 'completed' }, status: 'ok' }];
+      }
+      default:
+        return [404, { status: { error: `Unsupported: ${method} ${path}` } }];
+    }
+  };
+
+  const server = http.createServer((req, res) => {
+    let raw = '';
+    req.on('data', (chunk) => (raw += chunk));
+    req.on('end', () => {
+      const path = (req.url ?? '/').split('?')[0];
+      requests.push(`${req.method} ${path}`);
+
+      if (failures > 0) {
+        failures--;
+        res.writeHead(503, { 'Content-Type': 'text/plain' });
+        res.end('Service Unavailable');
+        return;
+      }
+
+      const [status, body] = handle(req.method ?? 'GET', path, raw ? JSON.parse(raw) : undefined);
+      res.writeHead(status, { 'Content-Type': 'application/json' });
+      res.end(JSON.stringify(body));
+    });
+  });
+
+  await new Promise<void>((resolve) => server.listen(0, '127.0.0.1', resolve));
+  const { port } = server.address() as AddressInfo;
+
+  return {
+    url: `http://127.0.0.1:${port}`,
+    requests,
+    failNext(count: number) {
+      failures = count;
+    },
+    close: () => new Promise<void>((resolve) => server.close(() => resolve())),
+  };
+}
@@ -139,6 +139,38 @@ describe('ConfigManager', () => {
 
       await expect(configManager.loadConfig()).rejects.toThrow(ConfigValidationError);
     });
+
+    it('無効なスコア統合方式の場合、エラーをスローする', async () => {
+      const invalidConfig = {
+        mode: 'local',
+        vectorStore: DEFAULT_CONFIG.vectorStore,
+        embedding: DEFAULT_CONFIG.embedding,
+        search: { bm25Weight: 0.3, vectorWeight: 0.7, fusion: 'max' },
+      };
+
+      fs.writeFileSync(testConfigPath, JSON.stringify(invalidConfig));
+
+      await expect(configManager.loadConfig()).rejects.toThrow(ConfigValidationError);
+    });
+
+    it('スコア統合方式を指定しない場合、linearとrrfK=60が補完される', async () => {
+      const config = {
+        mode: 'local',
+        vectorStore: DEFAULT_CONFIG.vectorStore,
+        embedding: DEFAULT_CONFIG.embedding,
+        search: { bm25Weight: 0.5, vectorWeight: 0.5 },
+      };
+
+      fs.writeFileSync(testConfigPath, JSON.stringify(config));
+
+      const loaded = await configManager.loadConfig();
+      expect(loaded.search).toEqual({
+        bm25Weight: 0.5,
+        vectorWeight: 0.5,
+        fusion: 'linear',
+        rrfK: 60,
+      });
+    });
   });
 
   describe('環境変数オーバーライド', () => {
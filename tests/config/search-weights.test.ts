@@ -0,0 +1,48 @@
+/**
+ * 検索スコアの重み設定のテスト
+ */
+
+import { describe, it, expect } from '@jest/globals';
+import { ConfigManager, resolveBm25Weight } from '../../src/config/config-manager.js';
+import { LspMcpConfig, DEFAULT_CONFIG } from '../../src/config/types.js';
+import { ConfigValidationError } from '../../src/utils/errors.js';
+
+describe('検索スコアの重み', () => {
+  const configManager = new ConfigManager('/nonexistent/.lsp-mcp.json');
+
+  const withWeights = (bm25Weight: number, vectorWeight: number): LspMcpConfig => ({
+    ...DEFAULT_CONFIG,
+    search: { ...DEFAULT_CONFIG.search!, bm25Weight, vectorWeight },
+  });
+
+  it('0から1の範囲の重みを受け付ける', () => {
+    expect(() => configManager.validateConfig(withWeights(0.3, 0.7))).not.toThrow();
+    expect(() => configManager.validateConfig(withWeights(0, 1))).not.toThrow();
+  });
+
+  it('範囲外の重みはエラーになる', () => {
+    expect(() => configManager.validateConfig(withWeights(1.5, 0.7))).toThrow(
+      ConfigValidationError
+    );
+    expect(() => configManager.validateConfig(withWeights(0.3, -0.1))).toThrow(
+      ConfigValidationError
+    );
+    expect(() => configManager.validateConfig(withWeights(NaN, 0.7))).toThrow(
+      ConfigValidationError
+    );
+  });
+
+  it('両方の重みが0の場合はエラーになる', () => {
+    expect(() => configManager.validateConfig(withWeights(0, 0))).toThrow(ConfigValidationError);
+  });
+
+  it('合計が1.0でない重みは比で正規化してBM25の重みを求める', () => {
+    expect(resolveBm25Weight({ bm25Weight: 0.3, vectorWeight: 0.7 })).toBeCloseTo(0.3);
+    expect(resolveBm25Weight({ bm25Weight: 0.5, vectorWeight: 0.3 })).toBeCloseTo(0.625);
+    expect(resolveBm25Weight({ bm25Weight: 1, vectorWeight: 1 })).toBeCloseTo(0.5);
+  });
+
+  it('検索設定がない場合はデフォルトの重みを使う', () => {
+    expect(resolveBm25Weight(undefined)).toBeCloseTo(0.3);
+  });
+});
@@ -0,0 +1,77 @@
+import { describe, it, expect } from '@jest/globals';
+import { CodeChunker } from '../../src/parser/code-chunker.js';
+
+const buildLines = (count: number): string =>
+  Array.from({ length: count }, (_, i) => `line ${i}`).join('\n');
+
+describe('CodeChunker', () => {
+  it('短いファイルは1つのチャンクになること', () => {
+    const chunker = new CodeChunker({ chunkLines: 10, overlapLines: 2 });
+    const chunks = chunker.chunk(buildLines(5));
+
+    expect(chunks).toHaveLength(1);
+    expect(chunks[0]).toEqual({ text: buildLines(5), startLine: 0, endLine: 4 });
+  });
+
+  it('重複する行範囲のチャンクに分割されること', () => {
+    const chunker = new CodeChunker({ chunkLines: 10, overlapLines: 3 });
+    const chunks = chunker.chunk(buildLines(25));
+
+    expect(chunks.map((c) => [c.startLine, c.endLine])).toEqual([
+      [0, 9],
+      [7, 16],
+      [14, 23],
+      [21, 24],
+    ]);
+    expect(chunks[1].text.split('\n')[0]).toBe('line 7');
+  });
+
+  it('チャンクが最終行でちょうど終わる場合に余分なチャンクを作らないこと', () => {
+    const chunker = new CodeChunker({ chunkLines: 10, overlapLines: 0 });
+    const chunks = chunker.chunk(buildLines(20));
+
+    expect(chunks.map((c) => [c.startLine, c.endLine])).toEqual([
+      [0, 9],
+      [10, 19],
+    ]);
+  });
+
+  it('空白のみのチャンクは生成しないこと', () => {
+    const chunker = new CodeChunker({ chunkLines: 3, overlapLines: 0 });
+    const chunks = chunker.chunk('a\nb\nc\n\n\n\nd');
+
+    expect(chunks.map((c) => c.startLine)).toEqual([0, 6]);
+    expect(chunker.chunk('   \n\n')).toEqual([]);
+  });
+
+  it('重複行数がチャンク行数以上でも処理が前進すること', () => {
+    const chunker = new CodeChunker({ chunkLines: 2, overlapLines: 5 });
+    const chunks = chunker.chunk(buildLines(4));
+
+    expect(chunks.map((c) => [c.startLine, c.endLine])).toEqual([
+      [0, 1],
+      [1, 2],
+      [2, 3],
+    ]);
+  });
+
+  it('指定した行範囲に含まれない行のみをチャンクにすること', () => {
+    const chunker = new CodeChunker({ chunkLines: 10, overlapLines: 0 });
+    const chunks = chunker.chunkUncovered(buildLines(12), [
+      { startLine: 2, endLine: 4 },
+      { startLine: 9, endLine: 20 },
+    ]);
+
+    expect(chunks).toEqual([
+      { text: buildLines(2), startLine: 0, endLine: 1 },
+      { text: 'line 5\nline 6\nline 7\nline 8', startLine: 5, endLine: 8 },
+    ]);
+    expect(chunker.chunkUncovered(buildLines(3), [{ startLine: 0, endLine: 2 }])).toEqual([]);
+  });
+
+  it('除外する行範囲がない場合はchunkと同じ結果になること', () => {
+    const chunker = new CodeChunker({ chunkLines: 10, overlapLines: 3 });
+
+    expect(chunker.chunkUncovered(buildLines(25), [])).toEqual(chunker.chunk(buildLines(25)));
+  });
+});
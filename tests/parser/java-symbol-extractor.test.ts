@@ -0,0 +1,158 @@
+/**
+ * Tests for Symbol Extractor (Java)
+ */
+
+import { readFileSync } from 'fs';
+import { join } from 'path';
+import { SymbolExtractor } from '../../src/parser/symbol-extractor.js';
+import { LanguageParser } from '../../src/parser/language-parser.js';
+import { Language, SymbolType, SymbolScope, SymbolInfo } from '../../src/parser/types.js';
+
+describe('SymbolExtractor (Java)', () => {
+  let languageParser: LanguageParser;
+  let symbolExtractor: SymbolExtractor;
+
+  beforeAll(() => {
+    languageParser = new LanguageParser();
+    symbolExtractor = new SymbolExtractor(languageParser);
+  });
+
+  describe('java-sample.java', () => {
+    let symbols: SymbolInfo[];
+
+    beforeAll(() => {
+      const code = readFileSync(
+        join(__dirname, '..', 'fixtures', 'samples', 'java-sample.java'),
+        'utf-8'
+      );
+      const result = symbolExtractor.extractSymbols(code, Language.Java);
+      symbols = result.symbols;
+    });
+
+    test('should extract classes with fields, constructors and methods', () => {
+      const user = symbols.find((s) => s.name === 'User' && s.type === SymbolType.Class);
+      expect(user).toBeDefined();
+      expect(user?.isExported).toBe(true);
+      expect(user?.docstring).toBe('User data class');
+      expect(user?.members?.map((m) => m.name)).toEqual([
+        'id',
+        'name',
+        'email',
+        'User',
+        'getId',
+        'getName',
+        'getEmail',
+      ]);
+
+      const id = user?.members?.find((m) => m.name === 'id');
+      expect(id?.type).toBe(SymbolType.Variable);
+      expect(id?.valueType).toBe('int');
+      expect(id?.isPrivate).toBe(true);
+
+      const constructor = user?.members?.find((m) => m.name === 'User');
+      expect(constructor?.type).toBe(SymbolType.Method);
+      expect(constructor?.parameters).toEqual([
+        { name: 'id', type: 'int' },
+        { name: 'name', type: 'String' },
+        { name: 'email', type: 'String' },
+      ]);
+    });
+
+    test('should extract interfaces with abstract methods', () => {
+      const repository = symbols.find((s) => s.name === 'Repository');
+      expect(repository?.type).toBe(SymbolType.Interface);
+      expect(repository?.isExported).toBe(false);
+      expect(repository?.docstring).toBe('Repository interface');
+
+      const findById = repository?.members?.find((m) => m.name === 'findById');
+      expect(findById?.isAbstract).toBe(true);
+      expect(findById?.returnType).toBe('Optional<User>');
+    });
+
+    test('should extract implemented interfaces and annotations', () => {
+      const manager = symbols.find((s) => s.name === 'UserManager');
+      expect(manager?.implements).toEqual(['Repository']);
+
+      const constructors = manager?.members?.filter((m) => m.name === 'UserManager');
+      expect(constructors).toHaveLength(2);
+
+      const save = manager?.members?.find((m) => m.name === 'save');
+      expect(save?.annotations).toEqual(['Override']);
+      expect(save?.isAbstract).toBe(false);
+    });
+
+    test('should extract static methods with Javadoc', () => {
+      const manager = symbols.find((s) => s.name === 'UserManager');
+      const calculateTotal = manager?.members?.find((m) => m.name === 'calculateTotal');
+      expect(calculateTotal?.isStatic).toBe(true);
+      expect(calculateTotal?.returnType).toBe('double');
+      expect(calculateTotal?.docstring).toBe('Calculate total sum');
+    });
+
+    test('should extract enums, records and annotation types', () => {
+      const code = [
+        'public enum Status { ACTIVE, INACTIVE; public boolean isActive() { return true; } }',
+        'public record Point(int x, int y) implements Comparable<Point> {}',
+        'public @interface Audited { String value(); }',
+        'class Config { public static final int MAX = 10; }',
+      ].join('\n');
+      const result = symbolExtractor.extractSymbols(code, Language.Java);
+
+      const status = result.symbols.find((s) => s.name === 'Status');
+      expect(status?.type).toBe(SymbolType.Enum);
+      expect(status?.members?.map((m) => m.name)).toEqual(['ACTIVE', 'INACTIVE', 'isActive']);
+
+      const point = result.symbols.find((s) => s.name === 'Point');
+      expect(point?.type).toBe(SymbolType.Class);
+      expect(point?.implements).toEqual(['Comparable']);
+      expect(point?.members?.map((m) => m.name)).toEqual(['x', 'y']);
+
+      const audited = result.symbols.find((s) => s.name === 'Audited');
+      expect(audited?.type).toBe(SymbolType.Annotation);
+
+      const config = result.symbols.find((s) => s.name === 'Config');
+      const max = config?.members?.find((m) => m.name === 'MAX');
+      expect(max?.type).toBe(SymbolType.Constant);
+      expect(max?.initialValue).toBe('10');
+    });
+
+    test('should extract nested classes with class scope', () => {
+      const code = 'public class Outer { private static class Inner {} }';
+      const result = symbolExtractor.extractSymbols(code, Language.Java);
+
+      const inner = result.symbols.find((s) => s.name === 'Inner');
+      expect(inner?.scope).toBe(SymbolScope.Class);
+      expect(inner?.isStatic).toBe(true);
+      expect(inner?.isPrivate).toBe(true);
+    });
+  });
+
+  describe('Edge cases', () => {
+    test('should extract annotations', () => {
+      const code = '@Override\npublic void method() {}';
+      const result = symbolExtractor.extractSymbols(code, Language.Java);
+      expect(result.symbols).toBeDefined();
+    });
+
+    test('should extract final classes', () => {
+      const code = 'public final class Utils {}';
+      const result = symbolExtractor.extractSymbols(code, Language.Java);
+      const cls = result.symbols.find((s) => s.name === 'Utils');
+      expect(cls).toBeDefined();
+    });
+
+    test('should extract synchronized methods', () => {
+      const code = 'public synchronized void update() {}';
+      const result = symbolExtractor.extractSymbols(code, Language.Java);
+      const method = result.symbols.find((s) => s.name === 'update');
+      expect(method).toBeDefined();
+    });
+
+    test('should extract varargs methods', () => {
+      const code = 'public void log(String... messages) {}';
+      const result = symbolExtractor.extractSymbols(code, Language.Java);
+      const method = result.symbols.find((s) => s.name === 'log');
+      expect(method).toBeDefined();
+    });
+  });
+});
@@ -0,0 +1,235 @@
+/**
+ * Tests for Symbol Extractor (Rust)
+ */
+
+import { readFileSync } from 'fs';
+import { join } from 'path';
+import { SymbolExtractor } from '../../src/parser/symbol-extractor.js';
+import { LanguageParser } from '../../src/parser/language-parser.js';
+import { Language, SymbolType, SymbolScope, SymbolInfo } from '../../src/parser/types.js';
+
+describe('SymbolExtractor (Rust)', () => {
+  let languageParser: LanguageParser;
+  let symbolExtractor: SymbolExtractor;
+
+  beforeAll(() => {
+    languageParser = new LanguageParser();
+    symbolExtractor = new SymbolExtractor(languageParser);
+  });
+
+  describe('rust-sample.rs', () => {
+    let symbols: SymbolInfo[];
+
+    beforeAll(() => {
+      const code = readFileSync(
+        join(__dirname, '..', 'fixtures', 'samples', 'rust-sample.rs'),
+        'utf-8'
+      );
+      const result = symbolExtractor.extractSymbols(code, Language.Rust);
+      symbols = result.symbols;
+    });
+
+    test('should extract structs with fields and docstrings', () => {
+      const user = symbols.find((s) => s.name === 'User' && s.type === SymbolType.Struct);
+      expect(user).toBeDefined();
+      expect(user?.isExported).toBe(true);
+      expect(user?.docstring).toBe('User struct definition');
+      expect(user?.members?.map((m) => m.name)).toEqual(['id', 'name', 'email']);
+    });
+
+    test('should extract private struct fields', () => {
+      const manager = symbols.find((s) => s.name === 'UserManager' && s.type === SymbolType.Struct);
+      const users = manager?.members?.find((m) => m.name === 'users');
+      expect(users?.isPrivate).toBe(true);
+      expect(users?.valueType).toBe('HashMap<u32, User>');
+    });
+
+    test('should extract traits with method signatures', () => {
+      const repository = symbols.find((s) => s.name === 'Repository');
+      expect(repository?.type).toBe(SymbolType.Trait);
+      expect(repository?.docstring).toBe('Repository trait definition');
+
+      const save = repository?.members?.find((m) => m.name === 'save');
+      expect(save?.type).toBe(SymbolType.Method);
+      expect(save?.isAbstract).toBe(true);
+      expect(save?.parameters).toEqual([{ name: 'user', type: 'User' }]);
+    });
+
+    test('should extract public functions', () => {
+      const calculateTotal = symbols.find((s) => s.name === 'calculate_total');
+      expect(calculateTotal?.type).toBe(SymbolType.Function);
+      expect(calculateTotal?.scope).toBe(SymbolScope.Global);
+      expect(calculateTotal?.isExported).toBe(true);
+      expect(calculateTotal?.isAsync).toBe(false);
+      expect(calculateTotal?.returnType).toBe('f64');
+      expect(calculateTotal?.docstring).toBe('Calculate total');
+    });
+
+    test('should detect async functions', () => {
+      const fetchUserData = symbols.find((s) => s.name === 'fetch_user_data');
+      expect(fetchUserData?.isAsync).toBe(true);
+      expect(fetchUserData?.parameters).toEqual([{ name: 'user_id', type: 'u32' }]);
+    });
+
+    test('should extract modules and their items', () => {
+      const tests = symbols.find((s) => s.name === 'tests');
+      expect(tests?.type).toBe(SymbolType.Module);
+      expect(tests?.isExported).toBe(false);
+
+      const testFn = symbols.find((s) => s.name === 'test_user_manager');
+      expect(testFn?.scope).toBe(SymbolScope.Module);
+    });
+
+    test('should not extract impl methods as top-level functions', () => {
+      expect(symbols.find((s) => s.name === 'add_user')).toBeUndefined();
+    });
+
+    test('should attach impl methods to the owning struct', () => {
+      const manager = symbols.find((s) => s.name === 'UserManager' && s.type === SymbolType.Struct);
+      const methods = manager?.members?.filter((m) => m.type === SymbolType.Method);
+      expect(methods?.map((m) => m.name)).toEqual([
+        'new',
+        'add_user',
+        'get_user_by_id',
+        'user_count',
+        'default',
+      ]);
+
+      const newFn = methods?.find((m) => m.name === 'new');
+      expect(newFn?.isStatic).toBe(true);
+      expect(newFn?.scope).toBe(SymbolScope.Class);
+      expect(newFn?.docstring).toBe('Create a new UserManager');
+
+      const addUser = methods?.find((m) => m.name === 'add_user');
+      expect(addUser?.isStatic).toBe(false);
+      expect(addUser?.parameters).toEqual([{ name: 'user', type: 'User' }]);
+    });
+
+    test('should record implemented traits', () => {
+      const manager = symbols.find((s) => s.name === 'UserManager' && s.type === SymbolType.Struct);
+      expect(manager?.implements).toEqual(['Default']);
+
+      const defaultFn = manager?.members?.find((m) => m.name === 'default');
+      expect(defaultFn?.implements).toEqual(['Default']);
+    });
+
+    test('should register impl blocks for types defined elsewhere', () => {
+      const code = [
+        'impl fmt::Display for Config<T> {',
+        '    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { Ok(()) }',
+        '}',
+      ].join('\n');
+      const result = symbolExtractor.extractSymbols(code, Language.Rust);
+
+      const config = result.symbols.find((s) => s.name === 'Config');
+      expect(config?.type).toBe(SymbolType.Impl);
+      expect(config?.valueType).toBe('Config<T>');
+      expect(config?.implements).toEqual(['Display']);
+      expect(config?.members?.map((m) => m.name)).toEqual(['fmt']);
+    });
+
+    test('should not invent structs for generic or foreign impl targets', () => {
+      const code = [
+        'impl<T: Debug> Describe for T {',
+        '    fn describe(&self) -> String { String::new() }',
+        '}',
+        'impl Display for Vec<Foo> {',
+        '    fn fmt(&self, f: &mut Formatter) -> Result { Ok(()) }',
+        '}',
+        'impl Debug for Vec<Foo> {',
+        '    fn fmt(&self, f: &mut Formatter) -> Result { Ok(()) }',
+        '}',
+      ].join('\n');
+      const result = symbolExtractor.extractSymbols(code, Language.Rust);
+
+      expect(result.symbols.filter((s) => s.type === SymbolType.Struct)).toEqual([]);
+      expect(result.symbols.find((s) => s.name === 'T')?.type).toBe(SymbolType.Impl);
+
+      const vecImpls = result.symbols.filter((s) => s.name === 'Vec');
+      expect(vecImpls).toHaveLength(1);
+      expect(vecImpls[0].implements).toEqual(['Display', 'Debug']);
+      expect(vecImpls[0].members).toHaveLength(2);
+    });
+
+    test('should attach impl blocks to types defined later in the file', () => {
+      const code = ['impl Point { fn origin() -> Self { Point } }', 'struct Point;'].join('\n');
+      const result = symbolExtractor.extractSymbols(code, Language.Rust);
+
+      const points = result.symbols.filter((s) => s.name === 'Point');
+      expect(points).toHaveLength(1);
+      expect(points[0].type).toBe(SymbolType.Struct);
+      expect(points[0].members?.map((m) => m.name)).toEqual(['origin']);
+    });
+
+    test('should extract statics, type aliases and macros', () => {
+      const code = [
+        'pub static mut COUNTER: u32 = 0;',
+        'pub type UserMap = HashMap<u32, User>;',
+        '#[macro_export]',
+        'macro_rules! user { () => {}; }',
+      ].join('\n');
+      const result = symbolExtractor.extractSymbols(code, Language.Rust);
+
+      const counter = result.symbols.find((s) => s.name === 'COUNTER');
+      expect(counter?.type).toBe(SymbolType.Variable);
+      expect(counter?.isStatic).toBe(true);
+
+      const userMap = result.symbols.find((s) => s.name === 'UserMap');
+      expect(userMap?.type).toBe(SymbolType.TypeAlias);
+      expect(userMap?.valueType).toBe('HashMap<u32, User>');
+
+      const macro = result.symbols.find((s) => s.name === 'user');
+      expect(macro?.type).toBe(SymbolType.Macro);
+      expect(macro?.isExported).toBe(true);
+    });
+  });
+
+  describe('Edge cases', () => {
+    test('should extract trait definitions', () => {
+      const code = 'trait Drawable { fn draw(&self); }';
+      const result = symbolExtractor.extractSymbols(code, Language.Rust);
+      const trait = result.symbols.find((s) => s.name === 'Drawable');
+      expect(trait).toBeDefined();
+      expect(trait?.type).toBe(SymbolType.Trait);
+    });
+
+    test('should extract impl blocks', () => {
+      const code = 'impl User { fn new() -> Self { } }';
+      const result = symbolExtractor.extractSymbols(code, Language.Rust);
+      const impl = result.symbols.find((s) => s.name === 'User');
+      expect(impl).toBeDefined();
+      expect(impl?.type).toBe(SymbolType.Impl);
+    });
+
+    test('should extract const items', () => {
+      const code = 'const MAX_SIZE: usize = 100;';
+      const result = symbolExtractor.extractSymbols(code, Language.Rust);
+      const constant = result.symbols.find((s) => s.name === 'MAX_SIZE');
+      expect(constant).toBeDefined();
+      expect(constant?.type).toBe(SymbolType.Constant);
+    });
+
+    test('should extract type aliases', () => {
+      const code = 'type Result<T> = std::result::Result<T, Error>;';
+      const result = symbolExtractor.extractSymbols(code, Language.Rust);
+      const typeAlias = result.symbols.find((s) => s.name === 'Result');
+      expect(typeAlias).toBeDefined();
+    });
+
+    test('should extract public functions', () => {
+      const code = 'pub fn helper() {}';
+      const result = symbolExtractor.extractSymbols(code, Language.Rust);
+      const func = result.symbols.find((s) => s.name === 'helper');
+      expect(func).toBeDefined();
+      expect(func?.isExported).toBe(true);
+    });
+
+    test('should treat pub(self) as private', () => {
+      const code = ['pub(self) fn hidden() {}', 'pub(crate) fn shared() {}'].join('\n');
+      const result = symbolExtractor.extractSymbols(code, Language.Rust);
+
+      expect(result.symbols.find((s) => s.name === 'hidden')?.isExported).toBe(false);
+      expect(result.symbols.find((s) => s.name === 'shared')?.isExported).toBe(true);
+    });
+  });
+});
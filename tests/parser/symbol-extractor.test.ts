@@ -508,75 +508,6 @@ describe('SymbolExtractor', () => {
     });
   });
 
-  describe('Edge cases for Rust', () => {
-    test('should extract trait definitions', () => {
-      const code = 'trait Drawable { fn draw(&self); }';
-      const result = symbolExtractor.extractSymbols(code, Language.Rust);
-      const trait = result.symbols.find((s) => s.name === 'Drawable');
-      expect(trait).toBeDefined();
-      expect(trait?.type).toBe(SymbolType.Interface);
-    });
-
-    test('should extract impl blocks', () => {
-      const code = 'impl User { fn new() -> Self { } }';
-      const result = symbolExtractor.extractSymbols(code, Language.Rust);
-      const impl = result.symbols.find((s) => s.name === 'User');
-      expect(impl).toBeDefined();
-    });
-
-    test('should extract const items', () => {
-      const code = 'const MAX_SIZE: usize = 100;';
-      const result = symbolExtractor.extractSymbols(code, Language.Rust);
-      const constant = result.symbols.find((s) => s.name === 'MAX_SIZE');
-      expect(constant).toBeDefined();
-      expect(constant?.type).toBe(SymbolType.Constant);
-    });
-
-    test('should extract type aliases', () => {
-      const code = 'type Result<T> = std::result::Result<T, Error>;';
-      const result = symbolExtractor.extractSymbols(code, Language.Rust);
-      const typeAlias = result.symbols.find((s) => s.name === 'Result');
-      expect(typeAlias).toBeDefined();
-    });
-
-    test('should extract public functions', () => {
-      const code = 'pub fn helper() {}';
-      const result = symbolExtractor.extractSymbols(code, Language.Rust);
-      const func = result.symbols.find((s) => s.name === 'helper');
-      expect(func).toBeDefined();
-      expect(func?.isExported).toBe(true);
-    });
-  });
-
-  describe('Edge cases for Java', () => {
-    test('should extract annotations', () => {
-      const code = '@Override\npublic void method() {}';
-      const result = symbolExtractor.extractSymbols(code, Language.Java);
-      expect(result.symbols).toBeDefined();
-    });
-
-    test('should extract final classes', () => {
-      const code = 'public final class Utils {}';
-      const result = symbolExtractor.extractSymbols(code, Language.Java);
-      const cls = result.symbols.find((s) => s.name === 'Utils');
-      expect(cls).toBeDefined();
-    });
-
-    test('should extract synchronized methods', () => {
-      const code = 'public synchronized void update() {}';
-      const result = symbolExtractor.extractSymbols(code, Language.Java);
-      const method = result.symbols.find((s) => s.name === 'update');
-      expect(method).toBeDefined();
-    });
-
-    test('should extract varargs methods', () => {
-      const code = 'public void log(String... messages) {}';
-      const result = symbolExtractor.extractSymbols(code, Language.Java);
-      const method = result.symbols.find((s) => s.name === 'log');
-      expect(method).toBeDefined();
-    });
-  });
-
   describe('Edge cases for C/C++', () => {
     test('should extract function pointers', () => {
       const code = 'void (*callback)(int);';
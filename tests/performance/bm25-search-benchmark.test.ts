@@ -0,0 +1,225 @@
+/**
+ * BM25検索のベンチマーク
+ *
+ * 従来のターム・ドキュメントごとに問い合わせる方式（N+1クエリ）と、
+ * 1回の結合クエリでスコアリングする`BM25Engine.search`の実行時間を比較します。
+ * スコアの比較は近接ブーストを無効にして行い、実行時間はデフォルト設定
+ * （近接ブーストあり、出現位置も取得）でも計測します。
+ *
+ * 実行方法:
+ *   npm run test:performance:bm25
+ *   BM25_BENCH_DOCUMENTS=200000 npm run test:performance:bm25
+ */
+
+import Database from 'better-sqlite3';
+import * as fs from 'fs/promises';
+import * as path from 'path';
+import { BM25Engine } from '../../src/storage/bm25-engine';
+import { parseBM25Query } from '../../src/storage/bm25-query';
+
+/** インデックス化するドキュメント数 */
+const DOCUMENT_COUNT = Number(process.env['BM25_BENCH_DOCUMENTS'] ?? 20000);
+
+/** 計測に使うクエリ */
+const QUERIES = [
+  'parse config',
+  'user repository find',
+  'handle request error',
+  'vector store upsert batch',
+  'async function',
+];
+
+/** 各クエリの計測回数 */
+const ITERATIONS = 5;
+
+/** ドキュメントの生成に使う語彙 */
+const VOCABULARY = [
+  'parse',
+  'config',
+  'user',
+  'repository',
+  'find',
+  'handle',
+  'request',
+  'error',
+  'vector',
+  'store',
+  'upsert',
+  'batch',
+  'async',
+  'function',
+  'index',
+  'document',
+  'search',
+  'query',
+  'token',
+  'file',
+  'path',
+  'cache',
+  'result',
+  'score',
+];
+
+/**
+ * 決定的な疑似乱数（実行ごとに同じコーパスを生成するため）
+ */
+function createRandom(seed: number): () => number {
+  let state = seed;
+  return () => {
+    state = (state * 1103515245 + 12345) % 2147483648;
+    return state / 2147483648;
+  };
+}
+
+/**
+ * シンボルに似たドキュメントを生成
+ */
+function generateDocument(random: () => number, index: number): string {
+  const words = Array.from({ length: 8 + Math.floor(random() * 24) }, () => {
+    const word = VOCABULARY[Math.floor(random() * VOCABULARY.length)];
+    return random() < 0.1 ? `${word}${index % 97}` : word;
+  });
+  return words.join(' ');
+}
+
+/**
+ * 従来方式のBM25検索（ターム・ドキュメントごとにクエリを発行し、統計も毎回計算）
+ */
+function legacySearch(
+  db: Database.Database,
+  query: string,
+  topK: number
+): Array<{ documentId: string; score: number }> {
+  const k1 = 1.5;
+  const b = 0.75;
+  const stats = db
+    .prepare(`SELECT COUNT(*) as count, AVG(length) as avgLength FROM document_stats`)
+    .get() as { count: number; avgLength: number };
+
+  const scores = new Map<string, number>();
+  for (const term of parseBM25Query(query).terms) {
+    const docs = db
+      .prepare(`SELECT document_id, frequency FROM inverted_index WHERE term = ?`)
+      .all(term) as Array<{ document_id: string; frequency: number }>;
+    if (docs.length === 0) {
+      continue;
+    }
+
+    const idf = Math.log((stats.count - docs.length + 0.5) / (docs.length + 0.5) + 1);
+    for (const doc of docs) {
+      const { length } = db
+        .prepare(`SELECT length FROM document_stats WHERE document_id = ?`)
+        .get(doc.document_id) as { length: number };
+      const tf = doc.frequency;
+      const score =
+        idf * ((tf * (k1 + 1)) / (tf + k1 * (1 - b + b * (length / stats.avgLength))));
+      scores.set(doc.document_id, (scores.get(doc.document_id) ?? 0) + score);
+    }
+  }
+
+  return Array.from(scores, ([documentId, score]) => ({ documentId, score }))
+    .sort((x, y) => y.score - x.score)
+    .slice(0, topK);
+}
+
+/**
+ * 実行時間の中央値（ミリ秒）を計測
+ */
+async function measure(fn: () => unknown): Promise<number> {
+  const durations: number[] = [];
+  for (let i = 0; i < ITERATIONS; i++) {
+    const start = performance.now();
+    await fn();
+    durations.push(performance.now() - start);
+  }
+  durations.sort((x, y) => x - y);
+  return durations[Math.floor(durations.length / 2)];
+}
+
+describe('BM25 Search Benchmark', () => {
+  const dbPath = path.join(process.cwd(), './tmp', `bench-bm25-${Date.now()}.db`);
+  let engine: BM25Engine;
+  let defaultEngine: BM25Engine;
+  let db: Database.Database;
+
+  beforeAll(async () => {
+    engine = new BM25Engine(dbPath, { proximityWeight: 0 });
+    await engine.initialize();
+
+    const random = createRandom(42);
+    for (let i = 0; i < DOCUMENT_COUNT; i++) {
+      const documentId = `/src/file${i % 1000}.ts:function:symbol${i}`;
+      await engine.indexDocument(documentId, generateDocument(random, i), { tokenizer: 'code' });
+    }
+
+    // 同じインデックスをデフォルト設定（proximityWeight: 0.5）で検索する
+    defaultEngine = new BM25Engine(dbPath);
+    await defaultEngine.initialize();
+
+    db = new Database(dbPath, { readonly: true });
+    console.log(`\n📚 Indexed ${DOCUMENT_COUNT} documents`);
+  }, 900000);
+
+  afterAll(async () => {
+    db.close();
+    await defaultEngine.close();
+    await engine.close();
+    await fs.rm(dbPath, { force: true });
+  });
+
+  test('結合クエリによるスコアリングは従来方式と同じ結果を返す', async () => {
+    for (const query of QUERIES) {
+      const expected = legacySearch(db, query, 10);
+      const actual = await engine.search(query, 10);
+
+      expect(actual).toHaveLength(expected.length);
+      actual.forEach((result, i) => expect(result.score).toBeCloseTo(expected[i].score, 8));
+    }
+  });
+
+  test('結合クエリによるスコアリングは従来方式より高速', async () => {
+    let legacyTotal = 0;
+    let batchedTotal = 0;
+
+    for (const query of QUERIES) {
+      const legacy = await measure(() => legacySearch(db, query, 10));
+      const batched = await measure(() => engine.search(query, 10));
+      legacyTotal += legacy;
+      batchedTotal += batched;
+
+      console.log(
+        `   "${query}": legacy ${legacy.toFixed(1)}ms, batched ${batched.toFixed(1)}ms ` +
+          `(${(legacy / batched).toFixed(1)}x)`
+      );
+    }
+
+    console.log(
+      `   Total: legacy ${legacyTotal.toFixed(1)}ms, batched ${batchedTotal.toFixed(1)}ms ` +
+        `(${(legacyTotal / batchedTotal).toFixed(1)}x)`
+    );
+    expect(batchedTotal).toBeLessThan(legacyTotal);
+  }, 600000);
+
+  test('デフォルト設定（近接ブーストあり）でも従来方式より高速', async () => {
+    let legacyTotal = 0;
+    let defaultTotal = 0;
+
+    for (const query of QUERIES) {
+      const legacy = await measure(() => legacySearch(db, query, 10));
+      const proximity = await measure(() => defaultEngine.search(query, 10));
+      legacyTotal += legacy;
+      defaultTotal += proximity;
+
+      console.log(
+        `   "${query}": legacy ${legacy.toFixed(1)}ms, default ${proximity.toFixed(1)}ms ` +
+          `(${(legacy / proximity).toFixed(1)}x)`
+      );
+    }
+
+    console.log(
+      `   Total: legacy ${legacyTotal.toFixed(1)}ms, default ${defaultTotal.toFixed(1)}ms ` +
+        `(${(legacyTotal / defaultTotal).toFixed(1)}x)`
+    );
+    expect(defaultTotal).toBeLessThan(legacyTotal);
+  }, 600000);
+});
@@ -48,7 +48,7 @@ describe('BackgroundUpdateQueue', () => {
     // コンポーネントの初期化
     fileScanner = new FileScanner(testProjectPath);
     symbolExtractor = new SymbolExtractor(languageParser);
-    commentExtractor = new CommentExtractor();
+    commentExtractor = new CommentExtractor(languageParser);
     markdownParser = new MarkdownParser();
     docCodeLinker = new DocCodeLinker(symbolExtractor, markdownParser);
 
@@ -0,0 +1,251 @@
+/**
+ * File Watcherによるインデックス更新のテスト
+ *
+ * File Watcherのイベントが除外パターンで絞り込まれ、BackgroundUpdateQueue経由で
+ * IndexingServiceの更新・削除として反映されることを確認する。
+ */
+
+import { IndexingService } from '../../src/services/indexing-service';
+import { BackgroundUpdateQueue } from '../../src/services/background-update-queue';
+import { FileScanner } from '../../src/scanner/file-scanner';
+import { LanguageParser } from '../../src/parser/language-parser';
+import { SymbolExtractor } from '../../src/parser/symbol-extractor';
+import { CommentExtractor } from '../../src/parser/comment-extractor';
+import { MarkdownParser } from '../../src/parser/markdown-parser';
+import { DocCodeLinker } from '../../src/parser/doc-code-linker';
+import { MockEmbeddingEngine } from '../__mocks__/mock-embedding-engine';
+import { MockVectorStore } from '../__mocks__/mock-vector-store';
+import { MockBM25Engine } from '../__mocks__/mock-bm25-engine';
+import type { EmbeddingEngine } from '../../src/embedding/types';
+import type { VectorStorePlugin } from '../../src/storage/types';
+import * as fs from 'fs/promises';
+import * as path from 'path';
+
+describe('File Watcherによるインデックス更新', () => {
+  let indexingService: IndexingService;
+  let languageParser: LanguageParser;
+  let embeddingEngine: EmbeddingEngine;
+  let vectorStore: VectorStorePlugin;
+  let bm25Engine: MockBM25Engine;
+  let testProjectPath: string;
+
+  beforeAll(async () => {
+    // Tree-sitterパーサーの初期化
+    languageParser = new LanguageParser();
+    await languageParser.initialize();
+  });
+
+  beforeEach(async () => {
+    testProjectPath = path.join(process.cwd(), './tmp', `test-watcher-${Date.now()}`);
+    await fs.mkdir(testProjectPath, { recursive: true });
+
+    const symbolExtractor = new SymbolExtractor(languageParser);
+    const markdownParser = new MarkdownParser();
+
+    embeddingEngine = new MockEmbeddingEngine();
+    await embeddingEngine.initialize();
+
+    vectorStore = new MockVectorStore();
+    await vectorStore.connect({ backend: 'mock', config: {} });
+
+    bm25Engine = new MockBM25Engine();
+    await bm25Engine.initialize();
+
+    indexingService = new IndexingService(
+      new FileScanner(testProjectPath),
+      symbolExtractor,
+      new CommentExtractor(languageParser),
+      markdownParser,
+      new DocCodeLinker(symbolExtractor, markdownParser),
+      embeddingEngine,
+      vectorStore,
+      bm25Engine
+    );
+  });
+
+  afterEach(async () => {
+    await indexingService.disableAllWatchers();
+
+    await embeddingEngine.dispose();
+    await vectorStore.disconnect();
+    await bm25Engine.close();
+
+    await fs.rm(testProjectPath, { recursive: true, force: true });
+  });
+
+  /**
+   * プロジェクトをインデックス化してFile Watcherを有効化
+   */
+  const indexAndWatch = async (options = {}): Promise<void> => {
+    await indexingService.indexProject('project-1', testProjectPath, options);
+    await indexingService.enableWatcher('project-1');
+  };
+
+  /**
+   * debounce（500ms）とキュー処理（100ms間隔）を待機
+   */
+  const waitForQueue = (): Promise<void> => new Promise((resolve) => setTimeout(resolve, 1000));
+
+  describe('File Watcherとの連携', () => {
+    test('enableWatcher: File Watcherを有効化できる', async () => {
+      await indexAndWatch();
+
+      expect(indexingService.isWatching('project-1')).toBe(true);
+    });
+
+    test('disableWatcher: File Watcherを無効化できる', async () => {
+      await indexAndWatch();
+      expect(indexingService.isWatching('project-1')).toBe(true);
+
+      await indexingService.disableWatcher('project-1');
+      expect(indexingService.isWatching('project-1')).toBe(false);
+    });
+
+    test('インデックス化されていないプロジェクトは監視できない', async () => {
+      await expect(indexingService.enableWatcher('unknown')).rejects.toThrow('not indexed');
+    });
+
+    test('ファイル変更イベント時に自動的に再インデックス化される', async () => {
+      const testFile = path.join(testProjectPath, 'watched.ts');
+      await fs.writeFile(testFile, 'export const version = 1;');
+      await indexAndWatch();
+
+      // updateFileが呼ばれることを追跡
+      const updateSpy = jest.spyOn(indexingService, 'updateFile');
+
+      // ファイルを変更
+      await fs.writeFile(testFile, 'export const version = 2;');
+      await waitForQueue();
+
+      expect(updateSpy).toHaveBeenCalledWith(testFile, 'project-1');
+    });
+
+    test('ファイル削除イベント時に自動的にインデックスから削除される', async () => {
+      const testFile = path.join(testProjectPath, 'to-delete.ts');
+      await fs.writeFile(testFile, 'export const value = 1;');
+      await indexAndWatch();
+
+      // deleteFileが呼ばれることを追跡
+      const deleteSpy = jest.spyOn(indexingService, 'deleteFile');
+
+      // ファイルを削除
+      await fs.unlink(testFile);
+      await waitForQueue();
+
+      expect(deleteSpy).toHaveBeenCalledWith(testFile, 'project-1');
+    });
+
+    test('ファイル追加イベント時に自動的にインデックス化される', async () => {
+      await indexAndWatch();
+
+      const updateSpy = jest.spyOn(indexingService, 'updateFile');
+
+      // 新しいファイルを追加
+      const newFile = path.join(testProjectPath, 'new.ts');
+      await fs.writeFile(newFile, 'export const newValue = 1;');
+      await waitForQueue();
+
+      expect(updateSpy).toHaveBeenCalledWith(newFile, 'project-1');
+    });
+
+    test('除外パターンと対象外の拡張子のファイルは無視される', async () => {
+      await fs.mkdir(path.join(testProjectPath, 'generated'), { recursive: true });
+      await indexAndWatch({ excludePatterns: ['**/generated/**'] });
+
+      const updateSpy = jest.spyOn(indexingService, 'updateFile');
+
+      await fs.writeFile(path.join(testProjectPath, 'generated', 'out.ts'), 'export {}');
+      await fs.writeFile(path.join(testProjectPath, 'notes.txt'), 'memo');
+      await waitForQueue();
+
+      expect(updateSpy).not.toHaveBeenCalled();
+    });
+
+    test('デフォルトの除外パターンに一致するファイルは無視される', async () => {
+      await fs.mkdir(path.join(testProjectPath, 'node_modules', 'pkg'), { recursive: true });
+      await indexAndWatch();
+
+      const updateSpy = jest.spyOn(indexingService, 'updateFile');
+
+      await fs.writeFile(path.join(testProjectPath, 'node_modules', 'pkg', 'index.ts'), '');
+      await waitForQueue();
+
+      expect(updateSpy).not.toHaveBeenCalled();
+    });
+
+    test('.gitignore形式の除外パターンをインデックス化時と同じように解釈する', async () => {
+      await fs.mkdir(path.join(testProjectPath, 'src', 'gen'), { recursive: true });
+      await indexAndWatch({ excludePatterns: ['gen/', '*.generated.ts'] });
+
+      const updateSpy = jest.spyOn(indexingService, 'updateFile');
+      const kept = path.join(testProjectPath, 'src', 'kept.ts');
+
+      await fs.writeFile(path.join(testProjectPath, 'src', 'gen', 'out.ts'), 'export {}');
+      await fs.writeFile(path.join(testProjectPath, 'src', 'api.generated.ts'), 'export {}');
+      await fs.writeFile(kept, 'export {}');
+      await waitForQueue();
+
+      expect(updateSpy.mock.calls.map((call) => call[0])).toEqual([kept]);
+    });
+
+    test('連続したファイル変更はデバウンスされる', async () => {
+      const testFile = path.join(testProjectPath, 'debounced.ts');
+      await fs.writeFile(testFile, 'export const v = 1;');
+      await indexAndWatch();
+
+      const updateSpy = jest.spyOn(indexingService, 'updateFile');
+
+      // 短時間に複数回変更
+      await fs.writeFile(testFile, 'export const v = 2;');
+      await new Promise((resolve) => setTimeout(resolve, 50));
+      await fs.writeFile(testFile, 'export const v = 3;');
+      await new Promise((resolve) => setTimeout(resolve, 50));
+      await fs.writeFile(testFile, 'export const v = 4;');
+      await waitForQueue();
+
+      // デバウンスにより1回だけ呼ばれる
+      expect(updateSpy).toHaveBeenCalledTimes(1);
+    });
+  });
+
+  describe('BackgroundUpdateQueueの操作種別', () => {
+    let queue: BackgroundUpdateQueue;
+
+    beforeEach(() => {
+      queue = new BackgroundUpdateQueue(indexingService);
+    });
+
+    afterEach(() => {
+      queue.stop();
+    });
+
+    test('delete操作はインデックスから削除する', async () => {
+      const deleteSpy = jest.spyOn(indexingService, 'deleteFile');
+      const updateSpy = jest.spyOn(indexingService, 'updateFile');
+      const removedFile = path.join(testProjectPath, 'removed.ts');
+
+      queue.enqueue(removedFile, 'project-1', undefined, 'delete');
+      queue.start();
+
+      await new Promise((resolve) => setTimeout(resolve, 200));
+
+      expect(deleteSpy).toHaveBeenCalledWith(removedFile, 'project-1');
+      expect(updateSpy).not.toHaveBeenCalled();
+    });
+
+    test('同じファイルの後から追加された操作で上書きされる', async () => {
+      const deleteSpy = jest.spyOn(indexingService, 'deleteFile');
+      const updateSpy = jest.spyOn(indexingService, 'updateFile');
+      const file = path.join(testProjectPath, 'recreated.ts');
+
+      queue.enqueue(file, 'project-1', undefined, 'delete');
+      queue.enqueue(file, 'project-1');
+      queue.start();
+
+      await new Promise((resolve) => setTimeout(resolve, 200));
+
+      expect(updateSpy).toHaveBeenCalledWith(file, 'project-1');
+      expect(deleteSpy).not.toHaveBeenCalled();
+    });
+  });
+});
@@ -5,7 +5,9 @@ import type {
   Vector,
   QueryResult,
   CollectionStats,
+  MetadataFilter,
 } from '../../src/storage/types';
+import { matchesMetadataFilter } from '../../src/storage/metadata-filter';
 import * as fs from 'fs/promises';
 import * as path from 'path';
 
@@ -56,7 +58,7 @@ class MockVectorStorePlugin implements VectorStorePlugin {
     collectionName: string,
     vector: number[],
     topK: number,
-    filter?: Record<string, unknown>
+    filter?: MetadataFilter
   ): Promise<QueryResult[]> {
     const collection = this.collections.get(collectionName);
     if (!collection) {
@@ -67,12 +69,8 @@ class MockVectorStorePlugin implements VectorStorePlugin {
     const results = collection.vectors
       .map((v) => {
         // フィルタチェック
-        if (filter) {
-          for (const [key, value] of Object.entries(filter)) {
-            if (v.metadata?.[key] !== value) {
-              return null;
-            }
-          }
+        if (filter && !matchesMetadataFilter(v.metadata, filter)) {
+          return null;
         }
 
         const similarity = this.cosineSimilarity(vector, v.vector);
@@ -355,6 +353,25 @@ describe('HybridSearchEngine', () => {
       expect(filtered.every((r) => r.id.startsWith('src/'))).toBe(true);
     });
 
+    test('パスパターンはIDではなくファイルパスのメタデータと照合する', () => {
+      const results = [
+        {
+          id: 'src-app:/repo/lib/a.ts:function:a',
+          score: 0.9,
+          metadata: { file_path: '/repo/lib/a.ts' },
+        },
+        {
+          id: 'app:/repo/src/b.ts:function:b',
+          score: 0.8,
+          metadata: { file_path: '/repo/src/b.ts' },
+        },
+      ];
+
+      const filtered = hybridEngine.filterResults(results, { pathPattern: 'src-' });
+
+      expect(filtered).toHaveLength(0);
+    });
+
     test('複数のフィルタ条件を組み合わせられる', async () => {
       const results = [
         {
@@ -375,6 +392,31 @@ describe('HybridSearchEngine', () => {
       expect(filtered[0].id).toBe('src/utils/helper.ts');
     });
 
+    test('シンボル種別でフィルタリングできる', () => {
+      const results = [
+        { id: 'a.ts:function:parse', score: 0.9, metadata: { type: 'function' } },
+        { id: 'a.ts:comment', score: 0.8, metadata: { type: 'comment' } },
+      ];
+
+      const filtered = hybridEngine.filterResults(results, { types: ['function'] });
+
+      expect(filtered.map((r) => r.id)).toEqual(['a.ts:function:parse']);
+    });
+
+    test('file_typeメタデータと大文字・小文字の異なる言語名でもフィルタリングできる', () => {
+      const results = [
+        { id: 'a.ts', score: 0.9, metadata: { file_type: 'ts', language: 'typescript' } },
+        { id: 'b.py', score: 0.8, metadata: { file_type: 'py', language: 'python' } },
+      ];
+
+      const filtered = hybridEngine.filterResults(results, {
+        fileTypes: ['.ts'],
+        languages: ['TypeScript'],
+      });
+
+      expect(filtered.map((r) => r.id)).toEqual(['a.ts']);
+    });
+
     test('フィルタ条件なしの場合は全件返す', async () => {
       const results = [
         { id: 'file1.ts', score: 0.9, metadata: {} },
@@ -450,14 +492,14 @@ describe('HybridSearchEngine', () => {
         {
           id: 'doc1',
           vector: [0.9, 0.1, 0.1],
-          metadata: { fileType: 'ts', language: 'TypeScript' },
+          metadata: { file_type: 'ts', language: 'TypeScript' },
         },
         {
           id: 'doc2',
           vector: [0.8, 0.2, 0.0],
-          metadata: { fileType: 'js', language: 'JavaScript' },
+          metadata: { file_type: 'js', language: 'JavaScript' },
         },
-        { id: 'doc3', vector: [0.1, 0.8, 0.9], metadata: { fileType: 'py', language: 'Python' } },
+        { id: 'doc3', vector: [0.1, 0.8, 0.9], metadata: { file_type: 'py', language: 'Python' } },
       ]);
     });
 
@@ -494,7 +536,7 @@ describe('HybridSearchEngine', () => {
       expect(results.length).toBeGreaterThan(0);
       expect(
         results.every((r) => {
-          const fileType = r.metadata?.fileType as string;
+          const fileType = r.metadata?.file_type as string;
           return fileType === 'ts' || fileType === 'js';
         })
       ).toBe(true);
@@ -523,7 +565,7 @@ describe('HybridSearchEngine', () => {
     test('ベクトル検索のみがヒットする場合も正しく動作する', async () => {
       // ベクトルには存在するがBM25にはない用語で検索
       await vectorStore.upsert('test-collection', [
-        { id: 'doc5', vector: [0.5, 0.5, 0.9], metadata: { fileType: 'go', language: 'Go' } },
+        { id: 'doc5', vector: [0.5, 0.5, 0.9], metadata: { file_type: 'go', language: 'Go' } },
       ]);
 
       const queryVector = [0.5, 0.5, 0.9]; // doc5に近いベクトル
@@ -550,6 +592,301 @@ describe('HybridSearchEngine', () => {
         expect(r.metadata?.bm25Score).toBeUndefined();
       });
     });
+
+    test('除外したタームを含むドキュメントはベクトル検索結果からも除かれる', async () => {
+      const queryVector = [0.85, 0.15, 0.05];
+
+      const results = await hybridEngine.search(
+        'test-collection',
+        'programming -typescript',
+        queryVector,
+        10
+      );
+
+      expect(results.length).toBeGreaterThan(0);
+      expect(results.some((r) => r.id === 'doc1')).toBe(false);
+    });
+
+    test('ORで指定したいずれかを含むドキュメントのみ返す', async () => {
+      const queryVector = [0.85, 0.15, 0.05];
+
+      const results = await hybridEngine.search(
+        'test-collection',
+        'python OR typescript',
+        queryVector,
+        10
+      );
+
+      expect(results.map((r) => r.id).sort()).toEqual(['doc1', 'doc3']);
+    });
+  });
+
+  describe('プロジェクト分離', () => {
+    beforeEach(async () => {
+      await vectorStore.createCollection('test-collection', 3);
+
+      // 他プロジェクトの方がクエリに近い結果を多数持つ状況を作る
+      const vectors = [];
+      for (let i = 0; i < 10; i++) {
+        await bm25Engine.indexDocument(`other${i}`, 'parse config file', {
+          projectId: 'project-b',
+        });
+        vectors.push({
+          id: `other${i}`,
+          vector: [0.9, 0.1, 0.0],
+          metadata: { project_id: 'project-b' },
+        });
+      }
+      await bm25Engine.indexDocument('mine', 'parse config', { projectId: 'project-a' });
+      vectors.push({ id: 'mine', vector: [0.1, 0.9, 0.0], metadata: { project_id: 'project-a' } });
+      await vectorStore.upsert('test-collection', vectors);
+    });
+
+    test('projectIdはBM25とベクトル検索の両方に渡される', async () => {
+      const bm25Spy = jest.spyOn(bm25Engine, 'search');
+      const querySpy = jest.spyOn(vectorStore, 'query');
+
+      await hybridEngine.search('test-collection', 'parse', [1, 0, 0], 2, {
+        projectId: 'project-a',
+      });
+
+      expect(bm25Spy).toHaveBeenCalledWith('parse', 4, { projectId: 'project-a' });
+      expect(querySpy).toHaveBeenCalledWith('test-collection', [1, 0, 0], 4, {
+        project_id: 'project-a',
+      });
+    });
+
+    test('他プロジェクトの結果が上位を占めても自プロジェクトの結果を返す', async () => {
+      const results = await hybridEngine.search('test-collection', 'parse config', [1, 0, 0], 2, {
+        projectId: 'project-a',
+      });
+
+      expect(results.map((r) => r.id)).toEqual(['mine']);
+    });
+  });
+
+  describe('スコア統合方式', () => {
+    const bm25Results = [
+      { documentId: 'both', score: 8.0 },
+      { documentId: 'bm25-only', score: 2.0 },
+    ];
+    const vectorResults = [
+      { id: 'vector-only', score: 0.95, metadata: {} },
+      { id: 'both', score: 0.9, metadata: {} },
+    ];
+
+    test('デフォルトはlinear（Min-Max正規化の線形結合）', () => {
+      expect(hybridEngine.getFusionOptions()).toEqual({ strategy: 'linear', rrfK: 60 });
+
+      const fused = hybridEngine.fuseResults(bm25Results, vectorResults);
+      const both = fused.find((r) => r.id === 'both');
+
+      // BM25: 8.0 → 1.0, ベクトル: 0.9 → 0.9/0.95
+      expect(both?.score).toBeCloseTo(0.3 * 1.0 + 0.7 * (0.9 / 0.95), 5);
+    });
+
+    test('rrfは順位から1/(k+rank)で統合し、両リスト1位で1.0になる', () => {
+      const fused = hybridEngine.fuseResults(bm25Results, vectorResults, {
+        strategy: 'rrf',
+        rrfK: 60,
+      });
+      const scores = new Map(fused.map((r) => [r.id, r.score]));
+
+      expect(scores.get('both')).toBeCloseTo((0.3 / 61 + 0.7 / 62) * 61, 5);
+      expect(scores.get('vector-only')).toBeCloseTo((0.7 / 61) * 61, 5);
+      expect(scores.get('bm25-only')).toBeCloseTo((0.3 / 62) * 61, 5);
+
+      const single = hybridEngine.fuseResults(
+        [{ documentId: 'top', score: 3 }],
+        [{ id: 'top', score: 0.5, metadata: {} }],
+        { strategy: 'rrf' }
+      );
+      expect(single[0].score).toBeCloseTo(1.0, 5);
+    });
+
+    test('rrfのスコアは元のスコアの大きさに依存しない', () => {
+      const scaled = bm25Results.map((r) => ({ ...r, score: r.score * 100 }));
+
+      const original = hybridEngine.fuseResults(bm25Results, vectorResults, { strategy: 'rrf' });
+      const fused = hybridEngine.fuseResults(scaled, vectorResults, { strategy: 'rrf' });
+
+      expect(fused).toEqual(original);
+    });
+
+    test('zscoreは各リストを標準化し、単一ヒットは平均相当（0.5）として扱う', () => {
+      const fused = hybridEngine.fuseResults(
+        [{ documentId: 'only', score: 12.0 }],
+        [
+          { id: 'a', score: 0.9, metadata: {} },
+          { id: 'b', score: 0.5, metadata: {} },
+        ],
+        { strategy: 'zscore' }
+      );
+      const scores = new Map(fused.map((r) => [r.id, r.score]));
+
+      expect(scores.get('only')).toBeCloseTo(0.3 * 0.5, 5);
+      // a, bのZスコアは±1
+      expect(scores.get('a')).toBeCloseTo(0.7 / (1 + Math.exp(-1)), 5);
+      expect(scores.get('b')).toBeCloseTo(0.7 / (1 + Math.exp(1)), 5);
+    });
+
+    test('コンストラクタで既定の方式を、searchの引数で呼び出しごとの方式を指定できる', async () => {
+      const engine = new HybridSearchEngine(bm25Engine, vectorStore, 0.3, { strategy: 'rrf' });
+      expect(engine.getFusionOptions()).toEqual({ strategy: 'rrf', rrfK: 60 });
+
+      await vectorStore.createCollection('test-collection', 3);
+      await vectorStore.upsert('test-collection', [
+        { id: 'a', vector: [1, 0, 0], metadata: {} },
+        { id: 'b', vector: [0.9, 0.1, 0], metadata: {} },
+      ]);
+      const fuseSpy = jest.spyOn(engine, 'fuseResults');
+
+      await engine.search('test-collection', '', [1, 0, 0], 10, undefined, {
+        strategy: 'zscore',
+      });
+
+      expect(fuseSpy).toHaveBeenCalledWith([], expect.any(Array), {
+        strategy: 'zscore',
+        rrfK: 60,
+      });
+    });
+
+    test('RRFの定数kが正の数でない場合はエラー', () => {
+      expect(() => new HybridSearchEngine(bm25Engine, vectorStore, 0.3, { rrfK: 0 })).toThrow(
+        'RRF constant k must be a positive number'
+      );
+    });
+  });
+
+  describe('フィルタのプッシュダウン', () => {
+    test('検索フィルタをベクターストアのメタデータフィルタに変換する', () => {
+      const metadataFilter = hybridEngine.buildVectorFilter({
+        projectId: 'project-a',
+        languages: ['TypeScript', 'Go'],
+        fileTypes: ['.ts', 'go'],
+        pathPattern: 'src/services',
+        types: ['function', 'method'],
+      });
+
+      expect(metadataFilter).toEqual({
+        project_id: 'project-a',
+        language: ['typescript', 'go'],
+        file_type: ['ts', 'go'],
+        file_path: { $like: '%src/services%' },
+        type: ['function', 'method'],
+      });
+    });
+
+    test('条件がない場合はundefinedを返す', () => {
+      expect(hybridEngine.buildVectorFilter({ languages: [], fileTypes: [] })).toBeUndefined();
+    });
+
+    test('他言語の結果が上位を占めても指定言語の結果を返す', async () => {
+      await vectorStore.createCollection('test-collection', 3);
+
+      const vectors = [];
+      for (let i = 0; i < 10; i++) {
+        vectors.push({
+          id: `py${i}`,
+          vector: [0.9, 0.1, 0.0],
+          metadata: { language: 'python', file_type: 'py', type: 'function' },
+        });
+      }
+      vectors.push({
+        id: 'ts-parser',
+        vector: [0.1, 0.9, 0.0],
+        metadata: { language: 'typescript', file_type: 'ts', type: 'function' },
+      });
+      await vectorStore.upsert('test-collection', vectors);
+
+      const results = await hybridEngine.search('test-collection', '', [1, 0, 0], 2, {
+        languages: ['TypeScript'],
+        types: ['function'],
+      });
+
+      expect(results.map((r) => r.id)).toEqual(['ts-parser']);
+    });
+  });
+
+  describe('BM25のみの結果のメタデータ', () => {
+    test('BM25インデックスの属性をメタデータとして補う', async () => {
+      await vectorStore.createCollection('test-collection', 3);
+      await bm25Engine.indexDocument('src/a.py:function:load', 'load config', {
+        projectId: 'project-a',
+        language: 'python',
+        filePath: 'src/a.py',
+        fileType: 'py',
+        symbolType: 'function',
+      });
+
+      const [result] = await hybridEngine.search('test-collection', 'load', [1, 0, 0], 5);
+
+      expect(result.id).toBe('src/a.py:function:load');
+      expect(result.metadata).toEqual({
+        project_id: 'project-a',
+        file_path: 'src/a.py',
+        language: 'python',
+        file_type: 'py',
+        type: 'function',
+      });
+    });
+
+    test('ベクトル検索のメタデータがある結果はそのまま返す', async () => {
+      await vectorStore.createCollection('test-collection', 3);
+      await bm25Engine.indexDocument('doc1', 'load config', { language: 'python' });
+      await vectorStore.upsert('test-collection', [
+        { id: 'doc1', vector: [1, 0, 0], metadata: { language: 'python', name: 'load' } },
+      ]);
+
+      const [result] = await hybridEngine.search('test-collection', 'load', [1, 0, 0], 5);
+
+      expect(result.metadata).toEqual({ language: 'python', name: 'load' });
+    });
+  });
+
+  describe('フィルタのみの検索', () => {
+    beforeEach(async () => {
+      for (const name of ['alpha', 'beta', 'gamma']) {
+        await bm25Engine.indexDocument(`src/${name}.rs:function:${name}`, `${name} body`, {
+          projectId: 'project-a',
+          language: 'rust',
+          filePath: `src/${name}.rs`,
+          fileType: 'rs',
+          symbolType: 'function',
+        });
+      }
+      await bm25Engine.indexDocument('src/delta.py:function:delta', 'delta body', {
+        projectId: 'project-a',
+        language: 'python',
+        filePath: 'src/delta.py',
+        fileType: 'py',
+        symbolType: 'function',
+      });
+    });
+
+    test('フィルタに一致するドキュメントをBM25の属性をメタデータとして返す', async () => {
+      const results = await hybridEngine.searchByFilter(
+        { projectId: 'project-a', languages: ['Rust'], types: ['function'] },
+        2
+      );
+
+      expect(results.map((r) => r.id)).toEqual([
+        'src/alpha.rs:function:alpha',
+        'src/beta.rs:function:beta',
+      ]);
+      expect(results[0].score).toBe(1);
+      expect(results[0].metadata).toMatchObject({ file_path: 'src/alpha.rs', type: 'function' });
+    });
+
+    test('除外条件で候補が減る場合は取得件数を増やしてlimit件を満たす', async () => {
+      const results = await hybridEngine.searchByFilter(
+        { languages: ['rust'] },
+        2,
+        '-alpha -beta'
+      );
+
+      expect(results.map((r) => r.id)).toEqual(['src/gamma.rs:function:gamma']);
+    });
   });
 
   describe('エッジケース', () => {
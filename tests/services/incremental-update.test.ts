@@ -5,7 +5,6 @@ import { SymbolExtractor } from '../../src/parser/symbol-extractor';
 import { CommentExtractor } from '../../src/parser/comment-extractor';
 import { MarkdownParser } from '../../src/parser/markdown-parser';
 import { DocCodeLinker } from '../../src/parser/doc-code-linker';
-import { FileWatcher } from '../../src/watcher/file-watcher';
 import { MockEmbeddingEngine } from '../__mocks__/mock-embedding-engine';
 import { MockVectorStore } from '../__mocks__/mock-vector-store';
 import { MockBM25Engine } from '../__mocks__/mock-bm25-engine';
@@ -16,7 +15,6 @@ import * as path from 'path';
 
 describe('インクリメンタル更新機能とFile Watcherの統合', () => {
   let indexingService: IndexingService;
-  let fileWatcher: FileWatcher;
   let fileScanner: FileScanner;
   let languageParser: LanguageParser;
   let symbolExtractor: SymbolExtractor;
@@ -44,7 +42,7 @@ describe('インクリメンタル更新機能とFile Watcherの統合', () => {
     // コンポーネントの初期化
     fileScanner = new FileScanner(testProjectPath);
     symbolExtractor = new SymbolExtractor(languageParser);
-    commentExtractor = new CommentExtractor();
+    commentExtractor = new CommentExtractor(languageParser);
     markdownParser = new MarkdownParser();
     docCodeLinker = new DocCodeLinker(symbolExtractor, markdownParser);
 
@@ -74,19 +72,11 @@ describe('インクリメンタル更新機能とFile Watcherの統合', () => {
       vectorStore,
       bm25Engine
     );
-
-    // File Watcherの初期化
-    fileWatcher = new FileWatcher({
-      rootPath: testProjectPath,
-      debounceMs: 100, // テストでは短く設定
-    });
   });
 
   afterEach(async () => {
     // File Watcherを停止
-    if (fileWatcher.isWatching()) {
-      await fileWatcher.stop();
-    }
+    await indexingService.disableAllWatchers();
 
     // リソースのクリーンアップ
     await embeddingEngine.dispose();
@@ -101,115 +91,18 @@ describe('インクリメンタル更新機能とFile Watcherの統合', () => {
     }
   });
 
-  describe('File Watcherとの連携', () => {
-    test('enableWatcher: File Watcherを有効化できる', async () => {
-      await indexingService.enableWatcher('project-1');
-
-      expect(fileWatcher.isWatching()).toBe(true);
-    });
-
-    test('disableWatcher: File Watcherを無効化できる', async () => {
-      await indexingService.enableWatcher('project-1');
-      expect(fileWatcher.isWatching()).toBe(true);
-
-      await indexingService.disableWatcher('project-1');
-      expect(fileWatcher.isWatching()).toBe(false);
-    });
-
-    test('ファイル変更イベント時に自動的に再インデックス化される', async () => {
-      const testFile = path.join(testProjectPath, 'watched.ts');
-      await fs.writeFile(testFile, 'export const version = 1;');
-
-      // 初回インデックス化
-      await indexingService.indexFile(testFile, 'project-1');
-
-      // File Watcherを有効化
-      await indexingService.enableWatcher('project-1');
-      await fileWatcher.start();
-
-      // updateFileが呼ばれることを追跡
-      const updateSpy = jest.spyOn(indexingService, 'updateFile');
-
-      // ファイルを変更
-      await fs.writeFile(testFile, 'export const version = 2;');
-
-      // debounce待機
-      await new Promise((resolve) => setTimeout(resolve, 200));
-
-      // updateFileが呼ばれたことを確認
-      expect(updateSpy).toHaveBeenCalledWith(testFile, 'project-1');
-    });
-
-    test('ファイル削除イベント時に自動的にインデックスから削除される', async () => {
-      const testFile = path.join(testProjectPath, 'to-delete.ts');
-      await fs.writeFile(testFile, 'export const value = 1;');
-
-      // 初回インデックス化
-      await indexingService.indexFile(testFile, 'project-1');
-
-      // File Watcherを有効化
-      await indexingService.enableWatcher('project-1');
-      await fileWatcher.start();
-
-      // deleteFileが呼ばれることを追跡
-      const deleteSpy = jest.spyOn(indexingService, 'deleteFile');
+  /**
+   * プロジェクトをインデックス化してFile Watcherを有効化
+   */
+  const indexAndWatch = async (options = {}): Promise<void> => {
+    await indexingService.indexProject('project-1', testProjectPath, options);
+    await indexingService.enableWatcher('project-1');
+  };
 
-      // ファイルを削除
-      await fs.unlink(testFile);
-
-      // debounce待機
-      await new Promise((resolve) => setTimeout(resolve, 200));
-
-      // deleteFileが呼ばれたことを確認
-      expect(deleteSpy).toHaveBeenCalledWith(testFile, 'project-1');
-    });
-
-    test('ファイル追加イベント時に自動的にインデックス化される', async () => {
-      // File Watcherを有効化
-      await indexingService.enableWatcher('project-1');
-      await fileWatcher.start();
-
-      // indexFileが呼ばれることを追跡
-      const indexSpy = jest.spyOn(indexingService, 'indexFile');
-
-      // 新しいファイルを追加
-      const newFile = path.join(testProjectPath, 'new.ts');
-      await fs.writeFile(newFile, 'export const newValue = 1;');
-
-      // debounce待機
-      await new Promise((resolve) => setTimeout(resolve, 200));
-
-      // indexFileが呼ばれたことを確認
-      expect(indexSpy).toHaveBeenCalledWith(newFile, 'project-1');
-    });
-
-    test('連続したファイル変更はデバウンスされる', async () => {
-      const testFile = path.join(testProjectPath, 'debounced.ts');
-      await fs.writeFile(testFile, 'export const v = 1;');
-
-      // 初回インデックス化
-      await indexingService.indexFile(testFile, 'project-1');
-
-      // File Watcherを有効化
-      await indexingService.enableWatcher('project-1');
-      await fileWatcher.start();
-
-      const updateSpy = jest.spyOn(indexingService, 'updateFile');
-
-      // 短時間に複数回変更
-      await fs.writeFile(testFile, 'export const v = 2;');
-      await new Promise((resolve) => setTimeout(resolve, 50));
-      await fs.writeFile(testFile, 'export const v = 3;');
-      await new Promise((resolve) => setTimeout(resolve, 50));
-      await fs.writeFile(testFile, 'export const v = 4;');
-
-      // debounce待機
-      await new Promise((resolve) => setTimeout(resolve, 200));
-
-      // デバウンスにより1回だけ呼ばれる
-      expect(updateSpy).toHaveBeenCalledTimes(1);
-    });
-  });
+  /**
+   * debounce（500ms）とキュー処理（100ms間隔）を待機
+   */
+  const waitForQueue = (): Promise<void> => new Promise((resolve) => setTimeout(resolve, 1000));
 
   describe('エラーハンドリング', () => {
     test('更新エラーが発生しても File Watcher は継続動作する', async () => {
@@ -218,30 +111,22 @@ describe('インクリメンタル更新機能とFile Watcherの統合', () => {
 
       await fs.writeFile(goodFile, 'export const good = 1;');
       await fs.writeFile(badFile, 'export const bad = 1;');
-
-      // 初回インデックス化
-      await indexingService.indexFile(goodFile, 'project-1');
-      await indexingService.indexFile(badFile, 'project-1');
-
-      // File Watcherを有効化
-      await indexingService.enableWatcher('project-1');
-      await fileWatcher.start();
+      await indexAndWatch();
 
       // badFileをディレクトリに置き換え（エラーを発生させる）
       await fs.unlink(badFile);
       await fs.mkdir(badFile);
+      await waitForQueue();
 
-      // debounce待機
-      await new Promise((resolve) => setTimeout(resolve, 200));
+      const updateSpy = jest.spyOn(indexingService, 'updateFile');
 
       // goodFileは正常に更新できる
       await fs.writeFile(goodFile, 'export const good = 999;');
-
-      // debounce待機
-      await new Promise((resolve) => setTimeout(resolve, 200));
+      await waitForQueue();
 
       // File Watcherは停止していない
-      expect(fileWatcher.isWatching()).toBe(true);
+      expect(indexingService.isWatching('project-1')).toBe(true);
+      expect(updateSpy).toHaveBeenCalledWith(goodFile, 'project-1');
     });
   });
 
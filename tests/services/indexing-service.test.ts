@@ -1,4 +1,4 @@
-import { IndexingService } from '../../src/services/indexing-service';
+import { IndexingService, INDEX_FORMAT_VERSION } from '../../src/services/indexing-service';
 import { FileScanner } from '../../src/scanner/file-scanner';
 import { LanguageParser } from '../../src/parser/language-parser';
 import { SymbolExtractor } from '../../src/parser/symbol-extractor';
@@ -42,7 +42,7 @@ describe('IndexingService', () => {
     // コンポーネントの初期化
     fileScanner = new FileScanner(testProjectPath);
     symbolExtractor = new SymbolExtractor(languageParser);
-    commentExtractor = new CommentExtractor();
+    commentExtractor = new CommentExtractor(languageParser);
     markdownParser = new MarkdownParser();
     docCodeLinker = new DocCodeLinker(symbolExtractor, markdownParser);
 
@@ -141,6 +141,198 @@ export { add };
       expect(result.symbolsCount).toBeGreaterThan(0);
     });
 
+    test('Rustのimplメソッドが所有する型とトレイト付きで登録される', async () => {
+      const testFile = path.join(testProjectPath, 'lib.rs');
+      await fs.writeFile(
+        testFile,
+        `pub struct Counter {
+    count: u32,
+}
+
+impl Default for Counter {
+    fn default() -> Self {
+        Counter { count: 0 }
+    }
+}
+`
+      );
+
+      const upsertSpy = jest.spyOn(vectorStore, 'upsert');
+      const result = await indexingService.indexFile(testFile, 'project-1');
+
+      expect(result.success).toBe(true);
+
+      const vectors = upsertSpy.mock.calls[0][1];
+      const counter = vectors.find((v) => v.metadata?.name === 'Counter');
+      expect(counter?.metadata?.implements).toEqual(['Default']);
+
+      const defaultFn = vectors.find((v) => v.metadata?.name === 'default');
+      expect(defaultFn?.metadata?.type).toBe('method');
+      expect(defaultFn?.metadata?.parent).toBe('Counter');
+      expect(defaultFn?.metadata?.implements).toEqual(['Default']);
+    });
+
+    test('クラスのメソッドはクラスとは別のドキュメントとして登録される', async () => {
+      const testFile = path.join(testProjectPath, 'greeter.ts');
+      await fs.writeFile(
+        testFile,
+        `export class Greeter {
+  greet(name: string): string {
+    return 'Hello, ' + name;
+  }
+}
+`
+      );
+
+      const upsertSpy = jest.spyOn(vectorStore, 'upsert');
+      const result = await indexingService.indexFile(testFile, 'project-1');
+
+      expect(result.success).toBe(true);
+
+      const vectors = upsertSpy.mock.calls[0][1];
+      expect(vectors.find((v) => v.metadata?.name === 'Greeter')?.metadata?.type).toBe('class');
+
+      const greet = vectors.find((v) => v.metadata?.name === 'greet');
+      expect(greet?.metadata?.type).toBe('method');
+      expect(greet?.metadata?.parent).toBe('Greeter');
+    });
+
+    test('埋め込みテキストにシンボルの定義範囲のコードを使う', async () => {
+      const testFile = path.join(testProjectPath, 'ops.py');
+      await fs.writeFile(
+        testFile,
+        `def first():
+    return 1
+
+
+def second():
+    return 2
+`
+      );
+
+      const embedSpy = jest.spyOn(embeddingEngine, 'embedBatch');
+      await indexingService.indexFile(testFile, 'project-1');
+
+      const texts = embedSpy.mock.calls[0][0];
+      const second = texts.find((t) => t.startsWith('second\n'));
+      expect(second).toContain('def second():');
+      expect(second).toContain('return 2');
+      expect(second).not.toContain('return 1');
+    });
+
+    test('シンボルのないファイルはチャンクとしてインデックス化される', async () => {
+      const testFile = path.join(testProjectPath, 'script.py');
+      await fs.writeFile(testFile, 'print("start")\nfor i in range(3):\n    print(i)\n');
+
+      const upsertSpy = jest.spyOn(vectorStore, 'upsert');
+      const result = await indexingService.indexFile(testFile, 'project-1');
+
+      expect(result.success).toBe(true);
+      expect(result.symbolsCount).toBe(0);
+      expect(result.vectorsCount).toBeGreaterThan(0);
+
+      const vectors = upsertSpy.mock.calls[0][1];
+      expect(vectors[0].metadata?.type).toBe('chunk');
+      expect(vectors[0].metadata?.line_start).toBe(0);
+    });
+
+    test('シンボルの定義範囲外のトップレベルのコードはチャンクになる', async () => {
+      const testFile = path.join(testProjectPath, 'run.py');
+      await fs.writeFile(
+        testFile,
+        `def helper(x):
+    return x * 2
+
+
+for item in load_items():
+    print(helper(item))
+`
+      );
+
+      const upsertSpy = jest.spyOn(vectorStore, 'upsert');
+      const result = await indexingService.indexFile(testFile, 'project-1');
+
+      expect(result.success).toBe(true);
+      const vectors = upsertSpy.mock.calls[0][1];
+      const chunks = vectors.filter((v) => v.metadata?.type === 'chunk');
+      expect(chunks).toHaveLength(1);
+      expect(chunks[0].metadata?.line_start).toBeGreaterThan(1);
+      expect(chunks[0].metadata?.line_end).toBe(6);
+      expect(vectors.some((v) => v.metadata?.name === 'helper')).toBe(true);
+    });
+
+    test('マーカー付きコメントが関連シンボル付きでインデックス化される', async () => {
+      const testFile = path.join(testProjectPath, 'todo.ts');
+      await fs.writeFile(
+        testFile,
+        `export function load(): number {
+  // TODO: add caching
+  return 1;
+}
+`
+      );
+
+      const upsertSpy = jest.spyOn(vectorStore, 'upsert');
+      const bm25Spy = jest.spyOn(bm25Engine, 'indexDocuments');
+      const result = await indexingService.indexFile(testFile, 'project-1');
+
+      expect(result.success).toBe(true);
+
+      const vectors = upsertSpy.mock.calls[0][1];
+      const todo = vectors.find((v) => v.metadata?.type === 'comment');
+      expect(todo?.id).toBe(`project-1:${testFile}:comment:load`);
+      expect(todo?.metadata?.marker).toBe('TODO');
+      expect(todo?.metadata?.associated_symbol).toBe('load');
+      expect(todo?.metadata?.content).toBe('TODO: add caching');
+      expect(bm25Spy).toHaveBeenCalledTimes(1);
+      expect(bm25Spy.mock.calls[0][0]).toContainEqual({
+        documentId: todo?.id,
+        content: 'TODO: add caching',
+        attributes: expect.objectContaining({
+          projectId: 'project-1',
+          filePath: testFile,
+          symbolType: 'comment',
+        }),
+      });
+    });
+
+    test('同じ行のシンボルも別々のIDでインデックス化される', async () => {
+      const testFile = path.join(testProjectPath, 'oneline.js');
+      await fs.writeFile(testFile, 'function a() {} function b() {} function a() {}\n');
+
+      const upsertSpy = jest.spyOn(vectorStore, 'upsert');
+      await indexingService.indexFile(testFile, 'project-1');
+
+      const ids = upsertSpy.mock.calls[0][1].map((v) => v.id);
+      expect(ids).toEqual([
+        `project-1:${testFile}:function:a`,
+        `project-1:${testFile}:function:b`,
+        `project-1:${testFile}:function:a#2`,
+      ]);
+    });
+
+    test('ファイル先頭に行を挿入してもシンボルのIDは変わらない', async () => {
+      const testFile = path.join(testProjectPath, 'stable.ts');
+      const source = `export class Service {
+  load(): number {
+    return 1;
+  }
+}
+`;
+      await fs.writeFile(testFile, source);
+
+      const upsertSpy = jest.spyOn(vectorStore, 'upsert');
+      await indexingService.indexFile(testFile, 'project-1');
+      const before = upsertSpy.mock.calls[0][1].map((v) => v.id);
+
+      await fs.writeFile(testFile, `import { x } from './x';\n\n${source}`);
+      await indexingService.updateFile(testFile, 'project-1');
+      const after = upsertSpy.mock.calls[1][1].map((v) => v.id);
+
+      expect(before).toContain(`project-1:${testFile}:method:Service.load`);
+      expect(after).toEqual(before);
+    });
+
     test('Markdownファイルをインデックス化できる', async () => {
       const testFile = path.join(testProjectPath, 'README.md');
       await fs.writeFile(
@@ -168,9 +360,22 @@ function hello() {
       expect(result.vectorsCount).toBeGreaterThan(0);
     });
 
-    test('対応していない拡張子のファイルはスキップされる', async () => {
-      const testFile = path.join(testProjectPath, 'test.txt');
-      await fs.writeFile(testFile, 'plain text file');
+    test('構文解析に対応していない拡張子のファイルはチャンクになる', async () => {
+      const testFile = path.join(testProjectPath, 'deploy.rb');
+      await fs.writeFile(testFile, 'require "net/http"\nputs fetch_release("v1")\n');
+
+      const upsertSpy = jest.spyOn(vectorStore, 'upsert');
+      const result = await indexingService.indexFile(testFile, 'project-1');
+
+      expect(result.success).toBe(true);
+      const vectors = upsertSpy.mock.calls[0][1];
+      expect(vectors.map((v) => v.metadata?.type)).toEqual(['chunk']);
+      expect(vectors[0].metadata?.file_type).toBe('rb');
+    });
+
+    test('バイナリファイルはスキップされる', async () => {
+      const testFile = path.join(testProjectPath, 'image.bin');
+      await fs.writeFile(testFile, Buffer.from([0x89, 0x50, 0x00, 0x01]));
 
       const result = await indexingService.indexFile(testFile, 'project-1');
 
@@ -277,6 +482,160 @@ function hello() {
     });
   });
 
+  describe('マニフェストによる差分インデックス化', () => {
+    test('再実行時は変更のないファイルをスキップする', async () => {
+      await fs.writeFile(path.join(testProjectPath, 'a.ts'), 'export function a() {}');
+      await fs.writeFile(path.join(testProjectPath, 'b.ts'), 'export function b() {}');
+
+      const first = await indexingService.indexProject('project-1', testProjectPath);
+      expect(first.indexedFiles).toBe(2);
+      expect(first.skippedFiles).toBe(0);
+
+      const embedSpy = jest.spyOn(embeddingEngine, 'embedBatch');
+      const second = await indexingService.indexProject('project-1', testProjectPath);
+
+      expect(second.indexedFiles).toBe(0);
+      expect(second.skippedFiles).toBe(2);
+      expect(second.totalSymbols).toBe(first.totalSymbols);
+      expect(second.totalVectors).toBe(first.totalVectors);
+      expect(embedSpy).not.toHaveBeenCalled();
+    });
+
+    test('変更されたファイルを再埋め込みし、削除されたファイルを除去する', async () => {
+      const fileA = path.join(testProjectPath, 'a.ts');
+      const fileB = path.join(testProjectPath, 'b.ts');
+      await fs.writeFile(fileA, 'export function a() {}');
+      await fs.writeFile(fileB, 'export function b() {}');
+      await indexingService.indexProject('project-1', testProjectPath);
+
+      await fs.writeFile(fileA, 'export function renamed() {}');
+      await fs.unlink(fileB);
+
+      const result = await indexingService.indexProject('project-1', testProjectPath);
+
+      expect(result.reembeddedFiles).toBe(1);
+      expect(result.purgedFiles).toBe(1);
+      expect(result.skippedFiles).toBe(0);
+
+      const dummy = new Array(embeddingEngine.getDimension()).fill(0);
+      const remaining = await vectorStore.query('code_vectors', dummy, 100);
+      expect(remaining.map((r) => r.id)).toEqual([`project-1:${fileA}:function:renamed`]);
+    });
+
+    test('mtimeのみ変わったファイルはハッシュが一致すればスキップする', async () => {
+      const fileA = path.join(testProjectPath, 'a.ts');
+      await fs.writeFile(fileA, 'export function a() {}');
+      await indexingService.indexProject('project-1', testProjectPath);
+
+      const future = new Date(Date.now() + 60_000);
+      await fs.utimes(fileA, future, future);
+
+      const result = await indexingService.indexProject('project-1', testProjectPath);
+      expect(result.skippedFiles).toBe(1);
+      expect(result.reembeddedFiles).toBe(0);
+    });
+
+    test('マニフェストは別のIndexingServiceインスタンス（再起動後）でも使われる', async () => {
+      await fs.writeFile(path.join(testProjectPath, 'a.ts'), 'export function a() {}');
+      await indexingService.indexProject('project-1', testProjectPath);
+
+      const restarted = new IndexingService(
+        fileScanner,
+        symbolExtractor,
+        commentExtractor,
+        markdownParser,
+        docCodeLinker,
+        embeddingEngine,
+        vectorStore,
+        bm25Engine
+      );
+      const result = await restarted.indexProject('project-1', testProjectPath);
+
+      expect(result.skippedFiles).toBe(1);
+      expect(result.indexedFiles).toBe(0);
+    });
+
+    test('インデックスをクリアすると次回は全ファイルを再インデックス化する', async () => {
+      await fs.writeFile(path.join(testProjectPath, 'a.ts'), 'export function a() {}');
+      await indexingService.indexProject('project-1', testProjectPath);
+
+      await indexingService.clearIndex('project-1');
+      const result = await indexingService.indexProject('project-1', testProjectPath);
+
+      expect(result.indexedFiles).toBe(1);
+      expect(result.skippedFiles).toBe(0);
+    });
+
+    test('インデックス形式が古いファイルは内容が同じでも再インデックス化する', async () => {
+      await fs.writeFile(path.join(testProjectPath, 'a.ts'), 'export function a() {}');
+      await indexingService.indexProject('project-1', testProjectPath);
+
+      // バージョン未記録（旧トークナイザー）のマニフェストを再現
+      for (const entry of await bm25Engine.getFileManifest('project-1')) {
+        await bm25Engine.upsertFileManifest({ ...entry, indexVersion: undefined });
+      }
+      const result = await indexingService.indexProject('project-1', testProjectPath);
+
+      expect(result.skippedFiles).toBe(0);
+      expect(result.reembeddedFiles).toBe(1);
+      expect((await bm25Engine.getFileManifest('project-1'))[0].indexVersion).toBe(
+        INDEX_FORMAT_VERSION
+      );
+    });
+
+    test('再インデックス化に失敗したファイルは次回スキップしない', async () => {
+      const fileA = path.join(testProjectPath, 'a.ts');
+      await fs.writeFile(fileA, 'export function a() {}');
+      await indexingService.indexProject('project-1', testProjectPath);
+
+      await fs.writeFile(fileA, 'export function changed() {}');
+      jest.spyOn(indexingService as any, 'indexFileInternal').mockResolvedValueOnce({
+        success: false,
+        filePath: fileA,
+        symbolsCount: 0,
+        vectorsCount: 0,
+        error: 'embed failed',
+      });
+      const failed = await indexingService.indexProject('project-1', testProjectPath);
+      expect(failed.failedFiles).toBe(1);
+
+      const retried = await indexingService.indexProject('project-1', testProjectPath);
+
+      expect(retried.skippedFiles).toBe(0);
+      expect(retried.indexedFiles).toBe(1);
+    });
+
+    test('同じディレクトリを別プロジェクトとして登録してもマニフェストを共有しない', async () => {
+      await fs.writeFile(path.join(testProjectPath, 'a.ts'), 'export function a() {}');
+      await indexingService.indexProject('project-1', testProjectPath);
+      await indexingService.indexProject('project-2', testProjectPath);
+
+      const result = await indexingService.indexProject('project-1', testProjectPath);
+
+      expect(result.skippedFiles).toBe(1);
+      expect(result.indexedFiles).toBe(0);
+    });
+
+    test('同じディレクトリを別プロジェクトとして登録しても互いのエントリを残す', async () => {
+      const fileA = path.join(testProjectPath, 'a.ts');
+      await fs.writeFile(fileA, 'export function a() {}');
+      await indexingService.indexProject('project-1', testProjectPath);
+      await indexingService.indexProject('project-2', testProjectPath);
+
+      await fs.writeFile(fileA, 'export function changed() {}');
+      await indexingService.updateFile(fileA, 'project-1');
+
+      const dummy = new Array(embeddingEngine.getDimension()).fill(0);
+      const vectors = await vectorStore.query('code_vectors', dummy, 100);
+      expect(vectors.map((r) => [r.id, r.metadata?.project_id]).sort()).toEqual([
+        [`project-1:${fileA}:function:changed`, 'project-1'],
+        [`project-2:${fileA}:function:a`, 'project-2'],
+      ]);
+      const documents = await bm25Engine.search('a', 100, { projectId: 'project-2' });
+      expect(documents.map((r) => r.documentId)).toEqual([`project-2:${fileA}:function:a`]);
+    });
+  });
+
   describe('進捗追跡', () => {
     test('進捗イベントが発火される', async () => {
       const progressEvents: any[] = [];
@@ -464,6 +823,24 @@ function hello() {
       expect(result.filePath).toBe(testFile);
     });
 
+    test('deleteFile: 削除済みの大きなファイルもメタデータで全エントリを削除できる', async () => {
+      const testFile = path.join(testProjectPath, 'large.ts');
+      const padding = '\n'.repeat(1500);
+      await fs.writeFile(testFile, `export const first = 1;${padding}export const last = 2;\n`);
+
+      await indexingService.indexFile(testFile, 'project-1');
+      await fs.unlink(testFile);
+
+      const result = await indexingService.deleteFile(testFile, 'project-1');
+      expect(result.success).toBe(true);
+
+      const remaining = await vectorStore.query('code_vectors', [], 100, { file_path: testFile });
+      expect(remaining).toHaveLength(0);
+      const bm25Results = await bm25Engine.search('last', 100);
+      const prefix = `project-1:${testFile}:`;
+      expect(bm25Results.filter((r) => r.documentId.startsWith(prefix))).toHaveLength(0);
+    });
+
     test('deleteFile: 存在しないファイルの削除もエラーにならない', async () => {
       const nonExistentFile = path.join(testProjectPath, 'non-existent.ts');
 
@@ -524,8 +901,8 @@ function hello() {
       await indexingService.indexFile(testFile, 'project-1');
 
       // スパイを設定してメソッド呼び出しを追跡
-      const vectorStoreSpy = jest.spyOn(vectorStore, 'delete');
-      const bm25Spy = jest.spyOn(bm25Engine, 'deleteDocument');
+      const vectorStoreSpy = jest.spyOn(vectorStore, 'deleteByFilter');
+      const bm25Spy = jest.spyOn(bm25Engine, 'deleteByPrefix');
 
       // ファイルを更新
       await fs.writeFile(testFile, 'export const updated = 2;');
@@ -582,6 +959,74 @@ function hello() {
     });
   });
 
+  describe('プロジェクトレジストリ', () => {
+    const createRestartedService = (): IndexingService =>
+      new IndexingService(
+        fileScanner,
+        symbolExtractor,
+        commentExtractor,
+        markdownParser,
+        docCodeLinker,
+        embeddingEngine,
+        vectorStore,
+        bm25Engine
+      );
+
+    test('再起動後もインデックス統計とオプションを復元できる', async () => {
+      await fs.writeFile(path.join(testProjectPath, 'a.ts'), 'export function a() {}');
+      await indexingService.indexProject('project-1', testProjectPath, {
+        languages: ['typescript'],
+      });
+      const before = await indexingService.getIndexStats('project-1');
+
+      const restarted = createRestartedService();
+      await restarted.loadIndexMetadata();
+      const after = await restarted.getIndexStats('project-1');
+
+      expect(after.rootPath).toBe(testProjectPath);
+      expect(after.status).toBe('indexed');
+      expect(after.totalFiles).toBe(before.totalFiles);
+      expect(after.totalVectors).toBe(before.totalVectors);
+      expect(after.lastIndexed.getTime()).toBe(before.lastIndexed.getTime());
+      expect(after.options).toEqual({ languages: ['typescript'] });
+    });
+
+    test('インデックス化中に終了したプロジェクトはerrorとして復元される', async () => {
+      await bm25Engine.upsertProject({
+        projectId: 'interrupted',
+        rootPath: '/tmp/interrupted',
+        options: {},
+        status: 'indexing',
+        lastIndexed: Date.now(),
+        totalFiles: 0,
+        totalSymbols: 0,
+        totalVectors: 0,
+      });
+
+      const restarted = createRestartedService();
+      await restarted.loadIndexMetadata();
+
+      expect((await restarted.getIndexStats('interrupted')).status).toBe('error');
+    });
+
+    test('前回のセッションのプロジェクトをクリアできる', async () => {
+      await fs.writeFile(path.join(testProjectPath, 'a.ts'), 'export function a() {}');
+      await indexingService.indexProject('project-1', testProjectPath);
+
+      const restarted = createRestartedService();
+      await restarted.loadIndexMetadata();
+      const result = await restarted.clearIndex('project-1');
+
+      expect(result.success).toBe(true);
+      expect(await restarted.getAllIndexStats()).toEqual([]);
+      expect(await bm25Engine.getProjects()).toEqual([]);
+
+      const dummy = new Array(embeddingEngine.getDimension()).fill(0);
+      expect(await vectorStore.query('code_vectors', dummy, 100)).toEqual([]);
+      expect(await bm25Engine.search('a', 10)).toEqual([]);
+    });
+  });
+
   describe('インデックスクリア', () => {
     test('特定プロジェクトのインデックスをクリアできる', async () => {
       await fs.writeFile(path.join(testProjectPath, 'clear-test.ts'), 'export const x = 1;');
@@ -596,6 +1041,23 @@ function hello() {
       expect(stats.totalFiles).toBe(0);
     });
 
+    test('マニフェストに記録のないBM25ドキュメントもプロジェクト単位で削除する', async () => {
+      await fs.writeFile(path.join(testProjectPath, 'a.ts'), 'export function a() {}');
+      await indexingService.indexProject('project-1', testProjectPath);
+      await bm25Engine.indexDocument('orphan.ts:function:orphan', 'orphan', {
+        projectId: 'project-1',
+      });
+      await bm25Engine.indexDocument('other.ts:function:other', 'other', {
+        projectId: 'project-2',
+      });
+
+      await indexingService.clearIndex('project-1');
+
+      expect((await bm25Engine.search('', 10)).map((r) => r.documentId)).toEqual([
+        'other.ts:function:other',
+      ]);
+    });
+
     test('全プロジェクトのインデックスをクリアできる', async () => {
       await fs.writeFile(path.join(testProjectPath, 'clear-all.ts'), 'export const x = 1;');
       await indexingService.indexProject('project-1', testProjectPath);
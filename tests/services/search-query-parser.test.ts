@@ -0,0 +1,170 @@
+/**
+ * search_codeのクエリ構文解析のテスト
+ */
+
+import { describe, it, expect } from '@jest/globals';
+import {
+  parseSearchQuery,
+  matchesSearchConditions,
+  globToRegExp,
+} from '../../src/services/search-query-parser';
+
+describe('parseSearchQuery', () => {
+  it('フィールド指定を検索フィルタに変換し、残りをキーワードと自然文に分ける', () => {
+    const parsed = parseSearchQuery('lang:ts type:function "upsert vectors" batch -test');
+
+    expect(parsed.filter).toEqual({ languages: ['typescript'], types: ['function'] });
+    expect(parsed.keywordQuery).toBe('"upsert vectors" batch -test');
+    expect(parsed.semanticQuery).toBe('upsert vectors batch');
+    expect(parsed.conditions).toEqual([]);
+  });
+
+  it('言語の略称と大文字・小文字を正規化する', () => {
+    expect(parseSearchQuery('language:Python lang:rs x').filter.languages).toEqual([
+      'python',
+      'rust',
+    ]);
+  });
+
+  it('pathのglobの固定部分を事前の絞り込みに使う', () => {
+    const parsed = parseSearchQuery('path:src/storage/** upsert');
+
+    expect(parsed.filter.pathPattern).toBe('src/storage/');
+    expect(parsed.conditions).toHaveLength(1);
+    expect(parsed.conditions[0]).toMatchObject({ field: 'path', negated: false });
+  });
+
+  it('nameのglobの固定部分を検索語に加える', () => {
+    const parsed = parseSearchQuery('name:parse* config');
+
+    expect(parsed.keywordQuery).toBe('parse config');
+    expect(parsed.semanticQuery).toBe('parse config');
+    expect(parsed.conditions[0]).toMatchObject({ field: 'name', negated: false });
+  });
+
+  it('ダブルクォートで囲んだフィールドの値を扱える', () => {
+    const parsed = parseSearchQuery('path:"my dir/**" handler');
+
+    expect(parsed.filter.pathPattern).toBe('my dir/');
+    expect(parsed.keywordQuery).toBe('handler');
+  });
+
+  it('除外したフィールド指定は取得後の除外条件にする', () => {
+    const parsed = parseSearchQuery('-path:tests/** -lang:js handler');
+
+    expect(parsed.filter).toEqual({});
+    expect(parsed.conditions.map((c) => [c.field, c.negated])).toEqual([
+      ['path', true],
+      ['lang', true],
+    ]);
+  });
+
+  it('未知のフィールドは通常の単語として扱う', () => {
+    const parsed = parseSearchQuery('std::vec http://example.com');
+
+    expect(parsed.filter).toEqual({});
+    expect(parsed.keywordQuery).toBe('std::vec http://example.com');
+  });
+
+  it('フィールド指定の直前のORを除く', () => {
+    expect(parseSearchQuery('yaml OR lang:py json').keywordQuery).toBe('yaml json');
+    expect(parseSearchQuery('yaml OR json').keywordQuery).toBe('yaml OR json');
+  });
+
+  it('フィールド指定のみの場合は自然文が空になる', () => {
+    expect(parseSearchQuery('lang:ts -deprecated').semanticQuery).toBe('');
+  });
+});
+
+describe('globToRegExp', () => {
+  it('パスの**は任意の階層、*は区切り内に一致する', () => {
+    const storage = globToRegExp('src/storage/**', { pathSegments: true });
+    expect(storage.test('/home/user/project/src/storage/bm25-engine.ts')).toBe(true);
+    expect(storage.test('/home/user/project/src/storage/sub/file.ts')).toBe(true);
+    expect(storage.test('/home/user/project/mysrc/storage/file.ts')).toBe(false);
+
+    const tests = globToRegExp('*.test.ts', { pathSegments: true });
+    expect(tests.test('tests/storage/bm25-engine.test.ts')).toBe(true);
+    expect(tests.test('src/storage/bm25-engine.ts')).toBe(false);
+  });
+
+  it('**/は0個以上のディレクトリに一致する', () => {
+    const pattern = globToRegExp('src/**/index.ts', { pathSegments: true });
+    expect(pattern.test('src/index.ts')).toBe(true);
+    expect(pattern.test('src/storage/index.ts')).toBe(true);
+  });
+
+  it('パス以外は大文字・小文字を区別せず全体に一致する', () => {
+    const pattern = globToRegExp('parse*');
+    expect(pattern.test('parseBM25Query')).toBe(true);
+    expect(pattern.test('ParseConfig')).toBe(true);
+    expect(pattern.test('reparse')).toBe(false);
+  });
+});
+
+describe('matchesSearchConditions', () => {
+  const result = {
+    id: '/repo/src/storage/bm25-query.ts:function:parseBM25Query',
+    metadata: {
+      file_path: '/repo/src/storage/bm25-query.ts',
+      name: 'parseBM25Query',
+      type: 'function',
+      language: 'typescript',
+    },
+  };
+
+  it('パスとシンボル名の条件をすべて満たす結果のみ一致する', () => {
+    const { conditions } = parseSearchQuery('path:src/storage/** name:parse* query');
+
+    expect(matchesSearchConditions(conditions, result)).toBe(true);
+    const renamed = { ...result, metadata: { ...result.metadata, name: 'loadConfig' } };
+    expect(matchesSearchConditions(conditions, renamed)).toBe(false);
+  });
+
+  it('同じフィールドの条件はいずれかに一致すればよい', () => {
+    const { conditions } = parseSearchQuery('path:lib/** path:src/** query');
+
+    expect(matchesSearchConditions(conditions, result)).toBe(true);
+  });
+
+  it('除外条件に一致する結果は除く', () => {
+    expect(matchesSearchConditions(parseSearchQuery('-lang:ts q').conditions, result)).toBe(false);
+    expect(matchesSearchConditions(parseSearchQuery('-path:tests/** q').conditions, result)).toBe(
+      true
+    );
+  });
+
+  it('メタデータがない結果はIDからパスとシンボル名を判定する', () => {
+    const { conditions } = parseSearchQuery('path:src/storage/** name:parse* query');
+
+    expect(
+      matchesSearchConditions(conditions, {
+        id: '/repo/src/storage/bm25-engine.ts:method:BM25Engine.parseQuery#2',
+      })
+    ).toBe(true);
+  });
+
+  it('パスや修飾名に:を含むIDも既知の種別で分解する', () => {
+    const { conditions } = parseSearchQuery('path:src/** type:function name:new query');
+
+    expect(
+      matchesSearchConditions(conditions, { id: 'C:/repo/src/lib.rs:function:Parser.new' })
+    ).toBe(true);
+    expect(
+      matchesSearchConditions(parseSearchQuery('-type:heading q').conditions, {
+        id: 'C:/repo/README.md:heading:Usage: search',
+      })
+    ).toBe(false);
+  });
+
+  it('IDの先頭のプロジェクトIDはパスに含めない', () => {
+    const { conditions } = parseSearchQuery('path:/repo/src/** query');
+
+    expect(
+      matchesSearchConditions(conditions, {
+        id: 'repo:/repo/src/lib.rs:function:Parser.new',
+        metadata: { project_id: 'repo' },
+      })
+    ).toBe(true);
+  });
+});
@@ -0,0 +1,55 @@
+/**
+ * ベクトルID生成のテスト
+ */
+
+import { describe, it, expect } from '@jest/globals';
+import {
+  VectorIdAllocator,
+  boundVectorId,
+  MAX_VECTOR_ID_BYTES,
+} from '../../src/services/vector-id';
+
+describe('VectorIdAllocator', () => {
+  it('種別と修飾名からIDを生成し、重複には出現順の番号を付ける', () => {
+    const allocator = new VectorIdAllocator('repo', '/repo/src/a.ts');
+
+    expect(allocator.allocate('method', 'Store.load')).toBe(
+      'repo:/repo/src/a.ts:method:Store.load'
+    );
+    expect(allocator.allocate('method', 'Store.load')).toBe(
+      'repo:/repo/src/a.ts:method:Store.load#2'
+    );
+    expect(allocator.allocate('code_block')).toBe('repo:/repo/src/a.ts:code_block');
+  });
+
+  it('同じファイルでもプロジェクトが異なれば別のIDにする', () => {
+    const first = new VectorIdAllocator('project-1', '/repo/src/a.ts');
+    const second = new VectorIdAllocator('project-2', '/repo/src/a.ts');
+
+    expect(first.allocate('function', 'main')).not.toBe(second.allocate('function', 'main'));
+  });
+
+  it('上限を超えるIDは上限以内に短縮し、元のIDごとに異なるIDにする', () => {
+    const allocator = new VectorIdAllocator('repo', `/repo/${'deep/'.repeat(80)}README.md`);
+
+    const first = allocator.allocate('heading', '長い見出し'.repeat(20));
+    const second = allocator.allocate('heading', '長い見出し'.repeat(21));
+
+    expect(Buffer.byteLength(first, 'utf8')).toBeLessThanOrEqual(MAX_VECTOR_ID_BYTES);
+    expect(Buffer.byteLength(second, 'utf8')).toBeLessThanOrEqual(MAX_VECTOR_ID_BYTES);
+    expect(first).not.toBe(second);
+    expect(first.startsWith('repo:/repo/deep/')).toBe(true);
+  });
+});
+
+describe('boundVectorId', () => {
+  it('上限以内のIDはそのまま返す', () => {
+    expect(boundVectorId('a.ts:function:main')).toBe('a.ts:function:main');
+  });
+
+  it('マルチバイト文字の途中で切らない', () => {
+    const id = boundVectorId('あ'.repeat(10), 20);
+
+    expect(id).toMatch(/^あ~[0-9a-f]{16}$/);
+  });
+});
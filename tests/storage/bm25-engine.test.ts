@@ -1,4 +1,5 @@
-import { BM25Engine } from '../../src/storage/bm25-engine';
+import { BM25Engine, MAX_CACHED_STATEMENTS } from '../../src/storage/bm25-engine';
+import Database from 'better-sqlite3';
 import * as fs from 'fs/promises';
 import * as path from 'path';
 
@@ -70,6 +71,35 @@ describe('BM25Engine', () => {
     });
   });
 
+  describe('コード向けトークナイザー', () => {
+    test('camelCaseの識別子が部分語のクエリで検索できる', async () => {
+      await engine.indexDocument('a.ts:function:getUserById', 'function getUserById(id)', {
+        tokenizer: 'code',
+      });
+      await engine.indexDocument('a.ts:function:listOrders', 'function listOrders()', {
+        tokenizer: 'code',
+      });
+
+      const results = await engine.search('user id', 10);
+      expect(results.map((r) => r.documentId)).toEqual(['a.ts:function:getUserById']);
+    });
+
+    test('snake_caseとcamelCaseの識別子が互いのクエリで検索できる', async () => {
+      await engine.indexDocument('a.rs:function:get_user_by_id', 'fn get_user_by_id()', {
+        tokenizer: 'code',
+      });
+
+      expect(await engine.search('getUserById', 10)).toHaveLength(1);
+      expect(await engine.search('get_user_by_id', 10)).toHaveLength(1);
+    });
+
+    test('textトークナイザーのドキュメントも複合語で検索できる', async () => {
+      await engine.indexDocument('README.md:heading:getUserById', 'Using getUserById');
+
+      expect(await engine.search('getUserById', 10)).toHaveLength(1);
+    });
+  });
+
   describe('ドキュメントのインデックス化', () => {
     test('単一ドキュメントをインデックス化できる', async () => {
       const docId = 'doc1';
@@ -175,6 +205,128 @@ describe('BM25Engine', () => {
       expect(typescript1!.score).toBeGreaterThan(0);
       expect(typescript4!.score).toBeGreaterThan(0);
     });
+
+    test('BM25の式どおりのスコアを返す', async () => {
+      // python: df=1, N=4, tf=1, doc3の長さ=6, 平均長=(4+4+6+6)/4=5
+      const idf = Math.log((4 - 1 + 0.5) / (1 + 0.5) + 1);
+      const expected = idf * ((1 * (1.5 + 1)) / (1 + 1.5 * (1 - 0.75 + 0.75 * (6 / 5))));
+
+      const results = await engine.search('Python', 10);
+      expect(results).toHaveLength(1);
+      expect(results[0].score).toBeCloseTo(expected, 10);
+    });
+
+    test('複数タームのスコアは各タームのスコアの合計になる', async () => {
+      const plain = new BM25Engine(testDbPath, { proximityWeight: 0 });
+      await plain.initialize();
+
+      const scoreOf = async (query: string): Promise<number> =>
+        (await plain.search(query, 10)).find((r) => r.documentId === 'doc1')!.score;
+
+      expect(await scoreOf('TypeScript JavaScript')).toBeCloseTo(
+        (await scoreOf('TypeScript')) + (await scoreOf('JavaScript')),
+        10
+      );
+      await plain.close();
+    });
+
+    test('インデックスの更新後はコーパス統計を再計算する', async () => {
+      expect((await engine.getDocumentStats()).totalDocuments).toBe(4);
+
+      await engine.indexDocument('doc5', 'Rust is a systems programming language');
+      expect((await engine.getDocumentStats()).totalDocuments).toBe(5);
+
+      await engine.deleteByPrefix('doc');
+      expect(await engine.getDocumentStats()).toEqual({
+        totalDocuments: 0,
+        averageDocumentLength: 0,
+      });
+    });
+  });
+
+  describe('フレーズ・近接検索', () => {
+    beforeEach(async () => {
+      await engine.indexDocument('phrase', 'wrap async function error handling');
+      // 同じ長さ・同じタームで並び順のみ異なるドキュメント
+      await engine.indexDocument('scattered', 'error async wrap handling function');
+    });
+
+    test('フレーズは連続して出現するドキュメントのみを返す', async () => {
+      const results = await engine.search('"async function error"', 10);
+
+      expect(results.map((r) => r.documentId)).toEqual(['phrase']);
+    });
+
+    test('フレーズとフレーズ外のタームを組み合わせられる', async () => {
+      expect(await engine.search('"async function" handling', 10)).toHaveLength(1);
+      expect(await engine.search('"function async" handling', 10)).toHaveLength(0);
+    });
+
+    test('識別子を含むフレーズはcodeトークナイザーのドキュメントにも一致する', async () => {
+      await engine.indexDocument('code', 'await this.userRepository.findById(id)', {
+        tokenizer: 'code',
+      });
+
+      const results = await engine.search('"userRepository findById"', 10);
+      expect(results.map((r) => r.documentId)).toEqual(['code']);
+    });
+
+    test('タームが近接して出現するドキュメントが上位になる', async () => {
+      const results = await engine.search('async function error', 10);
+
+      expect(results.map((r) => r.documentId)).toEqual(['phrase', 'scattered']);
+    });
+
+    test('proximityWeight=0で近接ブーストを無効にできる', async () => {
+      await engine.close();
+      engine = new BM25Engine(testDbPath, { proximityWeight: 0 });
+      await engine.initialize();
+
+      const boosted = new BM25Engine(testDbPath);
+      await boosted.initialize();
+
+      const plain = await engine.search('async function error', 10);
+      const withBoost = await boosted.search('async function error', 10);
+      const plainScore = plain.find((r) => r.documentId === 'phrase')!.score;
+      const boostedScore = withBoost.find((r) => r.documentId === 'phrase')!.score;
+
+      // 隣接しているため近接度1.0 → 1.5倍
+      expect(boostedScore).toBeCloseTo(plainScore * 1.5, 5);
+      await boosted.close();
+    });
+  });
+
+  describe('除外・OR検索', () => {
+    beforeEach(async () => {
+      await engine.indexDocument('parser', 'parse config file');
+      await engine.indexDocument('parser-test', 'parse config test fixture');
+      await engine.indexDocument('loader', 'load yaml settings');
+    });
+
+    test('除外したタームを含むドキュメントを返さない', async () => {
+      const results = await engine.search('parse config -test', 10);
+
+      expect(results.map((r) => r.documentId)).toEqual(['parser']);
+    });
+
+    test('除外したフレーズを含むドキュメントを返さない', async () => {
+      const results = await engine.search('config -"test fixture"', 10);
+
+      expect(results.map((r) => r.documentId)).toEqual(['parser']);
+    });
+
+    test('ORで連結したいずれかを含むドキュメントのみ返す', async () => {
+      const results = await engine.search('yaml OR fixture', 10);
+
+      expect(results.map((r) => r.documentId).sort()).toEqual(['loader', 'parser-test']);
+    });
+
+    test('filterDocumentsで任意のドキュメントに同じ条件を適用できる', async () => {
+      const ids = ['loader', 'parser', 'parser-test'];
+
+      expect(await engine.filterDocuments(ids, 'settings -test')).toEqual(['loader', 'parser']);
+      expect(await engine.filterDocuments(ids, 'settings')).toEqual(ids);
+    });
   });
 
   describe('転置インデックス', () => {
@@ -211,6 +363,68 @@ describe('BM25Engine', () => {
       expect(index?.positions).toContain(1); // 0-indexed position
       expect(index?.positions).toContain(3);
     });
+
+    test('位置情報はBLOBとして保存される', async () => {
+      await engine.indexDocument('doc1', 'alpha beta alpha');
+      await engine.close();
+
+      const db = new Database(testDbPath);
+      const row = db
+        .prepare(`SELECT typeof(positions) AS kind FROM inverted_index WHERE term = 'alpha'`)
+        .get() as { kind: string };
+      db.close();
+      expect(row.kind).toBe('blob');
+
+      engine = new BM25Engine(testDbPath);
+      await engine.initialize();
+      expect((await engine.getInvertedIndex('alpha'))?.positions).toEqual([0, 2]);
+    });
+
+    test('位置情報をJSONで保存していた旧スキーマのデータベースを移行できる', async () => {
+      await engine.close();
+      await fs.unlink(testDbPath);
+
+      const legacy = new Database(testDbPath);
+      legacy.exec(`
+        CREATE TABLE inverted_index (
+          term TEXT NOT NULL,
+          document_id TEXT NOT NULL,
+          frequency INTEGER NOT NULL,
+          positions TEXT NOT NULL,
+          PRIMARY KEY (term, document_id)
+        );
+        CREATE TABLE document_stats (document_id TEXT PRIMARY KEY, length INTEGER NOT NULL);
+      `);
+      const insert = legacy.prepare('INSERT INTO inverted_index VALUES (?, ?, ?, ?)');
+      insert.run('async', 'old:1', 1, '[0]');
+      insert.run('function', 'old:1', 2, '[1,5]');
+      insert.run('error', 'old:1', 1, '[2]');
+      legacy.prepare('INSERT INTO document_stats VALUES (?, ?)').run('old:1', 6);
+      legacy.close();
+
+      engine = new BM25Engine(testDbPath);
+      await engine.initialize();
+
+      expect(await engine.getInvertedIndex('function')).toEqual({
+        documentId: 'old:1',
+        frequency: 2,
+        positions: [1, 5],
+      });
+      // 移行後の出現位置でフレーズ判定できる
+      expect(await engine.search('"async function error"', 10)).toHaveLength(1);
+
+      await engine.close();
+      const db = new Database(testDbPath);
+      const columns = db.prepare('PRAGMA table_info(inverted_index)').all() as Array<{
+        name: string;
+        type: string;
+      }>;
+      db.close();
+      expect(columns.find((column) => column.name === 'positions')?.type).toBe('BLOB');
+
+      engine = new BM25Engine(testDbPath);
+      await engine.initialize();
+    });
   });
 
   describe('ドキュメント削除', () => {
@@ -240,6 +454,84 @@ describe('BM25Engine', () => {
     test('存在しないドキュメントの削除はエラーにならない', async () => {
       await expect(engine.deleteDocument('nonexistent')).resolves.not.toThrow();
     });
+
+    test('プレフィックスに一致するドキュメントをまとめて削除できる', async () => {
+      await engine.indexDocument('/src/a.ts:0', 'alpha function');
+      await engine.indexDocument('/src/a.ts:1500:comment', 'alpha comment');
+      await engine.indexDocument('/src/a.tsx:0', 'alpha component');
+
+      const deleted = await engine.deleteByPrefix('/src/a.ts:');
+
+      expect(deleted).toBe(2);
+      const results = await engine.search('alpha', 10);
+      expect(results.map((r) => r.documentId)).toEqual(['/src/a.tsx:0']);
+      expect(await engine.getInvertedIndex('comment')).toBeNull();
+    });
+
+    test('LIKEのワイルドカード文字を含むプレフィックスも文字通りに扱う', async () => {
+      await engine.indexDocument('/src/%_:0', 'wildcard');
+      await engine.indexDocument('/src/xy:0', 'plain');
+
+      expect(await engine.deleteByPrefix('/src/%')).toBe(1);
+      expect(await engine.deleteByPrefix('/src/_')).toBe(0);
+      expect((await engine.search('plain', 10)).map((r) => r.documentId)).toEqual(['/src/xy:0']);
+    });
+  });
+
+  describe('一括インデックス化・削除', () => {
+    test('複数ドキュメントを一括でインデックス化できる', async () => {
+      await engine.indexDocuments([
+        { documentId: 'doc1', content: 'TypeScript is great' },
+        {
+          documentId: 'doc2',
+          content: 'parseConfig loads settings',
+          attributes: { projectId: 'project-a', tokenizer: 'code' },
+        },
+      ]);
+
+      expect((await engine.getDocumentStats()).totalDocuments).toBe(2);
+      expect((await engine.search('config', 10, { projectId: 'project-a' }))[0].documentId).toBe(
+        'doc2'
+      );
+    });
+
+    test('既存のドキュメントは置き換え、同じIDは後のものが有効になる', async () => {
+      await engine.indexDocument('doc1', 'alpha beta');
+
+      await engine.indexDocuments([
+        { documentId: 'doc1', content: 'gamma' },
+        { documentId: 'doc1', content: 'delta' },
+      ]);
+
+      expect(await engine.search('alpha', 10)).toEqual([]);
+      expect(await engine.search('gamma', 10)).toEqual([]);
+      expect((await engine.search('delta', 10)).map((r) => r.documentId)).toEqual(['doc1']);
+      expect((await engine.getDocumentStats()).totalDocuments).toBe(1);
+    });
+
+    test('途中で失敗した場合はすべてロールバックされる', async () => {
+      await expect(
+        engine.indexDocuments([
+          { documentId: 'doc1', content: 'alpha' },
+          { documentId: null as unknown as string, content: 'beta' },
+        ])
+      ).rejects.toThrow();
+
+      expect((await engine.getDocumentStats()).totalDocuments).toBe(0);
+    });
+
+    test('複数ドキュメントを一括で削除し、削除した件数を返す', async () => {
+      await engine.indexDocuments([
+        { documentId: 'doc1', content: 'alpha' },
+        { documentId: 'doc2', content: 'alpha beta' },
+        { documentId: 'doc3', content: 'gamma' },
+      ]);
+
+      expect(await engine.deleteDocuments(['doc1', 'doc3', 'missing'])).toBe(2);
+      expect((await engine.search('alpha', 10)).map((r) => r.documentId)).toEqual(['doc2']);
+      expect(await engine.getInvertedIndex('gamma')).toBeNull();
+      expect(await engine.deleteDocuments([])).toBe(0);
+    });
   });
 
   describe('インデックスクリア', () => {
@@ -257,6 +549,306 @@ describe('BM25Engine', () => {
     });
   });
 
+  describe('プロジェクトによる絞り込み', () => {
+    test('projectIdを指定すると該当プロジェクトのドキュメントのみ検索する', async () => {
+      await engine.indexDocument('a:1', 'parse config', { projectId: 'project-a' });
+      await engine.indexDocument('b:1', 'parse config parse', { projectId: 'project-b' });
+      await engine.indexDocument('legacy:1', 'parse');
+
+      const scoped = await engine.search('parse', 10, { projectId: 'project-a' });
+      expect(scoped.map((r) => r.documentId)).toEqual(['a:1']);
+
+      const all = await engine.search('parse', 10);
+      expect(all).toHaveLength(3);
+    });
+
+    test('プロジェクトのドキュメントのみをまとめて削除できる', async () => {
+      await engine.indexDocument('a:1', 'parse config', { projectId: 'project-a' });
+      await engine.indexDocument('a:2', 'parse', { projectId: 'project-a' });
+      await engine.indexDocument('b:1', 'parse config', { projectId: 'project-b' });
+
+      expect(await engine.deleteByProject('project-a')).toBe(2);
+
+      expect((await engine.search('parse', 10)).map((r) => r.documentId)).toEqual(['b:1']);
+      expect((await engine.getDocumentStats()).totalDocuments).toBe(1);
+    });
+
+    test('プロジェクト内の1ファイルのドキュメントのみをまとめて削除できる', async () => {
+      await engine.indexDocument('a:/src/x.ts:1', 'parse', {
+        projectId: 'project-a',
+        filePath: '/src/x.ts',
+      });
+      await engine.indexDocument('a:/src/y.ts:1', 'parse', {
+        projectId: 'project-a',
+        filePath: '/src/y.ts',
+      });
+      await engine.indexDocument('b:/src/x.ts:1', 'parse', {
+        projectId: 'project-b',
+        filePath: '/src/x.ts',
+      });
+
+      expect(await engine.deleteByFile('project-a', '/src/x.ts')).toBe(1);
+
+      const remaining = (await engine.search('parse', 10)).map((r) => r.documentId);
+      expect(remaining.sort()).toEqual(['a:/src/y.ts:1', 'b:/src/x.ts:1']);
+    });
+
+    test('project_id列のない旧スキーマのデータベースを移行できる', async () => {
+      await engine.close();
+      await fs.unlink(testDbPath);
+
+      const legacy = new Database(testDbPath);
+      legacy.exec(`
+        CREATE TABLE document_stats (document_id TEXT PRIMARY KEY, length INTEGER NOT NULL)
+      `);
+      legacy.prepare('INSERT INTO document_stats VALUES (?, ?)').run('old:1', 3);
+      legacy.close();
+
+      engine = new BM25Engine(testDbPath);
+      await engine.initialize();
+      await engine.indexDocument('new:1', 'migrated content', { projectId: 'project-a' });
+
+      expect((await engine.getDocumentStats()).totalDocuments).toBe(2);
+      expect(await engine.search('migrated', 10, { projectId: 'project-a' })).toHaveLength(1);
+    });
+  });
+
+  describe('属性による絞り込み', () => {
+    beforeEach(async () => {
+      await engine.indexDocument('src/parser.ts:function:parse', 'parse tokens', {
+        projectId: 'p1',
+        language: 'TypeScript',
+        filePath: 'src/parser.ts',
+        fileType: 'ts',
+        symbolType: 'function',
+      });
+      await engine.indexDocument('src/parser.ts:comment:parse', 'parse the input tokens', {
+        projectId: 'p1',
+        language: 'typescript',
+        filePath: 'src/parser.ts',
+        fileType: 'ts',
+        symbolType: 'comment',
+      });
+      await engine.indexDocument('lib/parse.py:function:parse', 'parse parse parse', {
+        projectId: 'p1',
+        language: 'python',
+        filePath: 'lib/parse.py',
+        fileType: 'py',
+        symbolType: 'function',
+      });
+    });
+
+    test('言語で絞り込める（大文字・小文字を区別しない）', async () => {
+      const results = await engine.search('parse', 10, { languages: ['TypeScript'] });
+
+      expect(results.map((r) => r.documentId).sort()).toEqual([
+        'src/parser.ts:comment:parse',
+        'src/parser.ts:function:parse',
+      ]);
+    });
+
+    test('ファイルタイプ・パス・シンボル種別で絞り込める', async () => {
+      expect(
+        (await engine.search('parse', 10, { fileTypes: ['.py'] })).map((r) => r.documentId)
+      ).toEqual(['lib/parse.py:function:parse']);
+
+      expect(
+        (await engine.search('parse', 10, { pathPattern: 'src/' })).map((r) => r.documentId)
+      ).toHaveLength(2);
+
+      expect(
+        (await engine.search('parse', 10, { languages: ['typescript'], types: ['function'] })).map(
+          (r) => r.documentId
+        )
+      ).toEqual(['src/parser.ts:function:parse']);
+    });
+
+    test('絞り込み後の上位K件を返す（条件外の高スコア文書で枠を埋めない）', async () => {
+      const results = await engine.search('parse', 1, { languages: ['typescript'] });
+
+      expect(results).toHaveLength(1);
+      expect(results[0].documentId).toMatch(/^src\/parser\.ts:/);
+    });
+
+    test('パスパターンのLIKEワイルドカードは文字として扱う', async () => {
+      expect(await engine.search('parse', 10, { pathPattern: 'src_%' })).toHaveLength(0);
+    });
+
+    test('ドキュメント属性を取得できる（インデックスにないIDは含まない）', async () => {
+      const attributes = await engine.getDocumentAttributes([
+        'src/parser.ts:function:parse',
+        'unknown',
+      ]);
+
+      expect(attributes.size).toBe(1);
+      expect(attributes.get('src/parser.ts:function:parse')).toEqual({
+        projectId: 'p1',
+        language: 'typescript',
+        filePath: 'src/parser.ts',
+        fileType: 'ts',
+        symbolType: 'function',
+      });
+    });
+
+    test('検索語なしでフィルタに一致するドキュメントIDをID順に取得できる', async () => {
+      expect(await engine.findDocuments({ types: ['function'] }, 10)).toEqual([
+        'lib/parse.py:function:parse',
+        'src/parser.ts:function:parse',
+      ]);
+      expect(await engine.findDocuments({ languages: ['TypeScript'] }, 1)).toEqual([
+        'src/parser.ts:comment:parse',
+      ]);
+      expect(await engine.findDocuments({ projectId: 'p2' }, 10)).toEqual([]);
+    });
+
+    test('属性列のない旧スキーマのデータベースを移行できる', async () => {
+      await engine.close();
+      await fs.unlink(testDbPath);
+
+      const legacy = new Database(testDbPath);
+      legacy.exec(`
+        CREATE TABLE document_stats (
+          document_id TEXT PRIMARY KEY, length INTEGER NOT NULL, project_id TEXT
+        )
+      `);
+      legacy.close();
+
+      engine = new BM25Engine(testDbPath);
+      await engine.initialize();
+      await engine.indexDocument('a.go:function:main', 'main entry', {
+        language: 'go',
+        symbolType: 'function',
+      });
+
+      expect(await engine.search('main', 10, { languages: ['go'] })).toHaveLength(1);
+      expect(await engine.search('main', 10, { types: ['class'] })).toHaveLength(0);
+    });
+  });
+
+  describe('ファイルマニフェスト', () => {
+    const entry = {
+      filePath: '/src/a.ts',
+      projectId: 'p1',
+      size: 120,
+      mtime: 1700000000000.5,
+      hash: 'abc123',
+      symbolsCount: 2,
+      ids: ['/src/a.ts:function:a', '/src/a.ts:class:A'],
+      indexVersion: 1,
+    };
+
+    test('マニフェストは再オープン後も保持される', async () => {
+      await engine.upsertFileManifest(entry);
+      await engine.upsertFileManifest({ ...entry, filePath: '/lib/b.ts', projectId: 'p2' });
+
+      await engine.close();
+      engine = new BM25Engine(testDbPath);
+      await engine.initialize();
+
+      expect(await engine.getFileManifest('p1')).toEqual([entry]);
+    });
+
+    test('エントリを更新・削除できる', async () => {
+      await engine.upsertFileManifest(entry);
+      await engine.upsertFileManifest({ ...entry, hash: 'def456', ids: [] });
+
+      const [updated] = await engine.getFileManifest('p1');
+      expect(updated.hash).toBe('def456');
+      expect(updated.ids).toEqual([]);
+
+      await engine.deleteFileManifest('p1', '/src/a.ts');
+      expect(await engine.getFileManifest('p1')).toEqual([]);
+    });
+
+    test('同じファイルをプロジェクトごとに別のエントリとして保持する', async () => {
+      await engine.upsertFileManifest(entry);
+      await engine.upsertFileManifest({ ...entry, projectId: 'p2', hash: 'def456' });
+
+      expect(await engine.getFileManifest('p1')).toEqual([entry]);
+      expect((await engine.getFileManifest('p2'))[0].hash).toBe('def456');
+
+      await engine.deleteFileManifest('p2', '/src/a.ts');
+      expect(await engine.getFileManifest('p1')).toEqual([entry]);
+      expect(await engine.getFileManifest('p2')).toEqual([]);
+    });
+
+    test('ファイルパスのみを主キーとする旧スキーマのマニフェストを移行する', async () => {
+      await engine.close();
+      await fs.unlink(testDbPath);
+
+      const legacy = new Database(testDbPath);
+      legacy.exec(`
+        CREATE TABLE file_manifest (
+          file_path TEXT PRIMARY KEY, project_id TEXT NOT NULL, size INTEGER NOT NULL,
+          mtime REAL NOT NULL, hash TEXT NOT NULL, symbols_count INTEGER NOT NULL,
+          ids TEXT NOT NULL
+        )
+      `);
+      legacy
+        .prepare(`INSERT INTO file_manifest VALUES (?, ?, ?, ?, ?, ?, ?)`)
+        .run('/src/a.ts', 'p1', 120, entry.mtime, 'abc123', 2, JSON.stringify(entry.ids));
+      legacy.close();
+
+      engine = new BM25Engine(testDbPath);
+      await engine.initialize();
+      await engine.upsertFileManifest({ ...entry, projectId: 'p2' });
+
+      expect(await engine.getFileManifest('p1')).toEqual([{ ...entry, indexVersion: 0 }]);
+      expect(await engine.getFileManifest('p2')).toEqual([{ ...entry, projectId: 'p2' }]);
+    });
+
+    test('インデックス形式のバージョンが未指定のエントリは0として保存する', async () => {
+      await engine.upsertFileManifest({ ...entry, indexVersion: undefined });
+
+      expect((await engine.getFileManifest('p1'))[0].indexVersion).toBe(0);
+    });
+
+    test('プロジェクト単位でクリアできる', async () => {
+      await engine.upsertFileManifest(entry);
+      await engine.upsertFileManifest({ ...entry, filePath: '/lib/b.ts', projectId: 'p2' });
+
+      await engine.clearFileManifest('p1');
+
+      expect(await engine.getFileManifest('p1')).toEqual([]);
+      expect(await engine.getFileManifest('p2')).toHaveLength(1);
+    });
+  });
+
+  describe('プロジェクトレジストリ', () => {
+    const record = {
+      projectId: '/work/app',
+      rootPath: '/work/app',
+      options: { languages: ['typescript'], excludePatterns: ['dist/**'] },
+      status: 'indexed',
+      lastIndexed: 1700000000000,
+      totalFiles: 10,
+      totalSymbols: 42,
+      totalVectors: 50,
+    };
+
+    test('プロジェクトは再オープン後も保持される', async () => {
+      await engine.upsertProject(record);
+
+      await engine.close();
+      engine = new BM25Engine(testDbPath);
+      await engine.initialize();
+
+      expect(await engine.getProjects()).toEqual([record]);
+    });
+
+    test('プロジェクトを更新・削除できる', async () => {
+      await engine.upsertProject(record);
+      await engine.upsertProject({ ...record, status: 'error', totalFiles: 0 });
+
+      const [updated] = await engine.getProjects();
+      expect(updated.status).toBe('error');
+      expect(updated.totalFiles).toBe(0);
+
+      await engine.deleteProject('/work/app');
+      expect(await engine.getProjects()).toEqual([]);
+    });
+  });
+
   describe('BM25パラメータ', () => {
     test('カスタムパラメータでエンジンを初期化できる', async () => {
       await engine.close();
@@ -280,6 +872,23 @@ describe('BM25Engine', () => {
     });
   });
 
+  describe('プリペアドステートメントのキャッシュ', () => {
+    test('プレースホルダー数の異なる検索を繰り返してもキャッシュは上限を超えない', async () => {
+      await engine.indexDocument('doc1', 'parse config', { language: 'typescript' });
+
+      for (let count = 1; count <= MAX_CACHED_STATEMENTS + 20; count++) {
+        const languages = Array.from({ length: count }, (_, i) => `lang${i}`);
+        await engine.search('parse', 10, { languages: [...languages, 'typescript'] });
+      }
+
+      expect(engine.getCachedStatementCount()).toBeLessThanOrEqual(MAX_CACHED_STATEMENTS);
+      // 破棄されたステートメントは再作成される
+      expect(await engine.search('parse', 10, { languages: ['lang0', 'typescript'] })).toHaveLength(
+        1
+      );
+    });
+  });
+
   describe('エッジケース', () => {
     test('非常に長いドキュメントを処理できる', async () => {
       const longContent = Array(1000).fill('word').join(' ');
@@ -0,0 +1,123 @@
+/**
+ * BM25クエリ解析・位置判定のテスト
+ */
+
+import { describe, it, expect } from '@jest/globals';
+import {
+  parseBM25Query,
+  matchesPhrase,
+  proximityScore,
+  satisfiesConstraints,
+  constraintTerms,
+} from '../../src/storage/bm25-query';
+
+const positionsOf = (entries: Record<string, number[]>): Map<string, number[]> =>
+  new Map(Object.entries(entries));
+
+describe('parseBM25Query', () => {
+  it('ダブルクォートで囲んだ部分をフレーズとして抽出する', () => {
+    const parsed = parseBM25Query('"async function" error');
+
+    expect(parsed.required).toEqual([[[['async'], ['function']]]]);
+    expect(parsed.excluded).toEqual([]);
+    expect(parsed.terms).toEqual(['async', 'function', 'error']);
+    expect(parsed.groups).toEqual([['async'], ['function'], ['error']]);
+  });
+
+  it('識別子の複合語と部分語は同じグループにまとめる', () => {
+    const parsed = parseBM25Query('getUser id');
+
+    expect(parsed.groups).toEqual([['getuser', 'get', 'user'], ['id']]);
+  });
+
+  it('対応しないダブルクォートは区切り文字として扱う', () => {
+    const parsed = parseBM25Query('parse "config');
+
+    expect(parsed.required).toEqual([]);
+    expect(parsed.terms).toEqual(['parse', 'config']);
+  });
+
+  it('先頭に-を付けた単語・フレーズを除外条件として抽出する', () => {
+    const parsed = parseBM25Query('parse -test -"mock data" -getUser');
+
+    expect(parsed.excluded).toEqual([[['test']], [['mock'], ['data']], [['getuser']]]);
+    expect(parsed.terms).toEqual(['parse']);
+    expect(parsed.required).toEqual([]);
+  });
+
+  it('ORで連結した項目をいずれかが必須の条件にまとめる', () => {
+    const parsed = parseBM25Query('parse OR "load config" file');
+
+    expect(parsed.required).toEqual([[[['parse']], [['load'], ['config']]]]);
+    expect(parsed.terms).toEqual(['parse', 'load', 'config', 'file']);
+  });
+
+  it('先頭・末尾のORは通常の区切りとして扱う', () => {
+    expect(parseBM25Query('OR parse').required).toEqual([]);
+    expect(parseBM25Query('parse OR').required).toEqual([]);
+  });
+});
+
+describe('satisfiesConstraints', () => {
+  it('除外条件に一致するドキュメントは条件を満たさない', () => {
+    const parsed = parseBM25Query('parse -test');
+
+    expect(satisfiesConstraints(parsed, positionsOf({ parse: [0] }))).toBe(true);
+    expect(satisfiesConstraints(parsed, positionsOf({ parse: [0], test: [3] }))).toBe(false);
+  });
+
+  it('ORのいずれかが出現すれば条件を満たす', () => {
+    const parsed = parseBM25Query('yaml OR json');
+
+    expect(satisfiesConstraints(parsed, positionsOf({ json: [2] }))).toBe(true);
+    expect(satisfiesConstraints(parsed, positionsOf({ toml: [2] }))).toBe(false);
+  });
+
+  it('判定に必要なタームを重複なく返す', () => {
+    const parsed = parseBM25Query('"load config" OR config -test');
+
+    expect(constraintTerms(parsed)).toEqual(['load', 'config', 'test']);
+  });
+});
+
+describe('matchesPhrase', () => {
+  const phrase = [['async'], ['function'], ['error']];
+
+  it('タームが連続して出現する場合に一致する', () => {
+    const positions = positionsOf({ async: [3, 10], function: [11], error: [12] });
+    expect(matchesPhrase(phrase, positions)).toBe(true);
+  });
+
+  it('タームが離れて出現する場合は一致しない', () => {
+    const positions = positionsOf({ async: [0], function: [5], error: [6] });
+    expect(matchesPhrase(phrase, positions)).toBe(false);
+  });
+
+  it('いずれかのタームが出現しない場合は一致しない', () => {
+    expect(matchesPhrase(phrase, positionsOf({ async: [0], function: [1] }))).toBe(false);
+  });
+});
+
+describe('proximityScore', () => {
+  const groups = [['parse'], ['config'], ['file']];
+
+  it('隣接して出現する場合は1.0', () => {
+    const positions = positionsOf({ parse: [4], config: [5], file: [6] });
+    expect(proximityScore(groups, positions)).toBe(1);
+  });
+
+  it('出現グループ数を最小ウィンドウ幅で割った値を返す', () => {
+    // parse(0) ... config(9) file(10) / parse(20): 最小ウィンドウは9-20ではなく0-10
+    const positions = positionsOf({ parse: [0, 20], config: [9], file: [10] });
+    expect(proximityScore(groups, positions)).toBeCloseTo(3 / 11, 5);
+  });
+
+  it('出現しないグループは除外して評価する', () => {
+    const positions = positionsOf({ parse: [2], file: [3] });
+    expect(proximityScore(groups, positions)).toBe(1);
+  });
+
+  it('1グループしか出現しない場合は0', () => {
+    expect(proximityScore(groups, positionsOf({ parse: [0, 1] }))).toBe(0);
+  });
+});
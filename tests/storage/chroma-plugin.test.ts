@@ -0,0 +1,132 @@
+/**
+ * Chromaプラグインのテスト（インプロセスの代替サーバーを使用）
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from '@jest/globals';
+import { ChromaPlugin, buildChromaWhere } from '../../src/storage/chroma-plugin';
+import type { Vector } from '../../src/storage/types';
+import { startChromaStandIn } from '../__mocks__/chroma-stand-in';
+import type { ChromaStandIn } from '../__mocks__/chroma-stand-in';
+
+describe('buildChromaWhere', () => {
+  it('単一の条件はそのままwhere条件にする', () => {
+    expect(buildChromaWhere({ project_id: '/repo' })).toEqual({
+      where: { project_id: { $eq: '/repo' } },
+      exact: true,
+    });
+  });
+
+  it('複数の条件を$andで結合し、空配列は除く', () => {
+    expect(
+      buildChromaWhere({ project_id: '/repo', language: ['typescript', 'python'], type: [] })
+    ).toEqual({
+      where: {
+        $and: [{ project_id: { $eq: '/repo' } }, { language: { $in: ['typescript', 'python'] } }],
+      },
+      exact: true,
+    });
+  });
+
+  it('%を含む$likeはwhere条件に含めず、完全ではないと示す', () => {
+    expect(buildChromaWhere({ file_path: { $like: '%src/%' }, type: 'function' })).toEqual({
+      where: { type: { $eq: 'function' } },
+      exact: false,
+    });
+    expect(buildChromaWhere({ file_path: { $like: '/repo/a.ts' } }).where).toEqual({
+      file_path: { $eq: '/repo/a.ts' },
+    });
+  });
+});
+
+describe('ChromaPlugin', () => {
+  let server: ChromaStandIn;
+  let plugin: ChromaPlugin;
+  const collection = 'test_collection';
+
+  const vectors: Vector[] = [
+    {
+      id: '/repo/src/a.ts:class:Store',
+      vector: [1, 0, 0],
+      metadata: { file_path: '/repo/src/a.ts', language: 'typescript', implements: ['Plugin'] },
+    },
+    {
+      id: '/repo/tests/b.test.ts:function:load',
+      vector: [0, 1, 0],
+      metadata: { file_path: '/repo/tests/b.test.ts', language: 'typescript', scope: undefined },
+    },
+    {
+      id: '/repo/src/c.py:function:parse',
+      vector: [0.8, 0.6, 0],
+      metadata: { file_path: '/repo/src/c.py', language: 'python' },
+    },
+  ];
+
+  beforeEach(async () => {
+    server = await startChromaStandIn();
+    plugin = new ChromaPlugin({ initialDelay: 1, maxDelay: 1 });
+    await plugin.connect({ backend: 'chroma', config: { url: server.url } });
+    await plugin.createCollection(collection, 3);
+  });
+
+  afterEach(async () => {
+    await plugin.disconnect();
+    await server.close();
+  });
+
+  it('コサイン距離と次元数をメタデータに指定してコレクションを作成する', () => {
+    const create = server.requests.find((r) => r.method === 'POST' && r.body?.name === collection);
+    expect(create?.body.metadata).toEqual({ 'hnsw:space': 'cosine', dimension: 3 });
+  });
+
+  it('類似度の高い順に結果を返し、配列のメタデータを復元する', async () => {
+    await plugin.upsert(collection, vectors);
+
+    const results = await plugin.query(collection, [1, 0, 0], 2);
+
+    expect(results.map((r) => r.id)).toEqual([vectors[0].id, vectors[2].id]);
+    expect(results[0].score).toBeCloseTo(1);
+    expect(results[1].score).toBeCloseTo((0.8 + 1) / 2);
+    expect(results[0].metadata).toEqual(vectors[0].metadata);
+  });
+
+  it('メタデータフィルタをwhere条件として送る', async () => {
+    await plugin.upsert(collection, vectors);
+
+    const results = await plugin.query(collection, [1, 0, 0], 5, { language: 'python' });
+
+    expect(results.map((r) => r.id)).toEqual([vectors[2].id]);
+    const query = server.requests.find((r) => r.path.endsWith('/query'));
+    expect(query?.body.where).toEqual({ language: { $eq: 'python' } });
+  });
+
+  it('$likeに一致する結果がtopK件に満たない場合は候補を増やして再検索する', async () => {
+    // 条件外のベクトルがクエリの近くを多数占める状況を作る
+    const others = Array.from({ length: 40 }, (_, i) => ({
+      id: `/repo/lib/x${i}.ts:function:f`,
+      vector: [1, 0.01 * i, 0],
+      metadata: { file_path: `/repo/lib/x${i}.ts` },
+    }));
+    await plugin.upsert(collection, [
+      ...others,
+      {
+        id: '/repo/src/far.ts:function:f',
+        vector: [0, 0, 1],
+        metadata: { file_path: '/repo/src/far.ts' },
+      },
+    ]);
+
+    const results = await plugin.query(collection, [1, 0, 0], 1, {
+      file_path: { $like: '%/src/%' },
+    });
+
+    expect(results.map((r) => r.id)).toEqual(['/repo/src/far.ts:function:f']);
+    const queries = server.requests.filter((r) => r.path.endsWith('/query'));
+    expect(queries.map((r) => r.body.n_results)).toEqual([4, 16, 41]);
+  });
+
+  it('5xxはリトライする', async () => {
+    server.failNext(2);
+
+    await expect(plugin.getStats(collection)).resolves.toMatchObject({ dimension: 3 });
+  });
+});
@@ -0,0 +1,90 @@
+/**
+ * 組み込みベクターストアプラグインのテスト
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from '@jest/globals';
+import * as fs from 'fs/promises';
+import * as path from 'path';
+import { EmbeddedPlugin } from '../../src/storage/embedded-plugin';
+import { VectorStorePluginRegistry } from '../../src/storage/types';
+import type { VectorStoreConfig, Vector } from '../../src/storage/types';
+
+describe('EmbeddedPlugin', () => {
+  let plugin: EmbeddedPlugin;
+  let dbPath: string;
+  let config: VectorStoreConfig;
+  const collection = 'test_collection';
+
+  const vectors: Vector[] = [
+    {
+      id: '/repo/src/a.ts:function:parse',
+      vector: [1, 0, 0],
+      metadata: { file_path: '/repo/src/a.ts', language: 'typescript', type: 'function' },
+    },
+    {
+      id: '/repo/src/b.py:function:load',
+      vector: [0, 1, 0],
+      metadata: { file_path: '/repo/src/b.py', language: 'python', type: 'function' },
+    },
+    {
+      id: '/repo/src/c.ts:class:Store',
+      vector: [0.8, 0.6, 0],
+      metadata: { file_path: '/repo/src/c.ts', language: 'typescript', type: 'class' },
+    },
+  ];
+
+  beforeEach(async () => {
+    dbPath = path.join(process.cwd(), './tmp', `test-vectors-${Date.now()}.db`);
+    config = { backend: 'embedded', config: { path: dbPath } };
+    plugin = new EmbeddedPlugin();
+    await plugin.connect(config);
+    await plugin.createCollection(collection, 3);
+  });
+
+  afterEach(async () => {
+    await plugin.disconnect();
+    await fs.rm(dbPath, { force: true });
+    await fs.rm(`${dbPath}-wal`, { force: true });
+    await fs.rm(`${dbPath}-shm`, { force: true });
+  });
+
+  it('プラグイン名がembeddedでレジストリに登録できる', () => {
+    const registry = new VectorStorePluginRegistry();
+    registry.register(plugin);
+
+    expect(registry.get('embedded')).toBe(plugin);
+  });
+
+  it('再接続後もベクトルとコレクションが残っている', async () => {
+    await plugin.upsert(collection, vectors);
+    await plugin.disconnect();
+
+    const reopened = new EmbeddedPlugin();
+    await reopened.connect(config);
+    const results = await reopened.query(collection, [0, 1, 0], 1);
+    await reopened.disconnect();
+
+    expect(results[0].id).toBe(vectors[1].id);
+    expect(results[0].metadata).toEqual(vectors[1].metadata);
+    expect(results[0].score).toBeCloseTo(1);
+  });
+
+  it('削除したベクトルとコレクションは再接続後も削除されている', async () => {
+    await plugin.upsert(collection, vectors);
+    await plugin.delete(collection, [vectors[0].id]);
+    await plugin.deleteByFilter(collection, { language: 'python' });
+    await plugin.createCollection('removed', 3);
+    await plugin.deleteCollection('removed');
+    await plugin.disconnect();
+
+    const reopened = new EmbeddedPlugin();
+    await reopened.connect(config);
+    try {
+      const results = await reopened.query(collection, [1, 0, 0], 5);
+      expect(results.map((r) => r.id)).toEqual([vectors[2].id]);
+      await expect(reopened.getStats('removed')).rejects.toThrow('does not exist');
+    } finally {
+      await reopened.disconnect();
+    }
+  });
+});
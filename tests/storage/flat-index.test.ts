@@ -0,0 +1,59 @@
+/**
+ * フラットインデックスのテスト
+ *
+ * 検索・削除・統計の契約はプラグインの適合性テストで検証するため、
+ * ここではインデックス単体の振る舞いのみ確認する。
+ */
+
+import { describe, it, expect, beforeEach } from '@jest/globals';
+import { FlatIndex } from '../../src/storage/flat-index';
+import type { Vector } from '../../src/storage/types';
+
+describe('FlatIndex', () => {
+  let index: FlatIndex;
+
+  const vectors: Vector[] = [
+    { id: 'a', vector: [1, 0, 0], metadata: { language: 'typescript' } },
+    { id: 'b', vector: [0, 1, 0], metadata: { language: 'python' } },
+    { id: 'c', vector: [0.8, 0.6, 0] },
+  ];
+
+  beforeEach(() => {
+    index = new FlatIndex(3);
+    index.upsert(vectors);
+  });
+
+  it('呼び出し元の配列・メタデータとは別のコピーを保持する', () => {
+    const vector: Vector = { id: 'copy', vector: [0, 0, 1], metadata: { language: 'go' } };
+    index.upsert([vector]);
+    vector.vector[2] = 0;
+    vector.metadata!['language'] = 'rust';
+
+    const [result] = index.query([0, 0, 1], 1);
+    expect(result.id).toBe('copy');
+    expect(result.score).toBeCloseTo(1);
+    expect(result.metadata).toEqual({ language: 'go' });
+  });
+
+  it('検索結果のメタデータを変更してもインデックスに影響しない', () => {
+    const [result] = index.query([1, 0, 0], 1);
+    result.metadata!['language'] = 'rust';
+
+    expect(index.query([1, 0, 0], 1)[0].metadata).toEqual({ language: 'typescript' });
+  });
+
+  it('メタデータのないベクトルは空のメタデータとして扱う', () => {
+    expect(index.query([0.8, 0.6, 0], 1)[0]).toMatchObject({ id: 'c', metadata: {} });
+  });
+
+  it('フィルタに一致するIDを返す', () => {
+    expect(index.matchingIds({ language: ['python', 'go'] })).toEqual(['b']);
+  });
+
+  it('削除したベクトルは件数と検索結果から除かれる', () => {
+    index.delete(['a', 'unknown']);
+
+    expect(index.size).toBe(2);
+    expect(index.query([1, 0, 0], 5).map((r) => r.id)).not.toContain('a');
+  });
+});
@@ -0,0 +1,56 @@
+/**
+ * インメモリベクターストアプラグインのテスト
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from '@jest/globals';
+import { MemoryPlugin } from '../../src/storage/memory-plugin';
+import { VectorStorePluginRegistry } from '../../src/storage/types';
+import type { Vector } from '../../src/storage/types';
+
+describe('MemoryPlugin', () => {
+  let plugin: MemoryPlugin;
+  const collection = 'test_collection';
+
+  const vectors: Vector[] = [
+    {
+      id: '/repo/src/a.ts:function:parse',
+      vector: [1, 0, 0],
+      metadata: { file_path: '/repo/src/a.ts', language: 'typescript', type: 'function' },
+    },
+    {
+      id: '/repo/src/b.py:function:load',
+      vector: [0, 1, 0],
+      metadata: { file_path: '/repo/src/b.py', language: 'python', type: 'function' },
+    },
+    {
+      id: '/repo/src/c.ts:class:Store',
+      vector: [0.8, 0.6, 0],
+      metadata: { file_path: '/repo/src/c.ts', language: 'typescript', type: 'class' },
+    },
+  ];
+
+  beforeEach(async () => {
+    plugin = new MemoryPlugin();
+    await plugin.connect({ backend: 'memory', config: {} });
+    await plugin.createCollection(collection, 3);
+  });
+
+  afterEach(async () => {
+    await plugin.disconnect();
+  });
+
+  it('レジストリから名前で選択できる', () => {
+    const registry = new VectorStorePluginRegistry();
+    registry.register(plugin);
+
+    expect(registry.get('memory')).toBe(plugin);
+  });
+
+  it('切断するとデータは破棄される', async () => {
+    await plugin.upsert(collection, vectors);
+    await plugin.disconnect();
+    await plugin.connect({ backend: 'memory', config: {} });
+
+    await expect(plugin.getStats(collection)).rejects.toThrow('does not exist');
+  });
+});
@@ -0,0 +1,110 @@
+/**
+ * メタデータフィルタのテスト
+ */
+
+import { describe, it, expect } from '@jest/globals';
+import { matchesMetadataFilter, hasFilterConditions } from '../../src/storage/metadata-filter';
+import { buildFilterExpression, buildIdExpression } from '../../src/storage/milvus-plugin';
+
+describe('matchesMetadataFilter', () => {
+  const metadata = {
+    project_id: 'project-a',
+    file_path: '/work/app/src/services/parser.ts',
+    language: 'typescript',
+    line_start: 10,
+  };
+
+  it('スカラー値は完全一致で評価する', () => {
+    expect(matchesMetadataFilter(metadata, { project_id: 'project-a' })).toBe(true);
+    expect(matchesMetadataFilter(metadata, { project_id: 'project-b' })).toBe(false);
+    expect(matchesMetadataFilter(metadata, { line_start: 10 })).toBe(true);
+  });
+
+  it('配列はいずれかの値との一致で評価する', () => {
+    expect(matchesMetadataFilter(metadata, { language: ['python', 'typescript'] })).toBe(true);
+    expect(matchesMetadataFilter(metadata, { language: ['python', 'go'] })).toBe(false);
+  });
+
+  it('空配列は条件なしとして扱う', () => {
+    expect(matchesMetadataFilter(metadata, { language: [] })).toBe(true);
+    expect(matchesMetadataFilter(undefined, { language: [] })).toBe(true);
+  });
+
+  it('$likeは%を任意の文字列としてパターン一致で評価する', () => {
+    expect(matchesMetadataFilter(metadata, { file_path: { $like: '%src/services%' } })).toBe(true);
+    expect(matchesMetadataFilter(metadata, { file_path: { $like: '%.ts' } })).toBe(true);
+    expect(matchesMetadataFilter(metadata, { file_path: { $like: 'src/%' } })).toBe(false);
+    // 正規表現のメタ文字はリテラルとして扱う
+    expect(matchesMetadataFilter(metadata, { file_path: { $like: '%parser.t.' } })).toBe(false);
+  });
+
+  it('すべての条件をANDで評価する', () => {
+    expect(
+      matchesMetadataFilter(metadata, { project_id: 'project-a', language: ['python'] })
+    ).toBe(false);
+    expect(matchesMetadataFilter(metadata, {})).toBe(true);
+  });
+
+  it('メタデータに存在しないキーは一致しない', () => {
+    expect(matchesMetadataFilter(metadata, { type: 'function' })).toBe(false);
+    expect(matchesMetadataFilter(undefined, { type: 'function' })).toBe(false);
+  });
+});
+
+describe('hasFilterConditions', () => {
+  it('空配列以外の条件がある場合のみtrueを返す', () => {
+    expect(hasFilterConditions({})).toBe(false);
+    expect(hasFilterConditions({ language: [] })).toBe(false);
+    expect(hasFilterConditions({ language: [], type: 'function' })).toBe(true);
+    expect(hasFilterConditions({ file_path: { $like: '%' } })).toBe(true);
+  });
+});
+
+describe('buildFilterExpression', () => {
+  const scalarFields = new Set(['project_id', 'file_path', 'language', 'type']);
+
+  it('スカラーフィールドは直接参照する', () => {
+    expect(buildFilterExpression({ project_id: 'project-a' }, scalarFields)).toBe(
+      'project_id == "project-a"'
+    );
+  });
+
+  it('配列はin式、$likeはlike式に変換する', () => {
+    expect(
+      buildFilterExpression(
+        {
+          language: ['typescript', 'python'],
+          file_path: { $like: '%src/services%' },
+          type: 'function',
+        },
+        scalarFields
+      )
+    ).toBe(
+      'language in ["typescript", "python"] && file_path like "%src/services%" && type == "function"'
+    );
+  });
+
+  it('スカラーフィールドでないキーはJSONフィールドを参照する', () => {
+    expect(buildFilterExpression({ line_start: 10, project_id: 'p' })).toBe(
+      'metadata["line_start"] == 10 && metadata["project_id"] == "p"'
+    );
+  });
+
+  it('文字列リテラルのダブルクォートとバックスラッシュをエスケープする', () => {
+    expect(buildFilterExpression({ file_path: 'C:\\src\\"a".ts' }, scalarFields)).toBe(
+      'file_path == "C:\\\\src\\\\\\"a\\".ts"'
+    );
+  });
+
+  it('空配列の条件は無視する', () => {
+    expect(buildFilterExpression({ language: [] }, scalarFields)).toBe('');
+  });
+});
+
+describe('buildIdExpression', () => {
+  it('見出しや修飾名を含むIDのダブルクォートとバックスラッシュをエスケープする', () => {
+    expect(buildIdExpression(['a.md:heading:Say "hi"', 'b.ts:function:C\\d'])).toBe(
+      'id in ["a.md:heading:Say \\"hi\\"", "b.ts:function:C\\\\d"]'
+    );
+  });
+});
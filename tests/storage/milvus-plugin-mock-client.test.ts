@@ -0,0 +1,65 @@
+/**
+ * Milvusプラグインのテスト（SDKのモックを使用）
+ *
+ * 契約のテストケースはvector-store-conformance.test.tsで実行するため、
+ * ここではMilvus固有の内容のみ確認する。
+ */
+
+import { describe, it, expect, beforeEach, afterEach, jest } from '@jest/globals';
+import { MilvusPlugin, SCALAR_METADATA_FIELDS } from '../../src/storage/milvus-plugin';
+import type { Vector } from '../../src/storage/types';
+
+jest.mock('@zilliz/milvus2-sdk-node', () =>
+  jest.requireActual('../__mocks__/mock-milvus-client')
+);
+
+describe('MilvusPlugin（SDKのモック）', () => {
+  let plugin: MilvusPlugin;
+  const collection = 'test_collection';
+
+  beforeEach(async () => {
+    plugin = new MilvusPlugin({ initialDelay: 1, maxDelay: 1 });
+    await plugin.connect({ backend: 'milvus', config: { address: 'localhost:19530' } });
+    await plugin.createCollection(collection, 3);
+  });
+
+  afterEach(async () => {
+    await plugin.disconnect();
+  });
+
+  it('最大長を超えるスカラーフィールドの値を持つベクトルは除外し、他は保存する', async () => {
+    const longPath = `/repo/${'a'.repeat(SCALAR_METADATA_FIELDS['file_path'])}.ts`;
+    const vectors: Vector[] = [
+      { id: 'long:function:a', vector: [1, 0, 0], metadata: { file_path: longPath } },
+      { id: 'ok:function:b', vector: [0, 1, 0], metadata: { file_path: '/repo/b.ts' } },
+    ];
+
+    await plugin.upsert(collection, vectors);
+
+    const results = await plugin.query(collection, [1, 0, 0], 5);
+    expect(results.map((r) => r.id)).toEqual(['ok:function:b']);
+  });
+
+  it('最大長はUTF-8のバイト数で判定する', async () => {
+    // 文字数は最大長以内だが、バイト数は最大長を超える
+    const projectId = 'あ'.repeat(Math.ceil(SCALAR_METADATA_FIELDS['project_id'] / 3));
+
+    await plugin.upsert(collection, [
+      { id: 'a:function:a', vector: [1, 0, 0], metadata: { project_id: projectId } },
+    ]);
+
+    expect((await plugin.getStats(collection)).vectorCount).toBe(0);
+  });
+
+  it('除外したベクトルと同じIDの既存のベクトルは削除する', async () => {
+    await plugin.upsert(collection, [
+      { id: 'a:function:a', vector: [1, 0, 0], metadata: { file_path: '/repo/a.ts' } },
+    ]);
+
+    await plugin.upsert(collection, [
+      { id: 'a:function:a', vector: [1, 0, 0], metadata: { language: 'x'.repeat(100) } },
+    ]);
+
+    expect((await plugin.getStats(collection)).vectorCount).toBe(0);
+  });
+});
@@ -0,0 +1,37 @@
+/**
+ * 出現位置のエンコードのテスト
+ */
+
+import { describe, it, expect } from '@jest/globals';
+import { encodePositions, decodePositions } from '../../src/storage/position-codec';
+
+describe('encodePositions / decodePositions', () => {
+  it('差分をvarintで連結する', () => {
+    expect([...encodePositions([3, 10, 200])]).toEqual([0x03, 0x07, 0xbe, 0x01]);
+  });
+
+  it('エンコードした位置を元に戻せる', () => {
+    const positions = [0, 0, 1, 127, 128, 16511, 16512, 2 ** 31, 2 ** 40];
+    expect(decodePositions(encodePositions(positions))).toEqual(positions);
+  });
+
+  it('空配列は空のバイナリになる', () => {
+    expect(encodePositions([]).length).toBe(0);
+    expect(decodePositions(new Uint8Array())).toEqual([]);
+  });
+
+  it('JSONより小さいサイズで保存できる', () => {
+    const positions = Array.from({ length: 100 }, (_, i) => 1000 + i * 3);
+    expect(encodePositions(positions).length).toBeLessThan(JSON.stringify(positions).length / 4);
+  });
+
+  it('降順・負の値・整数以外はエラーになる', () => {
+    expect(() => encodePositions([5, 3])).toThrow(RangeError);
+    expect(() => encodePositions([-1])).toThrow(RangeError);
+    expect(() => encodePositions([1.5])).toThrow(RangeError);
+  });
+
+  it('途中で終わっているvarintはエラーになる', () => {
+    expect(() => decodePositions(Uint8Array.from([0x03, 0x80]))).toThrow(RangeError);
+  });
+});
@@ -0,0 +1,131 @@
+/**
+ * Qdrantプラグインのテスト（インプロセスの代替サーバーを使用）
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from '@jest/globals';
+import { QdrantPlugin, buildQdrantFilter, toPointId } from '../../src/storage/qdrant-plugin';
+import type { Vector } from '../../src/storage/types';
+import { startQdrantStandIn } from '../__mocks__/qdrant-stand-in';
+import type { QdrantStandIn } from '../__mocks__/qdrant-stand-in';
+
+describe('buildQdrantFilter', () => {
+  it('完全一致・in・部分一致をペイロードの条件に変換する', () => {
+    expect(
+      buildQdrantFilter({
+        project_id: '/repo',
+        language: ['typescript', 'python'],
+        file_path: { $like: '%src/storage%' },
+        type: [],
+      })
+    ).toEqual({
+      filter: {
+        must: [
+          { key: 'metadata.project_id', match: { value: '/repo' } },
+          { key: 'metadata.language', match: { any: ['typescript', 'python'] } },
+          { key: 'metadata.file_path', match: { text: 'src/storage' } },
+        ],
+      },
+      exact: true,
+    });
+  });
+
+  it('部分文字列一致で表せない$likeは固定部分で代用し、完全ではないと示す', () => {
+    expect(buildQdrantFilter({ file_path: { $like: '%.test.ts' } })).toEqual({
+      filter: { must: [{ key: 'metadata.file_path', match: { text: '.test.ts' } }] },
+      exact: false,
+    });
+  });
+
+  it('条件がない場合はフィルタを返さない', () => {
+    expect(buildQdrantFilter({ type: [] })).toEqual({ filter: undefined, exact: true });
+  });
+});
+
+describe('toPointId', () => {
+  it('ベクトルIDから決定的なUUIDを生成する', () => {
+    const id = toPointId('/repo/src/a.ts:function:parse');
+
+    expect(id).toMatch(/^[0-9a-f]{8}-[0-9a-f]{4}-5[0-9a-f]{3}-[89ab][0-9a-f]{3}-[0-9a-f]{12}$/);
+    expect(toPointId('/repo/src/a.ts:function:parse')).toBe(id);
+    expect(toPointId('/repo/src/a.ts:function:parse#2')).not.toBe(id);
+  });
+});
+
+describe('QdrantPlugin', () => {
+  let server: QdrantStandIn;
+  let plugin: QdrantPlugin;
+  const collection = 'test_collection';
+
+  const vectors: Vector[] = [
+    {
+      id: '/repo/src/a.ts:function:parse',
+      vector: [1, 0, 0],
+      metadata: { file_path: '/repo/src/a.ts', language: 'typescript', type: 'function' },
+    },
+    {
+      id: '/repo/tests/b.test.ts:function:load',
+      vector: [0, 1, 0],
+      metadata: { file_path: '/repo/tests/b.test.ts', language: 'typescript', type: 'function' },
+    },
+    {
+      id: '/repo/src/c.py:class:Store',
+      vector: [0.8, 0.6, 0],
+      metadata: { file_path: '/repo/src/c.py', language: 'python', type: 'class' },
+    },
+  ];
+
+  beforeEach(async () => {
+    server = await startQdrantStandIn();
+    plugin = new QdrantPlugin({ initialDelay: 1, maxDelay: 1 });
+    await plugin.connect({ backend: 'qdrant', config: { url: server.url } });
+    await plugin.createCollection(collection, 3);
+  });
+
+  afterEach(async () => {
+    await plugin.disconnect();
+    await server.close();
+  });
+
+  it('スキームのないaddressとtokenでも接続できる', async () => {
+    const other = new QdrantPlugin();
+    await other.connect({
+      backend: 'qdrant',
+      config: { address: server.url.replace('http://', ''), token: 'secret' },
+    });
+
+    expect((await other.getStats(collection)).dimension).toBe(3);
+  });
+
+  it('コレクション作成時にペイロードインデックスを作成する', () => {
+    expect(server.requests).toContain(`PUT /collections/${collection}/index`);
+  });
+
+  it('ペイロード付きでupsertし、類似度の高い順に元のIDとメタデータを返す', async () => {
+    await plugin.upsert(collection, vectors);
+
+    const results = await plugin.query(collection, [1, 0, 0], 2);
+
+    expect(results.map((r) => r.id)).toEqual([vectors[0].id, vectors[2].id]);
+    expect(results[0].score).toBeCloseTo(1);
+    expect(results[1].score).toBeCloseTo((0.8 + 1) / 2);
+    expect(results[0].metadata).toEqual(vectors[0].metadata);
+  });
+
+  it('完全に表現できない$likeは取得後に判定する', async () => {
+    await plugin.upsert(collection, vectors);
+
+    const results = await plugin.query(collection, [1, 0, 0], 5, {
+      file_path: { $like: '/repo/src/%' },
+    });
+
+    expect(results.map((r) => r.id)).toEqual([vectors[0].id, vectors[2].id]);
+  });
+
+  it('5xxはリトライし、上限を超えるとエラーになる', async () => {
+    server.failNext(2);
+    await expect(plugin.getStats(collection)).resolves.toMatchObject({ dimension: 3 });
+
+    server.failNext(10);
+    await expect(plugin.getStats(collection)).rejects.toThrow('failed after 4 attempts');
+  });
+});
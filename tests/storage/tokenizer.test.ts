@@ -0,0 +1,75 @@
+/**
+ * トークナイザーのテスト
+ */
+
+import { describe, it, expect } from '@jest/globals';
+import { tokenize, splitIdentifier } from '../../src/storage/tokenizer';
+
+const terms = (text: string, type: 'text' | 'code'): string[] =>
+  tokenize(text, type).map((token) => token.term);
+
+describe('splitIdentifier', () => {
+  it('camelCase・PascalCase・snake_caseを部分語に分割する', () => {
+    expect(splitIdentifier('getUserById')).toEqual(['get', 'user', 'by', 'id']);
+    expect(splitIdentifier('get_user_by_id')).toEqual(['get', 'user', 'by', 'id']);
+    expect(splitIdentifier('HTTPServer')).toEqual(['http', 'server']);
+    expect(splitIdentifier('MAX_RETRY_COUNT')).toEqual(['max', 'retry', 'count']);
+    expect(splitIdentifier('sha256Hash')).toEqual(['sha256', 'hash']);
+  });
+});
+
+describe('tokenize', () => {
+  it('textは従来どおり記号で分割し、識別子は分割しない', () => {
+    expect(terms('calculateTotal(price)', 'text')).toEqual(['calculatetotal', 'price']);
+  });
+
+  it('codeは複合語と部分語の両方を出力し、部分語のストップワードを除外する', () => {
+    expect(terms('getUserById', 'code')).toEqual(['getuserbyid', 'get', 'user', 'id']);
+  });
+
+  it('camelCaseとsnake_caseの識別子は同じトークンになる', () => {
+    expect(terms('get_user_by_id', 'code')).toEqual(terms('getUserById', 'code'));
+  });
+
+  it('::・.・/で区切られたパスを要素ごとに分割する', () => {
+    expect(terms('std::collections::HashMap', 'code')).toEqual([
+      'std',
+      'collections',
+      'hashmap',
+      'hash',
+      'map',
+    ]);
+    expect(terms('src/services/indexingService.ts', 'code')).toEqual([
+      'src',
+      'services',
+      'indexingservice',
+      'indexing',
+      'service',
+      'ts',
+    ]);
+    expect(terms('this.userRepository.save', 'code')).toEqual([
+      'userrepository',
+      'user',
+      'repository',
+      'save',
+    ]);
+  });
+
+  it('複合語と部分語は同じ位置を持つ', () => {
+    expect(tokenize('async getUser', 'code')).toEqual([
+      { term: 'async', position: 0 },
+      { term: 'getuser', position: 1 },
+      { term: 'get', position: 1 },
+      { term: 'user', position: 1 },
+    ]);
+  });
+
+  it('日本語などの非ASCII文字を保持する', () => {
+    expect(terms('ユーザー取得 getUser', 'code')).toContain('ユーザー取得');
+  });
+
+  it('空文字列は空配列を返す', () => {
+    expect(tokenize('', 'code')).toEqual([]);
+    expect(tokenize('   ', 'text')).toEqual([]);
+  });
+});
@@ -0,0 +1,71 @@
+/**
+ * 各VectorStorePluginに適合性テストスイートを実行
+ *
+ * 外部サービスが必要なプラグインは、インプロセスの代替サーバー（Qdrant、Chroma）または
+ * SDKのモック（Milvus）に接続してプロトコルテストとして実行します。
+ */
+
+import { jest } from '@jest/globals';
+import * as fs from 'fs/promises';
+import * as path from 'path';
+import { MemoryPlugin } from '../../src/storage/memory-plugin';
+import { EmbeddedPlugin } from '../../src/storage/embedded-plugin';
+import { QdrantPlugin } from '../../src/storage/qdrant-plugin';
+import { ChromaPlugin } from '../../src/storage/chroma-plugin';
+import { MilvusPlugin } from '../../src/storage/milvus-plugin';
+import { startQdrantStandIn } from '../__mocks__/qdrant-stand-in';
+import { startChromaStandIn } from '../__mocks__/chroma-stand-in';
+import {
+  describeVectorStoreConformance,
+  describeVectorStoreProtocol,
+} from './vector-store-conformance';
+
+jest.mock('@zilliz/milvus2-sdk-node', () =>
+  jest.requireActual('../__mocks__/mock-milvus-client')
+);
+
+describeVectorStoreConformance('MemoryPlugin', async () => ({
+  plugin: new MemoryPlugin(),
+  config: { backend: 'memory', config: {} },
+}));
+
+let embeddedDbCount = 0;
+describeVectorStoreConformance('EmbeddedPlugin', async () => {
+  const dbPath = path.join(
+    process.cwd(),
+    './tmp',
+    `test-conformance-${process.pid}-${Date.now()}-${embeddedDbCount++}.db`
+  );
+  return {
+    plugin: new EmbeddedPlugin(),
+    config: { backend: 'embedded', config: { path: dbPath } },
+    cleanup: async () => {
+      await fs.rm(dbPath, { force: true });
+      await fs.rm(`${dbPath}-wal`, { force: true });
+      await fs.rm(`${dbPath}-shm`, { force: true });
+    },
+  };
+});
+
+describeVectorStoreProtocol('QdrantPlugin (stand-in server)', async () => {
+  const server = await startQdrantStandIn();
+  return {
+    plugin: new QdrantPlugin({ initialDelay: 1, maxDelay: 1 }),
+    config: { backend: 'qdrant', config: { url: server.url } },
+    cleanup: () => server.close(),
+  };
+});
+
+describeVectorStoreProtocol('ChromaPlugin (stand-in server)', async () => {
+  const server = await startChromaStandIn();
+  return {
+    plugin: new ChromaPlugin({ initialDelay: 1, maxDelay: 1 }),
+    config: { backend: 'chroma', config: { url: server.url } },
+    cleanup: () => server.close(),
+  };
+});
+
+describeVectorStoreProtocol('MilvusPlugin (mock client)', async () => ({
+  plugin: new MilvusPlugin({ initialDelay: 1, maxDelay: 1 }),
+  config: { backend: 'milvus', config: { address: 'localhost:19530' } },
+}));
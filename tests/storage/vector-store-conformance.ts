@@ -0,0 +1,349 @@
+/**
+ * VectorStorePluginの適合性テストスイート
+ *
+ * すべてのプラグインが守るべき契約（types.tsのVectorStorePlugin）を検証します。
+ * 新しいプラグインは`describeVectorStoreConformance`にプラグインの生成処理を渡して実行します。
+ * 実際のサービスの代わりにテスト用の代替サーバーやSDKのモックに接続する場合は、
+ * 同じテストケースを`describeVectorStoreProtocol`で実行します。
+ *
+ * @example
+ * ```typescript
+ * describeVectorStoreConformance('MemoryPlugin', async () => ({
+ *   plugin: new MemoryPlugin(),
+ *   config: { backend: 'memory', config: {} },
+ * }));
+ * ```
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from '@jest/globals';
+import type {
+  VectorStorePlugin,
+  VectorStoreConfig,
+  Vector,
+  MetadataFilter,
+} from '../../src/storage/types';
+
+/**
+ * 適合性テストの対象
+ */
+export interface ConformanceTarget {
+  /** 未接続のプラグイン */
+  plugin: VectorStorePlugin;
+  /** `connect`に渡す設定 */
+  config: VectorStoreConfig;
+  /** 切断後の後処理（一時ファイルや代替サーバーの停止など） */
+  cleanup?: () => Promise<void>;
+}
+
+/**
+ * テスト用のベクトル（次元数3）
+ */
+export const CONFORMANCE_VECTORS: Vector[] = [
+  {
+    id: '/repo/src/a.ts:function:parse',
+    vector: [1, 0, 0],
+    metadata: { file_path: '/repo/src/a.ts', language: 'typescript', type: 'function' },
+  },
+  {
+    id: '/repo/src/b.py:function:load',
+    vector: [0, 1, 0],
+    metadata: { file_path: '/repo/src/b.py', language: 'python', type: 'function' },
+  },
+  {
+    id: '/repo/src/c.ts:class:Store',
+    vector: [0.8, 0.6, 0],
+    metadata: { file_path: '/repo/src/c.ts', language: 'typescript', type: 'class' },
+  },
+  {
+    id: '/repo/tests/d.test.ts:function:run',
+    vector: [-1, 0, 0],
+    metadata: { file_path: '/repo/tests/d.test.ts', language: 'typescript', type: 'function' },
+  },
+];
+
+/**
+ * 適合性テストスイートを定義
+ * @param name スイート名（プラグイン名）
+ * @param createTarget テストごとに新しい対象を生成する関数
+ */
+export function describeVectorStoreConformance(
+  name: string,
+  createTarget: () => Promise<ConformanceTarget>
+): void {
+  describeContract(`VectorStorePlugin conformance: ${name}`, createTarget);
+}
+
+/**
+ * 適合性テストと同じテストケースをプロトコルテストとして定義
+ *
+ * 代替サーバーやSDKのモックはテスト用に実装したもので、実際のサービスの挙動をすべて
+ * 再現するものではありません。そのため、プラグインのリクエストの組み立てとレスポンスの
+ * 変換の検証として扱い、実際のサービスに対する適合性テストとは区別します。
+ *
+ * @param name スイート名（プラグイン名と接続先）
+ * @param createTarget テストごとに新しい対象を生成する関数
+ */
+export function describeVectorStoreProtocol(
+  name: string,
+  createTarget: () => Promise<ConformanceTarget>
+): void {
+  describeContract(`VectorStorePlugin protocol: ${name}`, createTarget);
+}
+
+/**
+ * 契約のテストケースを定義
+ */
+function describeContract(
+  title: string,
+  createTarget: () => Promise<ConformanceTarget>
+): void {
+  describe(title, () => {
+    const collection = 'conformance_collection';
+    const vectors = CONFORMANCE_VECTORS;
+    let target: ConformanceTarget;
+    let plugin: VectorStorePlugin;
+
+    /** 検索結果のIDをスコア順に返す */
+    const ids = async (
+      queryVector: number[],
+      topK: number,
+      filter?: MetadataFilter
+    ): Promise<string[]> =>
+      (await plugin.query(collection, queryVector, topK, filter)).map((r) => r.id);
+
+    beforeEach(async () => {
+      target = await createTarget();
+      plugin = target.plugin;
+      await plugin.connect(target.config);
+      await plugin.createCollection(collection, 3);
+    });
+
+    afterEach(async () => {
+      await plugin.disconnect();
+      await target.cleanup?.();
+    });
+
+    describe('接続', () => {
+      it('接続前の操作はエラーになる', async () => {
+        const unconnected = await createTarget();
+        try {
+          await expect(unconnected.plugin.getStats(collection)).rejects.toThrow('not connected');
+        } finally {
+          await unconnected.cleanup?.();
+        }
+      });
+    });
+
+    describe('コレクション', () => {
+      it('同名のコレクションを作成するとエラーになる', async () => {
+        await expect(plugin.createCollection(collection, 3)).rejects.toThrow('already exists');
+      });
+
+      it('存在しないコレクションへの操作はエラーになる', async () => {
+        await expect(plugin.upsert('missing', vectors)).rejects.toThrow('does not exist');
+        await expect(plugin.query('missing', [1, 0, 0], 5)).rejects.toThrow('does not exist');
+        await expect(plugin.delete('missing', [vectors[0].id])).rejects.toThrow('does not exist');
+        await expect(plugin.deleteByFilter('missing', { language: 'python' })).rejects.toThrow(
+          'does not exist'
+        );
+        await expect(plugin.getStats('missing')).rejects.toThrow('does not exist');
+      });
+
+      it('存在しないコレクションの削除は何もしない', async () => {
+        await expect(plugin.deleteCollection('missing')).resolves.toBeUndefined();
+      });
+
+      it('削除したコレクションは同名で作り直せる', async () => {
+        await plugin.upsert(collection, vectors);
+        await plugin.deleteCollection(collection);
+        await plugin.createCollection(collection, 3);
+
+        expect((await plugin.getStats(collection)).vectorCount).toBe(0);
+      });
+    });
+
+    describe('検索', () => {
+      it('空のコレクションでは空の結果を返す', async () => {
+        expect(await plugin.query(collection, [1, 0, 0], 5)).toEqual([]);
+      });
+
+      it('スコアの高い順に最大topK件を返す', async () => {
+        await plugin.upsert(collection, vectors);
+
+        const results = await plugin.query(collection, [1, 0, 0], 3);
+
+        expect(results.map((r) => r.id)).toEqual([vectors[0].id, vectors[2].id, vectors[1].id]);
+        const scores = results.map((r) => r.score);
+        expect(scores).toEqual([...scores].sort((a, b) => b - a));
+      });
+
+      it('スコアは0-1の範囲で、同じ向きのベクトルは1になる', async () => {
+        await plugin.upsert(collection, vectors);
+
+        const results = await plugin.query(collection, [1, 0, 0], vectors.length);
+
+        expect(results).toHaveLength(vectors.length);
+        for (const result of results) {
+          expect(result.score).toBeGreaterThanOrEqual(0);
+          expect(result.score).toBeLessThanOrEqual(1);
+        }
+        expect(results[0].score).toBeCloseTo(1);
+        expect(results[results.length - 1].score).toBeCloseTo(0);
+      });
+
+      it('メタデータを返す', async () => {
+        await plugin.upsert(collection, vectors);
+
+        const [result] = await plugin.query(collection, [1, 0, 0], 1);
+
+        expect(result.metadata).toEqual(vectors[0].metadata);
+      });
+    });
+
+    describe('メタデータフィルタ', () => {
+      beforeEach(async () => {
+        await plugin.upsert(collection, vectors);
+      });
+
+      it('完全一致の条件を満たす結果のみ返す', async () => {
+        expect(await ids([1, 0, 0], 5, { language: 'python' })).toEqual([vectors[1].id]);
+      });
+
+      it('配列はいずれかの値に一致する結果を返す', async () => {
+        expect(await ids([1, 0, 0], 5, { type: ['class', 'interface'] })).toEqual([
+          vectors[2].id,
+        ]);
+      });
+
+      it('空配列は条件なしとして扱う', async () => {
+        expect(await ids([1, 0, 0], 5, { type: [] })).toHaveLength(vectors.length);
+        expect(await ids([1, 0, 0], 5, { type: [], language: 'python' })).toEqual([
+          vectors[1].id,
+        ]);
+      });
+
+      it('$likeは%を任意の文字列として一致させる', async () => {
+        expect(await ids([1, 0, 0], 5, { file_path: { $like: '/repo/src/%.ts' } })).toEqual([
+          vectors[0].id,
+          vectors[2].id,
+        ]);
+      });
+
+      it('複数の条件はすべて満たす結果のみ返す', async () => {
+        const filter: MetadataFilter = {
+          language: 'typescript',
+          type: 'function',
+          file_path: { $like: '%/src/%' },
+        };
+        expect(await ids([1, 0, 0], 5, filter)).toEqual([vectors[0].id]);
+      });
+
+      it('フィルタを満たす結果から上位K件を返す', async () => {
+        expect(await ids([1, 0, 0], 1, { language: 'python' })).toEqual([vectors[1].id]);
+      });
+
+      it('一致する結果がない場合は空の結果を返す', async () => {
+        expect(await ids([1, 0, 0], 5, { language: 'go' })).toEqual([]);
+      });
+    });
+
+    describe('upsert', () => {
+      it('同じIDのupsertはベクトルとメタデータを上書きする', async () => {
+        await plugin.upsert(collection, vectors);
+        await plugin.upsert(collection, [
+          { id: vectors[1].id, vector: [1, 0, 0], metadata: { language: 'go' } },
+        ]);
+
+        expect(await ids([1, 0, 0], 5, { language: 'python' })).toEqual([]);
+        const results = await plugin.query(collection, [1, 0, 0], 5, { language: 'go' });
+        expect(results.map((r) => r.id)).toEqual([vectors[1].id]);
+        expect(results[0].score).toBeCloseTo(1);
+        expect((await plugin.getStats(collection)).vectorCount).toBe(vectors.length);
+      });
+
+      it('次元数の異なるベクトルを含む場合はエラーになり何も書き込まない', async () => {
+        await expect(
+          plugin.upsert(collection, [vectors[0], { id: 'bad', vector: [1, 0] }])
+        ).rejects.toThrow(/dimension/i);
+
+        expect((await plugin.getStats(collection)).vectorCount).toBe(0);
+      });
+
+      it('次元数の異なるベクトルで検索するとエラーになる', async () => {
+        await plugin.upsert(collection, vectors);
+
+        await expect(plugin.query(collection, [1, 0], 1)).rejects.toThrow(/dimension/i);
+      });
+    });
+
+    describe('削除', () => {
+      beforeEach(async () => {
+        await plugin.upsert(collection, vectors);
+      });
+
+      it('指定したIDを削除し、存在しないIDは無視する', async () => {
+        await plugin.delete(collection, [vectors[0].id, 'unknown']);
+
+        const remaining = await ids([1, 0, 0], 5);
+        expect(remaining).not.toContain(vectors[0].id);
+        expect(remaining).toHaveLength(vectors.length - 1);
+      });
+
+      it('存在しないIDのみの削除は何もしない', async () => {
+        await expect(plugin.delete(collection, ['unknown'])).resolves.toBeUndefined();
+
+        expect((await plugin.getStats(collection)).vectorCount).toBe(vectors.length);
+      });
+
+      it('フィルタに一致するベクトルのみ削除する', async () => {
+        await plugin.deleteByFilter(collection, { file_path: { $like: '%.test.ts' } });
+        await plugin.deleteByFilter(collection, { language: 'python' });
+
+        expect(await ids([1, 0, 0], 5)).toEqual([vectors[0].id, vectors[2].id]);
+      });
+
+      it('条件のないフィルタでの削除はエラーになり何も削除しない', async () => {
+        await expect(plugin.deleteByFilter(collection, {})).rejects.toThrow(
+          'at least one filter condition'
+        );
+
+        expect((await plugin.getStats(collection)).vectorCount).toBe(vectors.length);
+      });
+
+      it('空配列の条件のみのフィルタでの削除はエラーになり何も削除しない', async () => {
+        await expect(plugin.deleteByFilter(collection, { language: [] })).rejects.toThrow(
+          'at least one filter condition'
+        );
+
+        expect((await plugin.getStats(collection)).vectorCount).toBe(vectors.length);
+      });
+    });
+
+    describe('統計情報', () => {
+      it('空のコレクションの統計情報を返す', async () => {
+        expect(await plugin.getStats(collection)).toEqual({
+          vectorCount: 0,
+          dimension: 3,
+          indexSize: 0,
+        });
+      });
+
+      it('ベクトル数・次元数・インデックスサイズを返す', async () => {
+        await plugin.upsert(collection, vectors);
+
+        expect(await plugin.getStats(collection)).toEqual({
+          vectorCount: vectors.length,
+          dimension: 3,
+          indexSize: vectors.length * 3 * 4,
+        });
+      });
+
+      it('削除後のベクトル数を返す', async () => {
+        await plugin.upsert(collection, vectors);
+        await plugin.delete(collection, [vectors[0].id]);
+
+        expect((await plugin.getStats(collection)).vectorCount).toBe(vectors.length - 1);
+      });
+    });
+  });
+}
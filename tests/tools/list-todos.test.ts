@@ -0,0 +1,166 @@
+/**
+ * list_todos MCPツールのテスト
+ *
+ * ベクターストアに格納されたコメントメタデータから
+ * マーカー付きコメントを絞り込んで一覧できることを確認する。
+ */
+
+import {
+  handleListTodos,
+  getInputSchemaJSON,
+  MAX_TODOS_PER_MARKER,
+} from '../../src/tools/list-todos-tool';
+import { MockVectorStore } from '../__mocks__/mock-vector-store';
+
+describe('list_todos MCP Tool', () => {
+  let vectorStore: MockVectorStore;
+
+  const comment = (
+    id: string,
+    metadata: Record<string, unknown>
+  ): { id: string; vector: number[]; metadata: Record<string, unknown> } => ({
+    id,
+    vector: new Array(384).fill(0),
+    metadata: { type: 'comment', language: 'typescript', ...metadata },
+  });
+
+  beforeEach(async () => {
+    vectorStore = new MockVectorStore();
+    await vectorStore.connect({ backend: 'mock', config: {} });
+    await vectorStore.createCollection('code_vectors', 384);
+    await vectorStore.upsert('code_vectors', [
+      comment('src/a.ts:10:comment', {
+        project_id: 'p1',
+        file_path: 'src/a.ts',
+        line_start: 10,
+        line_end: 10,
+        marker: 'TODO',
+        content: 'TODO: add caching',
+        associated_symbol: 'Service.load',
+      }),
+      comment('src/a.ts:2:comment', {
+        project_id: 'p1',
+        file_path: 'src/a.ts',
+        line_start: 2,
+        line_end: 2,
+        marker: 'FIXME',
+        content: 'FIXME: handle null',
+      }),
+      comment('lib/b.ts:5:comment', {
+        project_id: 'p2',
+        file_path: 'lib/b.ts',
+        line_start: 5,
+        line_end: 5,
+        marker: 'TODO',
+        content: 'TODO: remove',
+      }),
+      comment('src/a.ts:20:comment', {
+        project_id: 'p1',
+        file_path: 'src/a.ts',
+        line_start: 20,
+        line_end: 21,
+        content: 'plain comment',
+      }),
+      {
+        id: 'src/a.ts:10',
+        vector: new Array(384).fill(0),
+        metadata: { type: 'function', name: 'TODO', file_path: 'src/a.ts', line_start: 10 },
+      },
+    ]);
+  });
+
+  test('マーカー付きコメントのみをファイル・行順に返す', async () => {
+    const result = await handleListTodos({ limit: 100 }, vectorStore);
+
+    expect(result.todos.map((t) => `${t.filePath}:${t.lineStart}`)).toEqual([
+      'lib/b.ts:5',
+      'src/a.ts:2',
+      'src/a.ts:10',
+    ]);
+    expect(result.totalTodos).toBe(3);
+    expect(result.countsByMarker).toEqual({ TODO: 2, FIXME: 1 });
+    expect(result.todos[2].associatedSymbol).toBe('Service.load');
+    expect(result.todos[2].content).toBe('TODO: add caching');
+  });
+
+  test('マーカーで絞り込める', async () => {
+    const result = await handleListTodos({ marker: 'FIXME' as any, limit: 100 }, vectorStore);
+
+    expect(result.todos).toHaveLength(1);
+    expect(result.todos[0].marker).toBe('FIXME');
+  });
+
+  test('パスとプロジェクトで絞り込める', async () => {
+    const byPath = await handleListTodos({ pathPattern: 'lib/', limit: 100 }, vectorStore);
+    expect(byPath.todos.map((t) => t.filePath)).toEqual(['lib/b.ts']);
+
+    const byProject = await handleListTodos({ projectId: 'p1', limit: 100 }, vectorStore);
+    expect(byProject.todos.every((t) => t.projectId === 'p1')).toBe(true);
+    expect(byProject.totalTodos).toBe(2);
+  });
+
+  test('limitを超える分は切り詰め、総数は保持する', async () => {
+    const result = await handleListTodos({ limit: 1 }, vectorStore);
+
+    expect(result.todos).toHaveLength(1);
+    expect(result.totalTodos).toBe(3);
+  });
+
+  test('検索エラーは空の結果にせずエラーとして返す', async () => {
+    jest.spyOn(vectorStore, 'query').mockRejectedValue(new Error('connection refused'));
+
+    await expect(handleListTodos({ limit: 100 }, vectorStore)).rejects.toThrow(
+      'connection refused'
+    );
+  });
+
+  test('結果が検索件数に達した場合は件数を増やして再検索する', async () => {
+    const todos = Array.from({ length: 1500 }, (_, i) =>
+      comment(`src/many.ts:${i}:comment`, {
+        file_path: 'src/many.ts',
+        line_start: i,
+        line_end: i,
+        marker: 'TODO',
+        content: `TODO: ${i}`,
+      })
+    );
+    await vectorStore.upsert('code_vectors', todos);
+    const querySpy = jest.spyOn(vectorStore, 'query');
+
+    const result = await handleListTodos({ marker: 'TODO' as any, limit: 100 }, vectorStore);
+
+    expect(querySpy.mock.calls.map((call) => call[2])).toEqual([1000, 2000]);
+    expect(result.totalTodos).toBe(1502);
+    expect(result.truncated).toBe(false);
+    expect(result.truncatedMarkers).toEqual([]);
+  });
+
+  test('検索件数の上限に達したマーカーは切り詰めとして報告する', async () => {
+    jest.spyOn(vectorStore, 'query').mockImplementation(async (_collection, _vector, topK) =>
+      Array.from({ length: topK }, (_, i) => ({
+        id: `src/many.ts:${i}:comment`,
+        score: 0.9,
+        metadata: { type: 'comment', marker: 'TODO', file_path: 'src/many.ts', line_start: i },
+      }))
+    );
+
+    const result = await handleListTodos({ marker: 'TODO' as any, limit: 10 }, vectorStore);
+
+    expect(result.totalTodos).toBe(MAX_TODOS_PER_MARKER);
+    expect(result.truncated).toBe(true);
+    expect(result.truncatedMarkers).toEqual(['TODO']);
+  });
+
+  test('不正なマーカーはバリデーションエラーになる', async () => {
+    await expect(
+      handleListTodos({ marker: 'LATER' as any, limit: 100 }, vectorStore)
+    ).rejects.toThrow();
+  });
+
+  test('JSON Schemaにマーカーの列挙値が含まれる', () => {
+    const schema = getInputSchemaJSON();
+    expect(schema.properties.marker.enum).toEqual(
+      expect.arrayContaining(['TODO', 'FIXME', 'HACK', 'BUG', 'XXX', 'NOTE'])
+    );
+  });
+});
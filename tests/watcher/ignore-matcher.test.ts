@@ -0,0 +1,36 @@
+/**
+ * Ignore Matcher Tests
+ */
+
+import * as path from 'path';
+import { createIgnoreMatcher } from '../../src/watcher/ignore-matcher';
+import { DEFAULT_IGNORE_PATTERNS } from '../../src/watcher/file-watcher';
+
+describe('createIgnoreMatcher', () => {
+  const rootPath = path.resolve('/work/project');
+  const isIgnored = createIgnoreMatcher(rootPath, [...DEFAULT_IGNORE_PATTERNS, 'gen/', '*.tmp']);
+
+  test('デフォルトの除外パターンに一致するファイルを除外する', () => {
+    expect(isIgnored(path.join(rootPath, 'node_modules', 'pkg', 'index.ts'))).toBe(true);
+    expect(isIgnored(path.join(rootPath, '.git', 'HEAD'))).toBe(true);
+    expect(isIgnored(path.join(rootPath, 'logs', 'server.log'))).toBe(true);
+    expect(isIgnored(path.join(rootPath, 'src', 'index.ts'))).toBe(false);
+  });
+
+  test('.gitignore形式の追加パターンを解釈する', () => {
+    expect(isIgnored(path.join(rootPath, 'src', 'gen'), true)).toBe(true);
+    expect(isIgnored(path.join(rootPath, 'src', 'gen', 'out.ts'))).toBe(true);
+    expect(isIgnored(path.join(rootPath, 'src', 'cache.tmp'))).toBe(true);
+    expect(isIgnored(path.join(rootPath, 'src', 'generated.ts'))).toBe(false);
+  });
+
+  test('ルートからの相対パスも受け付ける', () => {
+    expect(isIgnored('dist/index.js')).toBe(true);
+    expect(isIgnored('src/index.ts')).toBe(false);
+  });
+
+  test('ルート自身とルート外のパスは除外しない', () => {
+    expect(isIgnored(rootPath, true)).toBe(false);
+    expect(isIgnored(path.resolve(rootPath, '..', 'other', 'dist', 'a.js'))).toBe(false);
+  });
+});